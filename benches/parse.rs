@@ -0,0 +1,89 @@
+/*!
+Benchmarks of parsing and validating addresses of each shape the parser handles differently; run
+with `cargo bench`, and compare against a saved baseline with `--save-baseline` and `--baseline`.
+*/
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use email_address::{EmailAddress, Options, Profile};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Benchmarks
+// ------------------------------------------------------------------------------------------------
+
+fn parse(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    for (name, address) in ADDRESSES {
+        group.throughput(Throughput::Bytes(address.len() as u64));
+        group.bench_with_input(BenchmarkId::new("from_str", name), address, |b, address| {
+            b.iter(|| EmailAddress::from_str(black_box(address)))
+        });
+        group.bench_with_input(BenchmarkId::new("is_valid", name), address, |b, address| {
+            b.iter(|| EmailAddress::is_valid(black_box(address)))
+        });
+    }
+    group.finish();
+}
+
+fn profiles(c: &mut Criterion) {
+    let mut group = c.benchmark_group("profile");
+    let address = "john.smith@example.com";
+    for profile in [
+        Profile::Rfc5321,
+        Profile::Rfc5322,
+        Profile::Rfc6531,
+        Profile::X509,
+    ] {
+        let options = Options::from(profile);
+        group.bench_function(format!("{:?}", profile), |b| {
+            b.iter(|| EmailAddress::parse_with(black_box(address), &options))
+        });
+    }
+    group.finish();
+}
+
+fn worst_case(c: &mut Criterion) {
+    let mut group = c.benchmark_group("worst_case");
+    let options = Options::default().without_length_limits();
+    let inputs = [
+        (
+            "long_local_part",
+            format!("{}@example.com", "a".repeat(10_000)),
+        ),
+        (
+            "many_sub_domains",
+            format!("simon@{}com", "a.".repeat(5_000)),
+        ),
+        (
+            "many_quoted_pairs",
+            format!("\"{}\"@example.com", "\\\"".repeat(5_000)),
+        ),
+        ("no_separator", "a".repeat(10_000)),
+        ("late_error", format!("{} @example.com", "a".repeat(10_000))),
+    ];
+    for (name, address) in &inputs {
+        group.throughput(Throughput::Bytes(address.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), address, |b, address| {
+            b.iter(|| EmailAddress::parse_with(black_box(address), &options))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse, profiles, worst_case);
+criterion_main!(benches);
+
+// ------------------------------------------------------------------------------------------------
+
+const ADDRESSES: &[(&str, &str)] = &[
+    ("ascii", "john.smith@example.com"),
+    ("ascii_tagged", "user.name+tag+sorting@example.com"),
+    ("utf8_local_part", "用户@例子.广告"),
+    ("utf8_domain", "simon@bücher.example"),
+    ("quoted_local_part", "\"john..smith\"@example.com"),
+    ("quoted_with_escapes", "\"john\\\"smith\\\\\"@example.com"),
+    ("ipv4_literal", "simon@[192.168.0.1]"),
+    ("ipv6_literal", "simon@[IPv6:2001:db8::1]"),
+    ("angle_brackets", "<john.smith@example.com>"),
+    ("invalid", "john smith@example.com"),
+];
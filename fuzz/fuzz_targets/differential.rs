@@ -0,0 +1,28 @@
+//!
+//! Compare the forms of a valid address that should agree; the minimally quoted and normalized
+//! forms are still valid, normalizing is idempotent, and only the first forms differ in quoting.
+//!
+
+#![no_main]
+
+use email_address::{EmailAddress, LocalPartCase};
+use libfuzzer_sys::fuzz_target;
+use std::str::FromStr;
+
+fuzz_target!(|address: &str| {
+    let email = match EmailAddress::from_str(address) {
+        Ok(email) => email,
+        Err(_) => return,
+    };
+
+    let minimal = email.minimally_quoted();
+    assert_eq!(EmailAddress::from_str(minimal.as_str()), Ok(minimal.clone()));
+    assert_eq!(minimal.local_part_unescaped(), email.local_part_unescaped());
+    assert_eq!(minimal.domain(), email.domain());
+
+    for case in [LocalPartCase::Preserve, LocalPartCase::Lowercase] {
+        let normalized = email.normalized(case);
+        assert!(EmailAddress::is_valid(normalized.as_str()));
+        assert_eq!(normalized.normalized(case), normalized);
+    }
+});
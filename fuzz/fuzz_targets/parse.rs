@@ -0,0 +1,47 @@
+//!
+//! Parse arbitrary bytes with each profile, checking that parsing never panics and that every
+//! error span can be used to slice the input.
+//!
+
+#![no_main]
+
+use email_address::{EmailAddress, Options, Preset, Profile};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let options = [
+        Options::default(),
+        Options::from(Profile::Rfc5321),
+        Options::from(Profile::Rfc5322),
+        Options::from(Profile::Rfc6531),
+        Options::from(Profile::X509),
+        Options::from(Preset::Minimal),
+        Options::from(Preset::Strict),
+    ];
+    for options in &options {
+        let from_bytes = EmailAddress::parse_bytes_with(data, options);
+        let address = match std::str::from_utf8(data) {
+            Ok(address) => address,
+            Err(_) => {
+                assert!(from_bytes.is_err());
+                continue;
+            }
+        };
+        assert_eq!(from_bytes, EmailAddress::parse_with(address, options));
+        if EmailAddress::is_valid_ascii(address) {
+            assert!(EmailAddress::is_valid(address));
+        }
+        match EmailAddress::parse_spanned(address, options) {
+            Ok(_) => assert!(EmailAddress::validate_with(address, options).is_ok()),
+            Err(error) => {
+                let _ = &address[error.span()];
+                for suggestion in error.suggestions() {
+                    let _ = suggestion.apply(address);
+                }
+                assert!(!EmailAddress::validate_with(address, options)
+                    .unwrap_err()
+                    .is_empty());
+            }
+        }
+    }
+});
@@ -0,0 +1,50 @@
+//!
+//! Check that every valid address can be taken apart and put back together; its text parses to
+//! the same address, and its parts are exactly the text either side of the '@'. The minimally
+//! quoted and normalized forms of an address parsed with the RFC 5322 profile, which allows the
+//! obsolete syntax, must parse with the same options.
+//!
+
+#![no_main]
+
+use email_address::{
+    BorrowedEmailAddress, EmailAddress, LocalPartCase, Options, Profile, SharedEmailAddress,
+};
+use libfuzzer_sys::fuzz_target;
+use std::str::FromStr;
+
+fuzz_target!(|address: &str| {
+    let options = Options::from(Profile::Rfc5322);
+    if let Ok(email) = EmailAddress::parse_with(address, &options) {
+        for form in [
+            email.minimally_quoted(),
+            email.normalized(LocalPartCase::Preserve),
+        ] {
+            assert_eq!(
+                EmailAddress::parse_with(form.as_str(), &options),
+                Ok(form.clone())
+            );
+        }
+    }
+
+    let email = match EmailAddress::from_str(address) {
+        Ok(email) => email,
+        Err(_) => {
+            assert!(!EmailAddress::is_valid(address));
+            return;
+        }
+    };
+    assert!(EmailAddress::is_valid(address));
+    assert_eq!(
+        email.as_str(),
+        format!("{}@{}", email.local_part(), email.domain())
+    );
+    assert_eq!(EmailAddress::from_str(email.as_str()), Ok(email.clone()));
+
+    let borrowed = BorrowedEmailAddress::parse(address).unwrap();
+    assert_eq!(borrowed.as_str(), email.as_str());
+    assert_eq!(EmailAddress::from(borrowed), email);
+
+    let shared = SharedEmailAddress::from(email.clone());
+    assert_eq!(EmailAddress::from(&shared), email);
+});
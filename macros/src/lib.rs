@@ -0,0 +1,39 @@
+/*!
+The `email!` macro, re-exported by the `email_address` crate with the `macros` feature; use it
+from there, rather than depending on this crate directly.
+*/
+
+#![warn(
+    missing_debug_implementations,
+    missing_docs,
+    unused_extern_crates,
+    rust_2018_idioms
+)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use std::str::FromStr;
+use syn::{parse_macro_input, LitStr};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse a string literal as an `EmailAddress` at compile time, with the same rules as
+/// `FromStr::from_str`; an invalid address is a compile error, and a valid one expands to an
+/// `EmailAddress` that is constructed without parsing again, or any chance of failing.
+///
+#[proc_macro]
+pub fn email(input: TokenStream) -> TokenStream {
+    let literal = parse_macro_input!(input as LitStr);
+    match email_address::EmailAddress::from_str(&literal.value()) {
+        Ok(email) => {
+            let (local, domain) = (email.local_part(), email.domain());
+            quote!(::email_address::EmailAddress::__from_parts_unchecked(#local, #domain)).into()
+        }
+        Err(e) => syn::Error::new(literal.span(), format!("invalid email address: {}", e))
+            .to_compile_error()
+            .into(),
+    }
+}
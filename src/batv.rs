@@ -0,0 +1,172 @@
+/*!
+Bounce Address Tag Validation (BATV), in the `prvs` form of
+[draft-levine-smtp-batv-01](https://datatracker.ietf.org/doc/html/draft-levine-smtp-batv-01).
+
+A sender signs the return path of each message it sends, as `prvs=KDDDSSSSSS=user@example.com`;
+where `K` is the number of the key used, `DDD` the last three digits of the day number on which
+the address expires, and `SSSSSS` the first three octets, in hexadecimal, of the HMAC-SHA1 of
+`KDDD` followed by the original address. Bounces sent to a return path without a valid tag are
+then known to be backscatter. Signing and verification require the `batv` feature.
+*/
+
+use crate::EmailAddress;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The parts of a BATV `prvs` address, returned by `EmailAddress::batv`.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let email = EmailAddress::from_str("prvs=0123a1b2c3=simon@example.com").unwrap();
+/// let batv = email.batv().unwrap();
+///
+/// assert_eq!(batv.key_number(), 0);
+/// assert_eq!(batv.expiry_day(), 123);
+/// assert_eq!(batv.hash(), "a1b2c3");
+/// assert_eq!(batv.address().to_string(), "simon@example.com");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Batv {
+    key_number: u8,
+    expiry_day: u16,
+    hash: String,
+    address: EmailAddress,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn parse(email: &EmailAddress) -> Option<Batv> {
+    let (scheme, rest) = email.local_part().split_once('=')?;
+    if !scheme.eq_ignore_ascii_case(PRVS) {
+        return None;
+    }
+    let (tag, local) = rest.split_once('=')?;
+    if tag.len() != TAG_LENGTH || local.is_empty() || !tag.is_ascii() {
+        return None;
+    }
+    let (key_number, day, hash) = (&tag[..1], &tag[1..4], &tag[4..]);
+    if !day.chars().all(|c| c.is_ascii_digit()) || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some(Batv {
+        key_number: key_number.parse().ok()?,
+        expiry_day: day.parse().ok()?,
+        hash: hash.to_ascii_lowercase(),
+        address: EmailAddress::from_parts(local, email.domain()),
+    })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Batv {
+    /// Returns the number of the key used to sign the address, from 0 to 9.
+    pub fn key_number(&self) -> u8 {
+        self.key_number
+    }
+
+    ///
+    /// Returns the last three digits of the day number, the number of days since 1970-01-01,
+    /// on which the address expires.
+    ///
+    pub fn expiry_day(&self) -> u16 {
+        self.expiry_day
+    }
+
+    /// Returns the hash, as six lowercase hexadecimal digits.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Returns the original address, without the BATV tag.
+    pub fn address(&self) -> &EmailAddress {
+        &self.address
+    }
+
+    /// Returns the original address, without the BATV tag.
+    pub fn into_address(self) -> EmailAddress {
+        self.address
+    }
+
+    ///
+    /// Sign `address` with `secret`, the key numbered `key_number`, returning the `prvs`
+    /// address that expires on the day numbered `expiry_day`. Returns an error if the signed
+    /// address is not valid, as when its local part is too long.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@example.com").unwrap();
+    /// let signed = Batv::sign(&email, 0, 20_000, b"secret").unwrap();
+    /// let batv = signed.batv().unwrap();
+    ///
+    /// assert_eq!(batv.address(), &email);
+    /// assert!(batv.verify(b"secret", 19_995));
+    /// assert!(!batv.verify(b"secret", 20_001));
+    /// assert!(!batv.verify(b"other", 19_995));
+    /// ```
+    ///
+    #[cfg(feature = "batv")]
+    pub fn sign(
+        address: &EmailAddress,
+        key_number: u8,
+        expiry_day: u32,
+        secret: &[u8],
+    ) -> Result<EmailAddress, crate::Error> {
+        use std::str::FromStr;
+        let tag = format!("{}{:03}", key_number % 10, expiry_day % DAY_MODULUS);
+        let hash = hmac(secret, &tag, address);
+        EmailAddress::from_str(&format!("{}={}{}={}", PRVS, tag, hash, address))
+    }
+
+    ///
+    /// Returns `true` if the hash was made with `secret`, and the address has not expired on the
+    /// day numbered `today`. As only the last three digits of the expiry day are kept, an
+    /// address is only taken to be unexpired if it expires within `MAX_LIFETIME` days.
+    ///
+    #[cfg(feature = "batv")]
+    pub fn verify(&self, secret: &[u8], today: u32) -> bool {
+        let tag = format!("{}{:03}", self.key_number, self.expiry_day);
+        let remaining =
+            (u32::from(self.expiry_day) + DAY_MODULUS - today % DAY_MODULUS) % DAY_MODULUS;
+        remaining <= Self::MAX_LIFETIME && hmac(secret, &tag, &self.address) == self.hash
+    }
+
+    /// The greatest number of days before it expires that a signed address is accepted.
+    pub const MAX_LIFETIME: u32 = 30;
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "batv")]
+fn hmac(secret: &[u8], tag: &str, address: &EmailAddress) -> String {
+    use hmac::{Hmac, Mac};
+    let mut mac = Hmac::<sha1::Sha1>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(tag.as_bytes());
+    mac.update(address.to_string().as_bytes());
+    mac.finalize().into_bytes()[..3]
+        .iter()
+        .map(|octet| format!("{:02x}", octet))
+        .collect()
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const PRVS: &str = "prvs";
+
+const TAG_LENGTH: usize = 10;
+
+#[cfg(feature = "batv")]
+const DAY_MODULUS: u32 = 1000;
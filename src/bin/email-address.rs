@@ -0,0 +1,391 @@
+/*!
+A command-line tool for checking email addresses, built with the `cli` feature.
+
+Each subcommand takes addresses as arguments, or reads them from standard input one per line
+when there are none; `extract` instead reads free text, and prints each address found in it, and
+`clean` reads CSV or JSON Lines records, and writes only those with a valid address. The exit
+status is 1 if any address was invalid.
+*/
+
+use clap::{Parser, Subcommand, ValueEnum};
+use email_address::{EmailAddress, LocalPartCase, Options, Preset, Profile};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "email-address",
+    version,
+    about = "Validate and normalize email addresses"
+)]
+struct Cli {
+    /// The standard to validate addresses against.
+    #[arg(long, value_enum, global = true, conflicts_with = "preset")]
+    profile: Option<ProfileArg>,
+    /// A curated set of rules to validate addresses with.
+    #[arg(long, value_enum, global = true)]
+    preset: Option<PresetArg>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print each address with whether it is valid, and why not.
+    Validate { addresses: Vec<String> },
+    /// Print the normalized form of each address.
+    Normalize {
+        /// Lowercase the local part, as well as the domain.
+        #[arg(long)]
+        lowercase: bool,
+        addresses: Vec<String>,
+    },
+    /// Describe the parts of each address, or every error in it.
+    Explain { addresses: Vec<String> },
+    /// Print each valid address found in free text.
+    Extract,
+    /// Copy the records with a valid address, reporting each record that is dropped.
+    Clean {
+        /// The format of the input and output records.
+        #[arg(long, value_enum)]
+        format: Format,
+        /// The CSV column, or JSON field, that holds the address.
+        #[arg(long, default_value = "email")]
+        field: String,
+        /// Replace each address with its normalized form.
+        #[arg(long)]
+        normalize: bool,
+        /// Write the cleaned records to this file, rather than to standard output.
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// Read records from this file, rather than from standard input.
+        input: Option<PathBuf>,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    /// Comma-separated values, with a header row naming the columns.
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum ProfileArg {
+    Rfc5321,
+    Rfc5322,
+    Rfc6531,
+    X509,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum PresetArg {
+    Minimal,
+    Rfc,
+    Strict,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Main
+// ------------------------------------------------------------------------------------------------
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let options = match (cli.profile, cli.preset) {
+        (Some(profile), _) => Options::from(Profile::from(profile)),
+        (None, Some(preset)) => Options::from(Preset::from(preset)),
+        (None, None) => Options::default(),
+    };
+    let result = match cli.command {
+        Command::Validate { addresses } => for_each(addresses, &options, validate),
+        Command::Normalize {
+            lowercase,
+            addresses,
+        } => {
+            let case = if lowercase {
+                LocalPartCase::Lowercase
+            } else {
+                LocalPartCase::Preserve
+            };
+            for_each(addresses, &options, |address, options| {
+                normalize(address, options, case)
+            })
+        }
+        Command::Explain { addresses } => for_each(addresses, &options, explain),
+        Command::Extract => extract(&options),
+        Command::Clean {
+            format,
+            field,
+            normalize,
+            output,
+            input,
+        } => clean(format, &field, normalize, output, input, &options),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("email-address: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl From<ProfileArg> for Profile {
+    fn from(profile: ProfileArg) -> Self {
+        match profile {
+            ProfileArg::Rfc5321 => Profile::Rfc5321,
+            ProfileArg::Rfc5322 => Profile::Rfc5322,
+            ProfileArg::Rfc6531 => Profile::Rfc6531,
+            ProfileArg::X509 => Profile::X509,
+        }
+    }
+}
+
+impl From<PresetArg> for Preset {
+    fn from(preset: PresetArg) -> Self {
+        match preset {
+            PresetArg::Minimal => Preset::Minimal,
+            PresetArg::Rfc => Preset::Rfc,
+            PresetArg::Strict => Preset::Strict,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Call `f` with each of `addresses`, or each line of standard input if there are none,
+/// returning `true` if `f` returned `true` for all of them.
+///
+fn for_each<F>(addresses: Vec<String>, options: &Options, mut f: F) -> std::io::Result<bool>
+where
+    F: FnMut(&str, &Options) -> bool,
+{
+    let mut all_valid = true;
+    if addresses.is_empty() {
+        for line in std::io::stdin().lock().lines() {
+            let line = line?;
+            let line = line.trim_start_matches(BOM).trim_end_matches(CR);
+            if !line.is_empty() {
+                all_valid &= f(line, options);
+            }
+        }
+    } else {
+        for address in &addresses {
+            all_valid &= f(address, options);
+        }
+    }
+    Ok(all_valid)
+}
+
+fn validate(address: &str, options: &Options) -> bool {
+    match EmailAddress::parse_with(address, options) {
+        Ok(_) => {
+            println!("{}\tvalid", address);
+            true
+        }
+        Err(e) => {
+            println!("{}\tinvalid\t{}", address, e);
+            false
+        }
+    }
+}
+
+fn normalize(address: &str, options: &Options, case: LocalPartCase) -> bool {
+    match EmailAddress::parse_with(address, options) {
+        Ok(email) => {
+            println!("{}", email.normalized(case));
+            true
+        }
+        Err(e) => {
+            eprintln!("{}: {}", address, e);
+            false
+        }
+    }
+}
+
+fn explain(address: &str, options: &Options) -> bool {
+    println!("{}", address);
+    match EmailAddress::parse_with_diagnostics(address, options) {
+        Ok((email, warnings)) => {
+            println!(
+                "  local part: {} ({:?})",
+                email.local_part(),
+                email.local_part_kind()
+            );
+            println!("  domain:     {}", email.domain());
+            if let Some(tag) = email.tag() {
+                println!("  tag:        {}", tag);
+            }
+            for warning in warnings {
+                println!("  warning:    {}", warning);
+            }
+            true
+        }
+        Err(_) => {
+            if let Err(error) = EmailAddress::parse_spanned(address, options) {
+                let span = error.span();
+                println!(
+                    "{}{}",
+                    " ".repeat(address[..span.start].chars().count()),
+                    "^".repeat(address[span].chars().count().max(1))
+                );
+                for suggestion in error.suggestions() {
+                    println!("  help:       {}", suggestion);
+                }
+            }
+            if let Err(errors) = EmailAddress::validate_with(address, options) {
+                for error in errors {
+                    println!("  error:      {}", error);
+                }
+            }
+            false
+        }
+    }
+}
+
+///
+/// Print each valid address in the text read from standard input; candidates are the words
+/// that contain an '@', with any surrounding punctuation removed.
+///
+fn extract(options: &Options) -> std::io::Result<bool> {
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        for word in line.split(|c: char| c.is_whitespace() || DELIMITERS.contains(&c)) {
+            let word = word.trim_matches(|c: char| c.is_ascii_punctuation() && c != '"');
+            if word.contains('@') {
+                if let Ok(email) = EmailAddress::parse_with(word, options) {
+                    writeln!(stdout, "{}", email)?;
+                }
+            }
+        }
+    }
+    Ok(true)
+}
+
+///
+/// Copy the records read from `input` to `output` that have a valid address in `field`,
+/// reporting each record dropped, and why, on standard error.
+///
+fn clean(
+    format: Format,
+    field: &str,
+    normalize: bool,
+    output: Option<PathBuf>,
+    input: Option<PathBuf>,
+    options: &Options,
+) -> std::io::Result<bool> {
+    let input: Box<dyn BufRead> = match input {
+        Some(path) => Box::new(BufReader::new(File::open(path)?)),
+        None => Box::new(BufReader::new(std::io::stdin())),
+    };
+    let output: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+    let clean_address = |address: &str| {
+        EmailAddress::parse_with(address, options).map(|email| {
+            if normalize {
+                email.normalized(LocalPartCase::Preserve).to_string()
+            } else {
+                address.to_string()
+            }
+        })
+    };
+    let mut all_valid = true;
+    let mut report = |record: u64, message: String| {
+        eprintln!("record {}: {}", record, message);
+        all_valid = false;
+    };
+    match format {
+        Format::Csv => {
+            let mut reader = csv::Reader::from_reader(input);
+            let mut writer = csv::Writer::from_writer(output);
+            let headers = reader.headers()?.clone();
+            let column = headers.iter().position(|header| header == field);
+            let column = match column {
+                Some(column) => column,
+                None => {
+                    eprintln!("email-address: no column named {:?}", field);
+                    return Ok(false);
+                }
+            };
+            writer.write_record(&headers)?;
+            for (index, record) in reader.records().enumerate() {
+                let record = record?;
+                let address = record.get(column).unwrap_or_default();
+                match clean_address(address) {
+                    Ok(address) => {
+                        writer.write_record(record.iter().enumerate().map(|(i, value)| {
+                            if i == column {
+                                address.as_str()
+                            } else {
+                                value
+                            }
+                        }))?
+                    }
+                    Err(e) => report(index as u64 + 1, format!("{}: {}", address, e)),
+                }
+            }
+            writer.flush()?;
+        }
+        Format::Jsonl => {
+            let mut output = output;
+            for (index, line) in input.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let record = index as u64 + 1;
+                let mut value: serde_json::Value = match serde_json::from_str(&line) {
+                    Ok(value) => value,
+                    Err(e) => {
+                        report(record, format!("invalid JSON: {}", e));
+                        continue;
+                    }
+                };
+                let address = match value.get(field).and_then(|address| address.as_str()) {
+                    Some(address) => address.to_string(),
+                    None => {
+                        report(record, format!("no string field named {:?}", field));
+                        continue;
+                    }
+                };
+                match clean_address(&address) {
+                    Ok(address) => {
+                        value[field] = serde_json::Value::String(address);
+                        serde_json::to_writer(&mut output, &value)?;
+                        writeln!(output)?;
+                    }
+                    Err(e) => report(record, format!("{}: {}", address, e)),
+                }
+            }
+            output.flush()?;
+        }
+    }
+    Ok(all_valid)
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const BOM: char = '\u{FEFF}';
+
+const CR: char = '\r';
+
+const DELIMITERS: &[char] = &['<', '>', ',', ';', '(', ')', '[', ']', '\'', '`'];
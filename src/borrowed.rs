@@ -0,0 +1,117 @@
+/*!
+An address that borrows from the string it was parsed from, for input that outlives the parsed
+value; the text is only copied if parsing had to change it, as when removing comments or folding
+white space.
+*/
+
+use crate::parser::Collector;
+use crate::{check_address, EmailAddress, Error, Options};
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An email address that borrows from its input where it can.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let email = BorrowedEmailAddress::parse("simon@example.com").unwrap();
+/// assert!(email.is_borrowed());
+/// assert_eq!(email.domain(), "example.com");
+///
+/// let email = BorrowedEmailAddress::parse("<simon@example.com>").unwrap();
+/// assert_eq!(email.as_str(), "simon@example.com");
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BorrowedEmailAddress<'a> {
+    address: Cow<'a, str>,
+    at: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for BorrowedEmailAddress<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.address)
+    }
+}
+
+impl Debug for BorrowedEmailAddress<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BorrowedEmailAddress")
+            .field("local", &self.local_part())
+            .field("domain", &self.domain())
+            .finish()
+    }
+}
+
+impl AsRef<str> for BorrowedEmailAddress<'_> {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
+}
+
+impl From<BorrowedEmailAddress<'_>> for EmailAddress {
+    fn from(email: BorrowedEmailAddress<'_>) -> Self {
+        email.into_owned()
+    }
+}
+
+impl<'a> BorrowedEmailAddress<'a> {
+    ///
+    /// Parse `address` with the same rules as `EmailAddress::from_str`.
+    ///
+    pub fn parse(address: &'a str) -> Result<Self, Error> {
+        Self::parse_with(address, &Options::default())
+    }
+
+    ///
+    /// Parse `address` using the rules selected by `options`.
+    ///
+    pub fn parse_with(address: &'a str, options: &Options) -> Result<Self, Error> {
+        let parsed = check_address(address, options, &mut Collector::default())?;
+        let at = parsed.local.len();
+        let address = match (&parsed.local, &parsed.domain) {
+            (Cow::Borrowed(local), Cow::Borrowed(domain))
+                if local.len() == parsed.local_span.len()
+                    && domain.len() == parsed.domain_span.len() =>
+            {
+                Cow::Borrowed(&address[parsed.local_span.start..parsed.domain_span.end])
+            }
+            (local, domain) => Cow::Owned(format!("{}@{}", local, domain)),
+        };
+        Ok(Self { address, at })
+    }
+
+    /// Returns `true` if the address borrows from the string it was parsed from.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.address, Cow::Borrowed(_))
+    }
+
+    /// Returns the email address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the local part of the address.
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// Returns the domain of the address.
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+
+    /// Returns the address as an owned `EmailAddress`.
+    pub fn into_owned(self) -> EmailAddress {
+        EmailAddress::from_parts(self.local_part(), self.domain())
+    }
+}
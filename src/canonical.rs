@@ -0,0 +1,182 @@
+/*!
+Provider-specific canonicalization, mapping the many addresses that a mail provider delivers to
+the same mailbox onto one canonical address; for example, Gmail ignores dots in the local part,
+and everything after a '+'.
+
+These equivalences are a property of each provider, not of the address syntax, and may change;
+they are intended for finding duplicate accounts, never for deciding where to send mail.
+*/
+
+use crate::parser::is_dot_atom;
+use crate::DOT;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Rules for the canonical form of addresses at one or more mail providers, used by
+/// `EmailAddress::canonicalize`.
+///
+pub trait ProviderRules {
+    ///
+    /// Returns the canonical local part and domain of the address with the given `local` part
+    /// and `domain`, or `None` if these rules do not apply to the address. The canonical local
+    /// part must be a `dot-atom`; if it is not, the address is left unchanged.
+    ///
+    fn canonicalize(&self, local: &str, domain: &str) -> Option<(String, String)>;
+}
+
+///
+/// The rules of a single mail provider, which apply to addresses at any of its domains.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let rule = ProviderRule::new(vec!["example.com", "example.net"])
+///     .with_canonical_domain("example.com")
+///     .with_tag_separator('-');
+///
+/// let email = EmailAddress::from_str("Simon-news@Example.NET").unwrap();
+/// assert_eq!(email.canonicalize(&rule).to_string(), "simon@example.com");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderRule {
+    domains: Vec<String>,
+    canonical_domain: Option<String>,
+    tag_separators: Vec<char>,
+    ignore_dots: bool,
+    lowercase: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ProviderRules for ProviderRule {
+    fn canonicalize(&self, local: &str, domain: &str) -> Option<(String, String)> {
+        let domain = domain.to_lowercase();
+        if !self.domains.contains(&domain) {
+            return None;
+        }
+        let local = match local.find(|c| self.tag_separators.contains(&c)) {
+            Some(separator) => &local[..separator],
+            None => local,
+        };
+        let mut local = if self.ignore_dots {
+            local.replace(DOT, "")
+        } else {
+            local.to_string()
+        };
+        if self.lowercase {
+            local = local.to_lowercase();
+        }
+        // Cutting a tag may leave a local part that is empty, or ends in '.', as in `a.+x`.
+        if !is_dot_atom(&local) {
+            return None;
+        }
+        let domain = self.canonical_domain.clone().unwrap_or(domain);
+        Some((local, domain))
+    }
+}
+
+impl<R: ProviderRules> ProviderRules for [R] {
+    ///
+    /// Apply the first of the rules that applies to the address.
+    ///
+    fn canonicalize(&self, local: &str, domain: &str) -> Option<(String, String)> {
+        self.iter()
+            .find_map(|rules| rules.canonicalize(local, domain))
+    }
+}
+
+impl<R: ProviderRules> ProviderRules for Vec<R> {
+    fn canonicalize(&self, local: &str, domain: &str) -> Option<(String, String)> {
+        self.as_slice().canonicalize(local, domain)
+    }
+}
+
+impl ProviderRule {
+    ///
+    /// A rule for the provider with the given `domains`, which by default only lowercases the
+    /// local part.
+    ///
+    pub fn new<I, S>(domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            domains: domains
+                .into_iter()
+                .map(|domain| domain.into().to_lowercase())
+                .collect(),
+            canonical_domain: None,
+            tag_separators: Vec::new(),
+            ignore_dots: false,
+            lowercase: true,
+        }
+    }
+
+    ///
+    /// The built-in rules for the major mail providers.
+    ///
+    pub fn built_in() -> &'static [ProviderRule] {
+        static RULES: std::sync::OnceLock<Vec<ProviderRule>> = std::sync::OnceLock::new();
+        RULES.get_or_init(|| {
+            vec![
+                ProviderRule::new(vec!["gmail.com", "googlemail.com"])
+                    .with_canonical_domain("gmail.com")
+                    .with_tag_separator('+')
+                    .with_ignored_dots(),
+                ProviderRule::new(vec!["outlook.com", "hotmail.com", "live.com", "msn.com"])
+                    .with_tag_separator('+'),
+                ProviderRule::new(vec!["icloud.com", "me.com", "mac.com"]).with_tag_separator('+'),
+                ProviderRule::new(vec![
+                    "protonmail.com",
+                    "protonmail.ch",
+                    "proton.me",
+                    "pm.me",
+                ])
+                .with_tag_separator('+'),
+                ProviderRule::new(vec!["fastmail.com", "fastmail.fm"]).with_tag_separator('+'),
+                ProviderRule::new(vec!["yahoo.com", "ymail.com"]).with_tag_separator('-'),
+            ]
+        })
+    }
+
+    ///
+    /// Replace the domain of matching addresses with `domain`.
+    ///
+    pub fn with_canonical_domain<S: Into<String>>(mut self, domain: S) -> Self {
+        self.canonical_domain = Some(domain.into().to_lowercase());
+        self
+    }
+
+    ///
+    /// Remove everything from the first `separator` in the local part, the tag of a
+    /// sub-address.
+    ///
+    pub fn with_tag_separator(mut self, separator: char) -> Self {
+        self.tag_separators.push(separator);
+        self
+    }
+
+    ///
+    /// Remove all dots from the local part.
+    ///
+    pub fn with_ignored_dots(mut self) -> Self {
+        self.ignore_dots = true;
+        self
+    }
+
+    ///
+    /// Preserve the case of the local part, for providers whose local parts are case-sensitive.
+    ///
+    pub fn without_lowercase(mut self) -> Self {
+        self.lowercase = false;
+        self
+    }
+}
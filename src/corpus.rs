@@ -0,0 +1,197 @@
+/*!
+A corpus of categorized example addresses, with the result of parsing each using the default
+`Options`, for testing code that handles addresses against this crate's understanding of
+validity.
+
+```rust
+use email_address::corpus::{self, Kind};
+use email_address::EmailAddress;
+use std::str::FromStr;
+
+for example in corpus::examples() {
+    let result = EmailAddress::from_str(example.address()).map(|_| ());
+    assert_eq!(result, example.result(), "{:?}", example);
+}
+
+assert!(corpus::examples_of(Kind::ValidUtf8).all(|example| example.is_valid()));
+```
+
+Errors that can only be returned when parsing bytes, or when using options other than the
+defaults, are not represented.
+*/
+
+use crate::Error;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The categories of example in the corpus.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Kind {
+    /// Valid addresses in ASCII, with a `dot-atom` local part and a domain name.
+    ValidAscii,
+    /// Valid addresses with characters outside ASCII, as allowed by RFC 6531.
+    ValidUtf8,
+    /// Valid addresses with a quoted string local part.
+    QuotedLocalPart,
+    /// Valid addresses with a domain literal.
+    DomainLiteral,
+    /// Invalid addresses, each with the error returned.
+    Invalid,
+}
+
+///
+/// A single example address, with its category and the error returned when parsing it, if
+/// any.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    address: String,
+    kind: Kind,
+    error: Option<Error>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns every example in the corpus, grouped by `Kind`.
+///
+pub fn examples() -> Vec<Example> {
+    let valid = |kind, address: &str| Example {
+        address: address.to_string(),
+        kind,
+        error: None,
+    };
+    let invalid = |address: &str, error| Example {
+        address: address.to_string(),
+        kind: Kind::Invalid,
+        error: Some(error),
+    };
+    let label = "a".repeat(63);
+    vec![
+        valid(Kind::ValidAscii, "simon@example.com"),
+        valid(Kind::ValidAscii, "simon.johnston@example.com"),
+        valid(Kind::ValidAscii, "simon+news@mail.example.co.uk"),
+        valid(Kind::ValidAscii, "x@x.x"),
+        valid(Kind::ValidAscii, "!#$%&'*+-/=?^_`{|}~@example.com"),
+        valid(Kind::ValidAscii, "simon@localhost"),
+        valid(Kind::ValidAscii, "simon@sub_domain.example.com"),
+        valid(Kind::ValidAscii, &format!("{}@example.com", "a".repeat(64))),
+        valid(Kind::ValidUtf8, "jöran@example.com"),
+        valid(Kind::ValidUtf8, "simon@Sörensen.example.com"),
+        valid(Kind::ValidUtf8, "用户@例子.广告"),
+        valid(Kind::ValidUtf8, "θσερ@εχαμπλε.ψομ"),
+        valid(Kind::ValidUtf8, "Pelé@example.com"),
+        valid(Kind::QuotedLocalPart, "\"john..doe\"@example.org"),
+        valid(Kind::QuotedLocalPart, "\"simon johnston\"@example.com"),
+        valid(Kind::QuotedLocalPart, "\"simon@johnston\"@example.com"),
+        valid(Kind::QuotedLocalPart, "\"si\\\"mon\"@example.com"),
+        valid(Kind::QuotedLocalPart, "\" \"@example.org"),
+        valid(Kind::DomainLiteral, "simon@[192.168.2.1]"),
+        valid(Kind::DomainLiteral, "simon@[IPv6:2001:db8::1]"),
+        valid(Kind::DomainLiteral, "simon@[IPv6:::1]"),
+        invalid("", Error::MissingSeparator),
+        invalid("simon", Error::MissingSeparator),
+        invalid("@example.com", Error::LocalPartEmpty),
+        invalid("\"\"@example.com", Error::LocalPartEmpty),
+        invalid("simon@", Error::DomainEmpty),
+        invalid(
+            "sim on@example.com",
+            Error::LocalPartInvalidCharacter(' ', 3),
+        ),
+        invalid(
+            ".simon@example.com",
+            Error::LocalPartInvalidCharacter('.', 0),
+        ),
+        invalid(
+            "si..mon@example.com",
+            Error::LocalPartInvalidCharacter('.', 3),
+        ),
+        invalid(
+            "simon(comment)@example.com",
+            Error::LocalPartInvalidCharacter('(', 5),
+        ),
+        invalid(
+            "\"si\u{0}mon\"@example.com",
+            Error::QuotedStringInvalidCharacter('\u{0}', 3),
+        ),
+        invalid("\"simon@example.com", Error::UnbalancedQuotes),
+        invalid("simon@exa mple.com", Error::DomainInvalidCharacter(' ', 9)),
+        invalid("simon@example..com", Error::DomainInvalidCharacter('.', 14)),
+        invalid("simon@.example.com", Error::DomainInvalidCharacter('.', 6)),
+        invalid(
+            "simon@[1.2.3.4",
+            Error::DomainLiteralInvalidCharacter('[', 6),
+        ),
+        invalid("simon@[x y]", Error::DomainLiteralInvalidCharacter(' ', 8)),
+        invalid(
+            &format!("{}@example.com", "a".repeat(65)),
+            Error::LocalPartTooLong,
+        ),
+        invalid(&format!("simon@{}a.com", label), Error::SubDomainTooLong),
+        invalid(
+            &format!("simon@{}.com", [label.as_str(); 4].join(".")),
+            Error::DomainTooLong,
+        ),
+        invalid(
+            &format!(
+                "{}@{}.com",
+                "a".repeat(64),
+                vec!["a".repeat(60); 4].join(".")
+            ),
+            Error::AddressTooLong,
+        ),
+    ]
+}
+
+///
+/// Returns the examples in the corpus of the given `kind`.
+///
+pub fn examples_of(kind: Kind) -> impl Iterator<Item = Example> {
+    examples()
+        .into_iter()
+        .filter(move |example| example.kind == kind)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Example {
+    /// Returns the example address.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the category of the example.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns `true` if the address is valid.
+    pub fn is_valid(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Returns the error returned when parsing the address, or `None` if it is valid.
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    ///
+    /// Returns the result of parsing the address, without the parsed address, for comparison
+    /// with the result of another parser.
+    ///
+    pub fn result(&self) -> Result<(), Error> {
+        match &self.error {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+}
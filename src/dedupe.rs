@@ -0,0 +1,138 @@
+/*!
+Deduplication of addresses, such as those of a contact list being imported, where addresses
+that differ only in ways the receiving host ignores are taken to be the same mailbox.
+*/
+
+use crate::{EmailAddress, EmailAddressList, ProviderRule};
+use std::collections::HashMap;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The rules by which two addresses are taken to be the same mailbox, used by
+/// `EmailAddress::dedupe`. Every rule ignores the case of the domain, which is never
+/// significant.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Equivalence {
+    /// Addresses are the same if they differ only in the case of the domain.
+    CaseInsensitiveDomain,
+    ///
+    /// Addresses are the same if they have the same canonical form under the built-in rules
+    /// of `ProviderRule::built_in`; addresses at other providers are compared as by
+    /// `CaseInsensitiveDomain`.
+    ///
+    ProviderCanonical,
+    ///
+    /// Addresses are the same if they differ only in a tag, as found by `EmailAddress::tag`,
+    /// or in the case of the domain.
+    ///
+    IgnoreTags,
+}
+
+///
+/// The result of `EmailAddress::dedupe`; the unique addresses, and each duplicate with the
+/// unique address it was taken to be the same as.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Deduplicated {
+    unique: EmailAddressList,
+    duplicates: Vec<(EmailAddress, usize)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the unique `addresses` under `equivalence`, keeping the first of each, with the
+/// duplicates found.
+///
+pub(crate) fn dedupe<I>(addresses: I, equivalence: Equivalence) -> Deduplicated
+where
+    I: IntoIterator<Item = EmailAddress>,
+{
+    let mut seen = HashMap::new();
+    let mut deduplicated = Deduplicated::default();
+    for email in addresses {
+        let key = equivalence.key(&email);
+        match seen.get(&key) {
+            Some(index) => deduplicated.duplicates.push((email, *index)),
+            None => {
+                seen.insert(key, deduplicated.unique.len());
+                deduplicated.unique.push(email);
+            }
+        }
+    }
+    deduplicated
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Equivalence {
+    ///
+    /// Returns the form of `email` compared under this rule; two addresses are the same if
+    /// their keys are equal.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("Simon+news@Example.COM").unwrap();
+    /// assert_eq!(
+    ///     Equivalence::CaseInsensitiveDomain.key(&email).as_str(),
+    ///     "Simon+news@example.com"
+    /// );
+    /// assert_eq!(Equivalence::IgnoreTags.key(&email).as_str(), "Simon@example.com");
+    /// ```
+    ///
+    pub fn key(self, email: &EmailAddress) -> EmailAddress {
+        let email = match self {
+            Equivalence::CaseInsensitiveDomain => email.clone(),
+            Equivalence::ProviderCanonical => email.canonicalize(ProviderRule::built_in()),
+            Equivalence::IgnoreTags => email.without_tag(),
+        };
+        EmailAddress::from_parts(email.local_part(), &email.domain().to_lowercase())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Deduplicated {
+    /// Returns the unique addresses, each the first of those taken to be the same mailbox.
+    pub fn unique(&self) -> &EmailAddressList {
+        &self.unique
+    }
+
+    ///
+    /// Returns the duplicates, in the order they were found, each with the index in `unique`
+    /// of the address it was taken to be the same as.
+    ///
+    pub fn duplicates(&self) -> &[(EmailAddress, usize)] {
+        &self.duplicates
+    }
+
+    ///
+    /// Returns the unique address that `email` was taken to be the same as, which is itself
+    /// if it is unique, or `None` if it was not deduplicated.
+    ///
+    pub fn representative(&self, email: &EmailAddress) -> Option<&EmailAddress> {
+        if let Some(unique) = self.unique.iter().find(|unique| *unique == email) {
+            return Some(unique);
+        }
+        self.duplicates
+            .iter()
+            .find(|(duplicate, _)| duplicate == email)
+            .map(|(_, index)| &self.unique.as_slice()[*index])
+    }
+
+    /// Returns the unique addresses, discarding the duplicates.
+    pub fn into_unique(self) -> EmailAddressList {
+        self.unique
+    }
+}
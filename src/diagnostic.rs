@@ -0,0 +1,473 @@
+/*!
+Numeric diagnostic codes, compatible with those of Dominic Sayers' [is_email](https://github.com/dominicsayers/isemail)
+so that results may be compared with other implementations, and stored compactly.
+
+Each `Diagnostic` belongs to a `Category`, and the code of every diagnostic is no greater than the
+code of its category; so comparing codes orders diagnostics from the least to the most severe.
+
+The diagnostics with codes above 150 are specific to this crate; they are the errors returned when
+`Options` reject an address that is_email would accept, so that every error returned by parsing
+is diagnosed in the `Error` category.
+*/
+
+use crate::{Error, Warning};
+#[cfg(feature = "serde_support")]
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The severity of a `Diagnostic`; the discriminant is the is_email category code.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+pub enum Category {
+    /// The address is valid.
+    Valid = 1,
+    /// The address is valid, but a DNS check found a problem with the domain.
+    DnsWarning = 7,
+    /// The address is valid for SMTP, but has unusual elements.
+    Rfc5321 = 15,
+    /// The address is valid within the message, but not for SMTP.
+    Cfws = 31,
+    /// The address contains deprecated elements, but may still be valid in restricted contexts.
+    Deprecated = 63,
+    /// The address is only valid according to the broad definition of RFC 5322; it is otherwise
+    /// invalid.
+    Rfc5322 = 127,
+    /// The address is invalid for any purpose.
+    Error = 255,
+}
+
+///
+/// A single diagnosis of an address; the discriminant is the is_email diagnosis code.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[repr(u8)]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// The address is valid.
+    Valid = 0,
+    /// The domain could not be found in DNS, or has no MX record.
+    DnsNoMxRecord = 5,
+    /// Neither an MX record nor an A record for the domain could be found in DNS.
+    DnsNoRecord = 6,
+    /// The address is at a top-level domain.
+    Rfc5321Tld = 9,
+    /// The top-level domain of the address starts with a number.
+    Rfc5321TldNumeric = 10,
+    /// The `local-part` is a quoted string.
+    Rfc5321QuotedString = 11,
+    /// The `domain` is an address literal.
+    Rfc5321AddressLiteral = 12,
+    /// The `domain` is an IPv6 address literal, and it uses `::` for a single group of zeros.
+    Rfc5321Ipv6Deprecated = 13,
+    /// The address contains a comment.
+    CfwsComment = 17,
+    /// The address contains folding white space.
+    CfwsFws = 18,
+    /// The `local-part` is in a deprecated form.
+    DeprecatedLocalPart = 33,
+    /// The address contains an obsolete form of folding white space.
+    DeprecatedFws = 34,
+    /// A quoted string contains a deprecated character.
+    DeprecatedQtext = 35,
+    /// A quoted pair contains a deprecated character.
+    DeprecatedQuotedPair = 36,
+    /// The address contains a comment in a position that is deprecated.
+    DeprecatedComment = 37,
+    /// A comment contains a deprecated character.
+    DeprecatedCtext = 38,
+    /// The address contains a comment or folding white space around the '@'.
+    DeprecatedCfwsNearAt = 49,
+    /// The `domain` is valid according to RFC 5322, but is not a valid host name.
+    Rfc5322Domain = 65,
+    /// The address is too long.
+    Rfc5322TooLong = 66,
+    /// The `local-part` is too long.
+    Rfc5322LocalTooLong = 67,
+    /// The `domain` is too long.
+    Rfc5322DomainTooLong = 68,
+    /// A `sub-domain` is too long.
+    Rfc5322LabelTooLong = 69,
+    /// The `domain` literal is not a valid RFC 5321 address literal.
+    Rfc5322DomainLiteral = 70,
+    /// The `domain` literal is not a valid RFC 5321 address literal, and contains obsolete
+    /// characters.
+    Rfc5322DomainLiteralObsoleteDtext = 71,
+    /// The IPv6 address literal has the wrong number of groups.
+    Rfc5322Ipv6GroupCount = 72,
+    /// The IPv6 address literal contains more than one `::`.
+    Rfc5322Ipv6DoubleColons = 73,
+    /// The IPv6 address literal contains a bad character.
+    Rfc5322Ipv6BadCharacter = 74,
+    /// The IPv6 address literal has too many groups.
+    Rfc5322Ipv6MaxGroups = 75,
+    /// The IPv6 address literal starts with a single colon.
+    Rfc5322Ipv6ColonStart = 76,
+    /// The IPv6 address literal ends with a single colon.
+    Rfc5322Ipv6ColonEnd = 77,
+    /// A domain literal contains a character that is not allowed.
+    ExpectingDtext = 129,
+    /// The address has no `local-part`.
+    NoLocalPart = 130,
+    /// The address has no `domain`.
+    NoDomain = 131,
+    /// The address contains consecutive dots.
+    ConsecutiveDots = 132,
+    /// The address contains text after a comment or folding white space.
+    AtextAfterCfws = 133,
+    /// The address contains text after a quoted string.
+    AtextAfterQuotedString = 134,
+    /// The `domain` contains text after a domain literal.
+    AtextAfterDomainLiteral = 135,
+    /// The address contains a character that is not allowed in a quoted pair.
+    ExpectingQuotedPair = 136,
+    /// The address contains a character that is not allowed.
+    ExpectingAtext = 137,
+    /// A quoted string contains a character that is not allowed.
+    ExpectingQtext = 138,
+    /// A comment contains a character that is not allowed.
+    ExpectingCtext = 139,
+    /// The address ends with a backslash.
+    BackslashEnd = 140,
+    /// Neither part of the address may begin with a dot.
+    DotStart = 141,
+    /// Neither part of the address may end with a dot.
+    DotEnd = 142,
+    /// A `sub-domain` begins with a hyphen.
+    DomainHyphenStart = 143,
+    /// A `sub-domain` ends with a hyphen.
+    DomainHyphenEnd = 144,
+    /// A quoted string is not closed.
+    UnclosedQuotedString = 145,
+    /// A comment is not closed.
+    UnclosedComment = 146,
+    /// A domain literal is not closed.
+    UnclosedDomainLiteral = 147,
+    /// Folding white space contains consecutive CRLF sequences.
+    FwsCrlfTwice = 148,
+    /// Folding white space ends with a CRLF sequence.
+    FwsCrlfEnd = 149,
+    /// The address contains a CR that is not followed by an LF.
+    CrNoLf = 150,
+    /// The `local-part` is a quoted string, which the options used do not allow.
+    QuotedStringNotAllowed = 151,
+    /// The `domain` is a domain literal, which the options used do not allow.
+    DomainLiteralNotAllowed = 152,
+    /// The `domain` has no top-level domain, which the options used require.
+    TldRequired = 153,
+    /// The `domain` can only be reached within a private network, which the options used do not
+    /// allow.
+    InternalDomain = 154,
+    /// The `domain` is one that the options used deny.
+    DeniedDomain = 155,
+    /// The `domain` is an address literal that is not globally routable, which the options used
+    /// do not allow.
+    NonGlobalAddressLiteral = 156,
+    /// The `local-part` has more consecutive sub-address separators than the options used allow.
+    TooManyTagSeparators = 157,
+    /// The `local-part` routes with '%' or '!', which the options used do not allow.
+    LocalPartRouting = 158,
+    /// The `domain` has more labels than the options used allow.
+    TooManyLabels = 159,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+const ALL: &[Diagnostic] = &[
+    Diagnostic::Valid,
+    Diagnostic::DnsNoMxRecord,
+    Diagnostic::DnsNoRecord,
+    Diagnostic::Rfc5321Tld,
+    Diagnostic::Rfc5321TldNumeric,
+    Diagnostic::Rfc5321QuotedString,
+    Diagnostic::Rfc5321AddressLiteral,
+    Diagnostic::Rfc5321Ipv6Deprecated,
+    Diagnostic::CfwsComment,
+    Diagnostic::CfwsFws,
+    Diagnostic::DeprecatedLocalPart,
+    Diagnostic::DeprecatedFws,
+    Diagnostic::DeprecatedQtext,
+    Diagnostic::DeprecatedQuotedPair,
+    Diagnostic::DeprecatedComment,
+    Diagnostic::DeprecatedCtext,
+    Diagnostic::DeprecatedCfwsNearAt,
+    Diagnostic::Rfc5322Domain,
+    Diagnostic::Rfc5322TooLong,
+    Diagnostic::Rfc5322LocalTooLong,
+    Diagnostic::Rfc5322DomainTooLong,
+    Diagnostic::Rfc5322LabelTooLong,
+    Diagnostic::Rfc5322DomainLiteral,
+    Diagnostic::Rfc5322DomainLiteralObsoleteDtext,
+    Diagnostic::Rfc5322Ipv6GroupCount,
+    Diagnostic::Rfc5322Ipv6DoubleColons,
+    Diagnostic::Rfc5322Ipv6BadCharacter,
+    Diagnostic::Rfc5322Ipv6MaxGroups,
+    Diagnostic::Rfc5322Ipv6ColonStart,
+    Diagnostic::Rfc5322Ipv6ColonEnd,
+    Diagnostic::ExpectingDtext,
+    Diagnostic::NoLocalPart,
+    Diagnostic::NoDomain,
+    Diagnostic::ConsecutiveDots,
+    Diagnostic::AtextAfterCfws,
+    Diagnostic::AtextAfterQuotedString,
+    Diagnostic::AtextAfterDomainLiteral,
+    Diagnostic::ExpectingQuotedPair,
+    Diagnostic::ExpectingAtext,
+    Diagnostic::ExpectingQtext,
+    Diagnostic::ExpectingCtext,
+    Diagnostic::BackslashEnd,
+    Diagnostic::DotStart,
+    Diagnostic::DotEnd,
+    Diagnostic::DomainHyphenStart,
+    Diagnostic::DomainHyphenEnd,
+    Diagnostic::UnclosedQuotedString,
+    Diagnostic::UnclosedComment,
+    Diagnostic::UnclosedDomainLiteral,
+    Diagnostic::FwsCrlfTwice,
+    Diagnostic::FwsCrlfEnd,
+    Diagnostic::CrNoLf,
+    Diagnostic::QuotedStringNotAllowed,
+    Diagnostic::DomainLiteralNotAllowed,
+    Diagnostic::TldRequired,
+    Diagnostic::InternalDomain,
+    Diagnostic::DeniedDomain,
+    Diagnostic::NonGlobalAddressLiteral,
+    Diagnostic::TooManyTagSeparators,
+    Diagnostic::LocalPartRouting,
+    Diagnostic::TooManyLabels,
+];
+
+impl Category {
+    /// Returns the numeric is_email code for this category.
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} ({})", self, self.code())
+    }
+}
+
+impl From<&Error> for Diagnostic {
+    fn from(error: &Error) -> Self {
+        match error {
+            Error::LocalPartInvalidCharacter(_, _) => Diagnostic::ExpectingAtext,
+            Error::QuotedStringInvalidCharacter(_, _) => Diagnostic::ExpectingQtext,
+            Error::DomainInvalidCharacter(_, _) => Diagnostic::ExpectingAtext,
+            Error::DomainLiteralInvalidCharacter(_, _) => Diagnostic::ExpectingDtext,
+            Error::MissingSeparator => Diagnostic::NoDomain,
+            Error::LocalPartEmpty => Diagnostic::NoLocalPart,
+            Error::LocalPartTooLong => Diagnostic::Rfc5322LocalTooLong,
+            Error::DomainEmpty => Diagnostic::NoDomain,
+            Error::DomainTooLong => Diagnostic::Rfc5322DomainTooLong,
+            Error::SubDomainTooLong => Diagnostic::Rfc5322LabelTooLong,
+            Error::AddressTooLong => Diagnostic::Rfc5322TooLong,
+            Error::DomainTooFew => Diagnostic::TldRequired,
+            Error::DomainInvalidSeparator => Diagnostic::ConsecutiveDots,
+            Error::UnbalancedQuotes => Diagnostic::UnclosedQuotedString,
+            Error::InvalidComment => Diagnostic::UnclosedComment,
+            Error::InvalidIPAddress => Diagnostic::Rfc5322DomainLiteral,
+            Error::UnsupportedQuotedLocalPart => Diagnostic::QuotedStringNotAllowed,
+            Error::UnsupportedDomainLiteral => Diagnostic::DomainLiteralNotAllowed,
+            Error::InvalidUtf8(_) => Diagnostic::ExpectingAtext,
+            Error::MissingAngleBrackets => Diagnostic::ExpectingAtext,
+            Error::InvalidXtext(_) => Diagnostic::ExpectingAtext,
+            Error::InternalDomain => Diagnostic::InternalDomain,
+            Error::DeniedDomain => Diagnostic::DeniedDomain,
+            Error::NonGlobalAddressLiteral => Diagnostic::NonGlobalAddressLiteral,
+            Error::DomainTooMany => Diagnostic::TooManyLabels,
+            Error::TooManyTagSeparators => Diagnostic::TooManyTagSeparators,
+            Error::PercentHackRouting => Diagnostic::LocalPartRouting,
+            Error::BangPathRouting => Diagnostic::LocalPartRouting,
+        }
+    }
+}
+
+impl From<&Warning> for Diagnostic {
+    fn from(warning: &Warning) -> Self {
+        match warning {
+            Warning::QuotedLocalPart => Diagnostic::Rfc5321QuotedString,
+            Warning::DomainLiteral => Diagnostic::Rfc5321AddressLiteral,
+            Warning::DotlessDomain => Diagnostic::Rfc5321Tld,
+            Warning::NumericTld => Diagnostic::Rfc5321TldNumeric,
+            Warning::NonLdhDomain => Diagnostic::Rfc5322Domain,
+            Warning::Comment => Diagnostic::CfwsComment,
+            Warning::FoldingWhiteSpace => Diagnostic::CfwsFws,
+            Warning::ObsoleteSyntax => Diagnostic::DeprecatedLocalPart,
+            Warning::LocalPartTooLong => Diagnostic::Rfc5322LocalTooLong,
+            Warning::DomainTooLong => Diagnostic::Rfc5322DomainTooLong,
+            Warning::SubDomainTooLong => Diagnostic::Rfc5322LabelTooLong,
+            Warning::AddressTooLong => Diagnostic::Rfc5322TooLong,
+        }
+    }
+}
+
+impl Diagnostic {
+    ///
+    /// Returns the diagnostic with the numeric is_email `code`, if there is one.
+    ///
+    pub fn from_code(code: u8) -> Option<Self> {
+        ALL.iter()
+            .find(|diagnostic| diagnostic.code() == code)
+            .copied()
+    }
+
+    /// Returns the numeric is_email code for this diagnostic.
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    /// Returns the category, and so the severity, of this diagnostic.
+    pub fn category(&self) -> Category {
+        match self.code() {
+            0 => Category::Valid,
+            1..=7 => Category::DnsWarning,
+            8..=15 => Category::Rfc5321,
+            16..=31 => Category::Cfws,
+            32..=63 => Category::Deprecated,
+            64..=127 => Category::Rfc5322,
+            _ => Category::Error,
+        }
+    }
+
+    ///
+    /// Returns `true` if the address is invalid, that is it falls in the `Rfc5322` or `Error`
+    /// categories.
+    ///
+    pub fn is_error(&self) -> bool {
+        self.category() >= Category::Rfc5322
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Feature-specific Implementations
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for Error {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(miette_code(self)))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        miette_help(self).map(|help| Box::new(help) as Box<dyn Display>)
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for crate::ParseError {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(miette_code(self.error())))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        miette_help(self.error()).map(|help| Box::new(help) as Box<dyn Display>)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        Some(Box::new(std::iter::once(
+            miette::LabeledSpan::new_primary_with_span(
+                Some(miette_label(self.error()).to_string()),
+                self.span(),
+            ),
+        )))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "miette")]
+fn miette_code(error: &Error) -> String {
+    format!("email_address::{:?}", Diagnostic::from(error))
+}
+
+#[cfg(feature = "miette")]
+fn miette_label(error: &Error) -> &'static str {
+    match error {
+        Error::LocalPartInvalidCharacter(_, _)
+        | Error::QuotedStringInvalidCharacter(_, _)
+        | Error::DomainInvalidCharacter(_, _)
+        | Error::DomainLiteralInvalidCharacter(_, _) => "this character is not allowed here",
+        Error::MissingSeparator => "no '@' in this address",
+        Error::LocalPartEmpty => "this local part is empty",
+        Error::LocalPartTooLong => "this local part is too long",
+        Error::DomainEmpty => "this domain is empty",
+        Error::DomainTooLong => "this domain is too long",
+        Error::SubDomainTooLong => "a sub-domain here is too long",
+        Error::AddressTooLong => "this address is too long",
+        Error::DomainTooFew => "this domain has too few parts",
+        Error::DomainInvalidSeparator => "this '.' is misplaced",
+        Error::UnbalancedQuotes => "this quoted string is not closed",
+        Error::InvalidComment => "this comment is malformed",
+        Error::InvalidIPAddress => "this is not a valid address literal",
+        Error::UnsupportedQuotedLocalPart => "this quoted string is not allowed",
+        Error::UnsupportedDomainLiteral => "this domain literal is not allowed",
+        Error::InvalidUtf8(_) => "this byte is not valid UTF-8",
+        Error::MissingAngleBrackets => "this must be enclosed in '<' and '>'",
+        Error::InvalidXtext(_) => "this is not valid xtext",
+        Error::InternalDomain => "this domain is only reachable on a private network",
+        Error::DeniedDomain => "this domain is not allowed",
+        Error::NonGlobalAddressLiteral => "this IP address is not globally routable",
+        Error::DomainTooMany => "this domain has too many parts",
+        Error::TooManyTagSeparators => "this local part repeats its sub-address separator",
+        Error::PercentHackRouting => "this local part routes with '%'",
+        Error::BangPathRouting => "this local part routes with '!'",
+    }
+}
+
+#[cfg(feature = "miette")]
+fn miette_help(error: &Error) -> Option<&'static str> {
+    match error {
+        Error::LocalPartInvalidCharacter(_, _) => Some(
+            "special characters and spaces are only allowed in a local part that is a quoted \
+             string, and consecutive, leading, or trailing dots are not allowed",
+        ),
+        Error::QuotedStringInvalidCharacter(_, _) => {
+            Some("a '\"' or '\\' in a quoted string must be preceded by a '\\'")
+        }
+        Error::DomainInvalidCharacter(_, _) => {
+            Some("a domain is made up of letters, digits, and hyphens, separated by single dots")
+        }
+        Error::DomainLiteralInvalidCharacter(_, _) => Some(
+            "a domain literal is enclosed in '[' and ']', and may not contain '[', ']', or '\\'",
+        ),
+        Error::MissingSeparator => Some("an email address has the form `local-part@domain`"),
+        Error::LocalPartTooLong => Some("by default the local part may be at most 64 octets long"),
+        Error::DomainTooLong => Some("by default the domain may be at most 254 octets long"),
+        Error::SubDomainTooLong => {
+            Some("by default each part of the domain may be at most 63 octets long")
+        }
+        Error::AddressTooLong => Some("by default the address may be at most 254 octets long"),
+        Error::DomainTooFew => Some("the domain must include a top-level domain, such as `.com`"),
+        Error::InternalDomain => Some("the domain must be reachable from the public Internet"),
+        Error::UnbalancedQuotes => Some("add a closing '\"' to the quoted string"),
+        Error::InvalidComment => Some("add a closing ')' to the comment"),
+        Error::InvalidIPAddress => {
+            Some("a domain literal must be an IPv4 address, or a tagged address such as `IPv6:::1`")
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,225 @@
+/*!
+Differential testing of this crate against other validators, such as the one a codebase is
+migrating from; each input is checked by every validator, and the inputs on which they disagree
+are reported. Requires the `differential` feature, which is intended for tests and tools rather
+than production builds.
+*/
+
+use crate::{corpus, EmailAddress, Options};
+use regex::Regex;
+use std::fmt::{Debug, Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A set of named validators, always starting with this crate, to run the same inputs through.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let differential = Differential::new()
+///     .with_html5()
+///     .with_validator("contains-at", |address| address.contains('@'));
+///
+/// let disagreements = differential.run(vec!["simon@example.com", "\"simon\"@example.com", "@"]);
+/// assert_eq!(disagreements.len(), 2);
+/// assert_eq!(disagreements[0].input(), "\"simon\"@example.com");
+/// assert_eq!(
+///     disagreements[0].results(),
+///     &[
+///         ("email_address".to_string(), true),
+///         ("html5".to_string(), false),
+///         ("contains-at".to_string(), true),
+///     ]
+/// );
+/// ```
+///
+pub struct Differential {
+    validators: Vec<(String, Validator)>,
+}
+
+///
+/// An input on which the validators of a `Differential` disagree, with the result of each.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Disagreement {
+    input: String,
+    results: Vec<(String, bool)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Debug for Differential {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Differential")
+            .field(
+                "validators",
+                &self
+                    .validators
+                    .iter()
+                    .map(|(name, _)| name)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl Default for Differential {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Differential {
+    ///
+    /// Create a differential with a single validator, this crate using the default `Options`,
+    /// named `email_address`.
+    ///
+    pub fn new() -> Self {
+        Self::with_options(Options::default())
+    }
+
+    ///
+    /// Create a differential with a single validator, this crate using `options`, named
+    /// `email_address`.
+    ///
+    pub fn with_options(options: Options) -> Self {
+        Self {
+            validators: Vec::new(),
+        }
+        .with_validator(THIS_CRATE, move |address| {
+            EmailAddress::parse_with(address, &options).is_ok()
+        })
+    }
+
+    ///
+    /// Add a validator named `name`, which returns `true` if an input is valid.
+    ///
+    pub fn with_validator<S, F>(mut self, name: S, validator: F) -> Self
+    where
+        S: Into<String>,
+        F: Fn(&str) -> bool + 'static,
+    {
+        self.validators.push((name.into(), Box::new(validator)));
+        self
+    }
+
+    ///
+    /// Add a validator named `name` that accepts the inputs matched by the regular expression
+    /// `pattern`, which should be anchored if it is to match whole inputs.
+    ///
+    pub fn with_regex<S: Into<String>>(self, name: S, pattern: &str) -> Result<Self, regex::Error> {
+        let regex = Regex::new(pattern)?;
+        Ok(self.with_validator(name, move |address| regex.is_match(address)))
+    }
+
+    ///
+    /// Add the validator of the HTML `input type=email` element, named `html5`, which is
+    /// defined by the WHATWG HTML standard as a regular expression.
+    ///
+    pub fn with_html5(self) -> Self {
+        self.with_regex(HTML5, HTML5_PATTERN)
+            .expect("the HTML5 pattern is valid")
+    }
+
+    /// Returns the names of the validators, in the order they were added.
+    pub fn names(&self) -> Vec<&str> {
+        self.validators
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    ///
+    /// Run each of `inputs` through every validator, returning the inputs on which they
+    /// disagree, in order.
+    ///
+    pub fn run<I, S>(&self, inputs: I) -> Vec<Disagreement>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        inputs
+            .into_iter()
+            .filter_map(|input| self.check(input.as_ref()))
+            .collect()
+    }
+
+    ///
+    /// Run the addresses of `corpus::examples` through every validator, returning those on
+    /// which they disagree.
+    ///
+    pub fn run_corpus(&self) -> Vec<Disagreement> {
+        self.run(
+            corpus::examples()
+                .iter()
+                .map(|example| example.address().to_string()),
+        )
+    }
+
+    fn check(&self, input: &str) -> Option<Disagreement> {
+        let results: Vec<(String, bool)> = self
+            .validators
+            .iter()
+            .map(|(name, validator)| (name.clone(), validator(input)))
+            .collect();
+        if results.iter().all(|(_, valid)| *valid == results[0].1) {
+            None
+        } else {
+            Some(Disagreement {
+                input: input.to_string(),
+                results,
+            })
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Disagreement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}:", self.input)?;
+        for (name, valid) in &self.results {
+            write!(f, " {}={}", name, if *valid { "valid" } else { "invalid" })?;
+        }
+        Ok(())
+    }
+}
+
+impl Disagreement {
+    /// Returns the input on which the validators disagree.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    ///
+    /// Returns the name of each validator, in the order they were added, with `true` if it
+    /// accepted the input.
+    ///
+    pub fn results(&self) -> &[(String, bool)] {
+        &self.results
+    }
+
+    /// Returns the names of the validators that accepted the input.
+    pub fn accepted_by(&self) -> Vec<&str> {
+        self.results
+            .iter()
+            .filter(|(_, valid)| *valid)
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+}
+
+type Validator = Box<dyn Fn(&str) -> bool>;
+
+// ------------------------------------------------------------------------------------------------
+
+const THIS_CRATE: &str = "email_address";
+
+const HTML5: &str = "html5";
+
+const HTML5_PATTERN: &str = "^[a-zA-Z0-9.!#$%&'*+/=?^_`{|}~-]+@[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?(?:\\.[a-zA-Z0-9](?:[a-zA-Z0-9-]{0,61}[a-zA-Z0-9])?)*$";
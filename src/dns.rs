@@ -0,0 +1,131 @@
+/*!
+Checks, using DNS, that the domain of an address can receive mail; requires the `dns` feature,
+and a Tokio runtime.
+
+Following RFC 5321, §5.1, mail is delivered to the hosts named by the MX records of the domain,
+in order of preference, or if it has none, to the domain itself if it has an A or AAAA record.
+A domain may also publish a "null MX", RFC 7505, to declare that it accepts no mail at all.
+*/
+
+use crate::{Diagnostic, EmailAddress, LBRACKET};
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+use hickory_resolver::TokioAsyncResolver;
+use std::sync::OnceLock;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The result of checking the domain of an address with `EmailAddress::check_dns`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DnsCheck {
+    /// The domain has MX records; the names of the mail exchanges, in order of preference.
+    Mx(Vec<String>),
+    /// The domain has no MX records, but has an A or AAAA record, and so is its own mail
+    /// exchange.
+    ImplicitMx,
+    /// The domain publishes a null MX record, RFC 7505, declaring that it accepts no mail.
+    NullMx,
+    /// The domain has no MX, A, or AAAA records, or does not exist.
+    NoRecords,
+    /// The domain is an address literal, so no lookup is needed.
+    AddressLiteral,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) async fn check(
+    email: &EmailAddress,
+    resolver: &TokioAsyncResolver,
+) -> Result<DnsCheck, ResolveError> {
+    let domain = email.domain();
+    if domain.starts_with(LBRACKET) {
+        return Ok(DnsCheck::AddressLiteral);
+    }
+    // Fully qualified, so that the resolver's search domains are not tried.
+    let domain = format!("{}.", domain.trim_end_matches('.'));
+    let mx = match resolver.mx_lookup(domain.as_str()).await {
+        Ok(lookup) => lookup
+            .iter()
+            .map(|mx| (mx.preference(), mx.exchange().to_utf8()))
+            .collect(),
+        Err(e) if is_no_records(&e) => Vec::new(),
+        Err(e) => return Err(e),
+    };
+    let has_address = mx.is_empty()
+        && match resolver.lookup_ip(domain.as_str()).await {
+            Ok(lookup) => lookup.iter().next().is_some(),
+            Err(e) if is_no_records(&e) => false,
+            Err(e) => return Err(e),
+        };
+    Ok(classify(mx, has_address))
+}
+
+pub(crate) fn shared_resolver() -> Result<&'static TokioAsyncResolver, ResolveError> {
+    static RESOLVER: OnceLock<TokioAsyncResolver> = OnceLock::new();
+    if let Some(resolver) = RESOLVER.get() {
+        return Ok(resolver);
+    }
+    let resolver = TokioAsyncResolver::tokio_from_system_conf()?;
+    Ok(RESOLVER.get_or_init(|| resolver))
+}
+
+///
+/// The result of a check given the `(preference, exchange)` pairs of the MX records of a domain,
+/// and whether it has an A or AAAA record.
+///
+pub(crate) fn classify(mut mx: Vec<(u16, String)>, has_address: bool) -> DnsCheck {
+    match mx.as_slice() {
+        [] if has_address => DnsCheck::ImplicitMx,
+        [] => DnsCheck::NoRecords,
+        [(0, exchange)] if exchange == "." || exchange.is_empty() => DnsCheck::NullMx,
+        _ => {
+            mx.sort_by_key(|(preference, _)| *preference);
+            DnsCheck::Mx(
+                mx.into_iter()
+                    .map(|(_, exchange)| exchange.trim_end_matches('.').to_string())
+                    .collect(),
+            )
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl DnsCheck {
+    ///
+    /// Returns `true` if mail can be delivered to the domain; it has MX records, an A or AAAA
+    /// record in their place, or is an address literal.
+    ///
+    pub fn accepts_mail(&self) -> bool {
+        matches!(
+            self,
+            DnsCheck::Mx(_) | DnsCheck::ImplicitMx | DnsCheck::AddressLiteral
+        )
+    }
+}
+
+impl From<&DnsCheck> for Diagnostic {
+    fn from(check: &DnsCheck) -> Self {
+        match check {
+            DnsCheck::Mx(_) | DnsCheck::AddressLiteral => Diagnostic::Valid,
+            DnsCheck::ImplicitMx => Diagnostic::DnsNoMxRecord,
+            DnsCheck::NullMx | DnsCheck::NoRecords => Diagnostic::DnsNoRecord,
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_no_records(error: &ResolveError) -> bool {
+    matches!(error.kind(), ResolveErrorKind::NoRecordsFound { .. })
+}
@@ -0,0 +1,317 @@
+/*!
+Lists of domains, such as those of disposable email services or free webmail providers, against
+which the domain of an address may be checked.
+
+A list matches a domain if it contains the domain itself, or any domain the domain is a
+sub-domain of; domains are compared without regard to case or a trailing dot.
+
+A `DomainList` looks up each parent of a domain in a hash set, while a `DomainSuffixSet` stores
+its domains in a trie of labels, from the top-level domain down, so that a domain is matched in
+a single walk of its labels, and domains sharing parents share storage; it is intended for
+allow and deny lists of many thousands of domains.
+*/
+
+use crate::{EmailAddress, DOT};
+use std::collections::{HashMap, HashSet};
+use std::iter::FromIterator;
+use std::str::FromStr;
+use std::sync::Arc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A list of domains, which matches each of its domains and all of their sub-domains.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let list = DomainList::from_str("# blocked\nexample.com\nexample.org\n").unwrap();
+///
+/// assert!(list.contains("example.com"));
+/// assert!(list.contains("Mail.Example.COM"));
+/// assert!(!list.contains("example.net"));
+/// assert!(list.matches(&EmailAddress::from_str("simon@example.org").unwrap()));
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainList {
+    domains: HashSet<String>,
+}
+
+///
+/// A set of domains, stored as a trie of labels, which matches each of its domains and all of
+/// their sub-domains.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let blocked: DomainSuffixSet = vec!["example.com", "spam.example.org"].into_iter().collect();
+///
+/// assert!(blocked.matches(&EmailAddress::from_str("simon@mail.example.com").unwrap()));
+/// assert!(blocked.matches(&EmailAddress::from_str("simon@SPAM.example.org").unwrap()));
+/// assert!(!blocked.matches(&EmailAddress::from_str("simon@example.org").unwrap()));
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainSuffixSet {
+    root: Node,
+    len: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Node {
+    terminal: bool,
+    children: HashMap<Box<str>, Node>,
+}
+
+#[cfg(feature = "disposable")]
+const DISPOSABLE_DOMAINS: &str = include_str!("data/disposable.txt");
+
+#[cfg(feature = "free_provider")]
+const FREE_PROVIDER_DOMAINS: &str = include_str!("data/free_providers.txt");
+
+const SPECIAL_USE_DOMAINS: &str = include_str!("data/special_use.txt");
+
+impl FromStr for DomainList {
+    type Err = std::convert::Infallible;
+
+    ///
+    /// Parse a list with one domain per line; blank lines, and lines starting with '#', are
+    /// ignored.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::default().with_domains(
+            s.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        ))
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for DomainList {
+    fn from_iter<I: IntoIterator<Item = S>>(domains: I) -> Self {
+        Self::default().with_domains(domains)
+    }
+}
+
+impl DomainList {
+    ///
+    /// The built-in list of the domains of disposable, or temporary, email services.
+    ///
+    #[cfg(feature = "disposable")]
+    pub fn disposable() -> &'static Self {
+        static LIST: std::sync::OnceLock<DomainList> = std::sync::OnceLock::new();
+        LIST.get_or_init(|| DomainList::from_str(DISPOSABLE_DOMAINS).unwrap())
+    }
+
+    ///
+    /// The built-in list of the domains of consumer webmail providers, both free services and
+    /// those provided by ISPs.
+    ///
+    #[cfg(feature = "free_provider")]
+    pub fn free_providers() -> &'static Self {
+        static LIST: std::sync::OnceLock<DomainList> = std::sync::OnceLock::new();
+        LIST.get_or_init(|| DomainList::from_str(FREE_PROVIDER_DOMAINS).unwrap())
+    }
+
+    ///
+    /// The built-in list of the IANA Special-Use Domain Names registry, RFC 6761, such as
+    /// `onion`, `home.arpa`, and `test`, none of which are publicly reachable.
+    ///
+    pub fn special_use() -> &'static Self {
+        shared_special_use()
+    }
+
+    ///
+    /// Add `domain` to the list.
+    ///
+    pub fn with_domain<S: Into<String>>(mut self, domain: S) -> Self {
+        self.insert(domain);
+        self
+    }
+
+    ///
+    /// Add each of `domains` to the list.
+    ///
+    pub fn with_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for domain in domains {
+            self.insert(domain);
+        }
+        self
+    }
+
+    ///
+    /// Add `domain` to the list, returning `true` if it was not already present.
+    ///
+    pub fn insert<S: Into<String>>(&mut self, domain: S) -> bool {
+        self.domains.insert(normalize(&domain.into()))
+    }
+
+    ///
+    /// Remove `domain` from the list, returning `true` if it was present. Sub-domains of `domain`
+    /// may still match if a parent domain remains in the list.
+    ///
+    pub fn remove(&mut self, domain: &str) -> bool {
+        self.domains.remove(&normalize(domain))
+    }
+
+    /// Returns the number of domains in the list.
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    /// Returns `true` if the list contains no domains.
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    ///
+    /// Returns `true` if the list contains `domain`, or a domain that `domain` is a sub-domain of.
+    ///
+    pub fn contains(&self, domain: &str) -> bool {
+        let domain = normalize(domain);
+        let mut suffix = domain.as_str();
+        loop {
+            if self.domains.contains(suffix) {
+                return true;
+            }
+            match suffix.split_once(DOT) {
+                Some((_, parent)) => suffix = parent,
+                None => return false,
+            }
+        }
+    }
+
+    ///
+    /// Returns `true` if the list contains the domain of `email`.
+    ///
+    pub fn matches(&self, email: &EmailAddress) -> bool {
+        self.contains(email.domain())
+    }
+}
+
+impl FromStr for DomainSuffixSet {
+    type Err = std::convert::Infallible;
+
+    ///
+    /// Parse a set with one domain per line; blank lines, and lines starting with '#', are
+    /// ignored.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::default().with_domains(
+            s.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+        ))
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for DomainSuffixSet {
+    fn from_iter<I: IntoIterator<Item = S>>(domains: I) -> Self {
+        Self::default().with_domains(domains)
+    }
+}
+
+impl From<&DomainList> for DomainSuffixSet {
+    fn from(list: &DomainList) -> Self {
+        list.domains.iter().collect()
+    }
+}
+
+impl DomainSuffixSet {
+    ///
+    /// Add `domain` to the set.
+    ///
+    pub fn with_domain<S: AsRef<str>>(mut self, domain: S) -> Self {
+        self.insert(domain);
+        self
+    }
+
+    ///
+    /// Add each of `domains` to the set.
+    ///
+    pub fn with_domains<I, S>(mut self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for domain in domains {
+            self.insert(domain);
+        }
+        self
+    }
+
+    ///
+    /// Add `domain` to the set, returning `true` if it was not already present.
+    ///
+    pub fn insert<S: AsRef<str>>(&mut self, domain: S) -> bool {
+        let domain = normalize(domain.as_ref());
+        let node = domain.rsplit(DOT).fold(&mut self.root, |node, label| {
+            node.children.entry(label.into()).or_default()
+        });
+        let inserted = !node.terminal;
+        node.terminal = true;
+        if inserted {
+            self.len += 1;
+        }
+        inserted
+    }
+
+    /// Returns the number of domains in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the set contains no domains.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///
+    /// Returns `true` if the set contains `domain`, or a domain that `domain` is a sub-domain of.
+    ///
+    pub fn contains(&self, domain: &str) -> bool {
+        let domain = normalize(domain);
+        let mut node = &self.root;
+        for label in domain.rsplit(DOT) {
+            match node.children.get(label) {
+                Some(child) if child.terminal => return true,
+                Some(child) => node = child,
+                None => return false,
+            }
+        }
+        false
+    }
+
+    ///
+    /// Returns `true` if the set contains the domain of `email`.
+    ///
+    pub fn matches(&self, email: &EmailAddress) -> bool {
+        self.contains(email.domain())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn shared_special_use() -> &'static Arc<DomainList> {
+    static LIST: std::sync::OnceLock<Arc<DomainList>> = std::sync::OnceLock::new();
+    LIST.get_or_init(|| Arc::new(DomainList::from_str(SPECIAL_USE_DOMAINS).unwrap()))
+}
+
+fn normalize(domain: &str) -> String {
+    domain.trim_end_matches(DOT).to_lowercase()
+}
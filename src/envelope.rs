@@ -0,0 +1,182 @@
+/*!
+The envelope of an SMTP transaction, RFC 5321, §2.3.1; the reverse-path given by the `MAIL`
+command, and the forward-paths given by one or more `RCPT` commands.
+
+If any address of the envelope is internationalized, the whole transaction requires the
+`SMTPUTF8` extension of RFC 6531, which is requested by a parameter of the `MAIL` command; a
+server that does not support it cannot be sent the transaction at all.
+*/
+
+use crate::{EmailAddress, ReversePath, SMTP_MAIL_FROM, SMTP_UTF8_PARAMETER};
+use std::fmt::{Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The envelope of an SMTP transaction; a sender, which may be the null reverse-path, and at
+/// least one recipient.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let envelope = Envelope::new(
+///     ReversePath::from_str("<simon@example.com>").unwrap(),
+///     vec![EmailAddress::from_str("jöran@example.org").unwrap()],
+/// )
+/// .unwrap();
+///
+/// assert!(envelope.requires_smtputf8());
+/// assert_eq!(
+///     envelope.to_commands().unwrap(),
+///     vec![
+///         "MAIL FROM:<simon@example.com> SMTPUTF8",
+///         "RCPT TO:<jöran@example.org>",
+///     ]
+/// );
+///
+/// assert_eq!(
+///     Envelope::new(ReversePath::Null, vec![]),
+///     Err(EnvelopeError::NoRecipients)
+/// );
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope {
+    reverse_path: ReversePath,
+    recipients: Vec<EmailAddress>,
+}
+
+///
+/// The reasons an `Envelope` cannot be built, or sent.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EnvelopeError {
+    /// The envelope has no recipients.
+    NoRecipients,
+    ///
+    /// The envelope has an internationalized address, given here, but the server does not
+    /// support the `SMTPUTF8` extension.
+    ///
+    Smtputf8Required(EmailAddress),
+    ///
+    /// The envelope has an address, given here, that is not an RFC 5321 `Mailbox`, as tested by
+    /// `EmailAddress::is_smtp_mailbox`, and so cannot be written into a command.
+    ///
+    NotSmtpMailbox(EmailAddress),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for EnvelopeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvelopeError::NoRecipients => write!(f, "The envelope has no recipients."),
+            EnvelopeError::Smtputf8Required(email) => write!(
+                f,
+                "The address {} requires the {} extension.",
+                email, SMTP_UTF8_PARAMETER
+            ),
+            EnvelopeError::NotSmtpMailbox(email) => {
+                write!(
+                    f,
+                    "The address {:?} is not an SMTP mailbox.",
+                    email.as_str()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+impl Envelope {
+    ///
+    /// Create an envelope, which must have at least one recipient.
+    ///
+    pub fn new<P: Into<ReversePath>>(
+        reverse_path: P,
+        recipients: Vec<EmailAddress>,
+    ) -> Result<Self, EnvelopeError> {
+        if recipients.is_empty() {
+            return Err(EnvelopeError::NoRecipients);
+        }
+        Ok(Self {
+            reverse_path: reverse_path.into(),
+            recipients,
+        })
+    }
+
+    ///
+    /// Add `recipient` to the envelope.
+    ///
+    pub fn with_recipient(mut self, recipient: EmailAddress) -> Self {
+        self.recipients.push(recipient);
+        self
+    }
+
+    /// Returns the reverse-path, the sender of the envelope.
+    pub fn reverse_path(&self) -> &ReversePath {
+        &self.reverse_path
+    }
+
+    /// Returns the recipients of the envelope, in the order they were added.
+    pub fn recipients(&self) -> &[EmailAddress] {
+        &self.recipients
+    }
+
+    ///
+    /// Returns `true` if the sender or any recipient is internationalized, so that the
+    /// transaction requires the `SMTPUTF8` extension.
+    ///
+    pub fn requires_smtputf8(&self) -> bool {
+        self.addresses().any(EmailAddress::requires_smtputf8)
+    }
+
+    ///
+    /// Check that the envelope can be sent to a server, where `smtputf8` is `true` if the
+    /// server supports the `SMTPUTF8` extension; otherwise the first internationalized address
+    /// is returned in the error.
+    ///
+    pub fn check_smtputf8(&self, smtputf8: bool) -> Result<(), EnvelopeError> {
+        match self.addresses().find(|email| email.requires_smtputf8()) {
+            Some(email) if !smtputf8 => Err(EnvelopeError::Smtputf8Required(email.clone())),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Returns the `MAIL` command, with the `SMTPUTF8` parameter if it is required, followed by
+    /// a `RCPT` command for each recipient; each without the trailing CRLF. If any address is
+    /// not an SMTP mailbox, the first is returned in the error.
+    ///
+    pub fn to_commands(&self) -> Result<Vec<String>, EnvelopeError> {
+        if let Some(email) = self.addresses().find(|email| !email.is_smtp_mailbox()) {
+            return Err(EnvelopeError::NotSmtpMailbox(email.clone()));
+        }
+        let mut mail = format!("{}{}", SMTP_MAIL_FROM, self.reverse_path);
+        if self.requires_smtputf8() {
+            mail.push(' ');
+            mail.push_str(SMTP_UTF8_PARAMETER);
+        }
+        Ok(std::iter::once(mail)
+            .chain(
+                self.recipients
+                    .iter()
+                    .filter_map(EmailAddress::to_rcpt_to_command),
+            )
+            .collect())
+    }
+
+    fn addresses(&self) -> impl Iterator<Item = &EmailAddress> {
+        self.reverse_path
+            .mailbox()
+            .into_iter()
+            .chain(&self.recipients)
+    }
+}
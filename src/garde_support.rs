@@ -0,0 +1,64 @@
+/*!
+Integration with the derive macro of the `garde` crate, replacing its own check of email
+addresses, which is based on the HTML form rules, with this crate's; requires the `garde`
+feature.
+
+Fields that are strings can be checked with the custom function `garde_email_address`, and
+fields that are already an `EmailAddress`, which is always valid, can be given the `dive`
+rule.
+
+```rust
+use email_address::EmailAddress;
+use garde::Validate;
+
+#[derive(Validate)]
+struct SignUp {
+    #[garde(custom(email_address::garde_email_address))]
+    email: String,
+    #[garde(dive)]
+    backup: Option<EmailAddress>,
+}
+
+let sign_up = SignUp {
+    email: "\"simon johnston\"@example.com".to_string(),
+    backup: None,
+};
+assert!(sign_up.validate().is_ok());
+
+let sign_up = SignUp {
+    email: "simon@example..com".to_string(),
+    backup: None,
+};
+let report = sign_up.validate().unwrap_err();
+assert_eq!(report.iter().next().unwrap().0.to_string(), "email");
+```
+*/
+
+use crate::EmailAddress;
+use garde::error::{Path, Report};
+use garde::Validate;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Check that `value` is a valid address, for use as a `garde` custom function with any
+/// context; the error has the message of this crate's `Error`.
+///
+pub fn garde_email_address<S: AsRef<str> + ?Sized, C>(value: &S, _: &C) -> garde::Result {
+    EmailAddress::from_str(value.as_ref())
+        .map(|_| ())
+        .map_err(|error| garde::Error::new(error.to_string()))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Validate for EmailAddress {
+    type Context = ();
+
+    fn validate_into(&self, _: &Self::Context, _: &mut dyn FnMut() -> Path, _: &mut Report) {}
+}
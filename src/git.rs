@@ -0,0 +1,131 @@
+/*!
+Parsing of the author and committer identities written by git, `Name <email>`, optionally
+followed by the time, in seconds since the Unix epoch, and the UTC offset, as in the headers of
+a commit object, `Name <email> 1700000000 +0100`.
+
+Git does not validate the address; it records whatever the user configured, and when nothing is
+configured, it builds one from the user and host names, such as `simon@laptop.(none)`. The raw
+address is therefore always kept, alongside the result of parsing it.
+*/
+
+use crate::{EmailAddress, Error, Options, Preset, GT, LT};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An author or committer identity written by git.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let ident = GitIdent::parse("Simon Johnston <simon@example.com> 1700000000 +0100").unwrap();
+/// assert_eq!(ident.name(), "Simon Johnston");
+/// assert_eq!(ident.address().unwrap().domain(), "example.com");
+/// assert_eq!(ident.time(), Some((1700000000, 60)));
+///
+/// let ident = GitIdent::parse("simon <simon@laptop.(none)>").unwrap();
+/// assert_eq!(ident.email(), "simon@laptop.(none)");
+/// assert!(ident.address().is_ok());
+/// assert!(ident.has_placeholder_domain());
+///
+/// let ident = GitIdent::parse_with("simon <simon@laptop.(none)>", &Options::default()).unwrap();
+/// assert!(ident.address().is_err());
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitIdent {
+    name: String,
+    email: String,
+    address: Result<EmailAddress, Error>,
+    time: Option<(i64, i32)>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl GitIdent {
+    ///
+    /// Parse `ident`, returning `None` if it has no address in angle brackets. The address is
+    /// parsed leniently, with the options of `Preset::Minimal`, so that any address with a
+    /// local part and a domain is accepted, as git would.
+    ///
+    pub fn parse(ident: &str) -> Option<Self> {
+        Self::parse_with(ident, &Options::from(Preset::Minimal))
+    }
+
+    ///
+    /// Parse `ident`, parsing its address using the rules selected by `options`.
+    ///
+    pub fn parse_with(ident: &str, options: &Options) -> Option<Self> {
+        let ident = ident.trim_end_matches(['\r', '\n']);
+        let start = ident.find(LT)?;
+        let end = start + ident[start..].find(GT)?;
+        let email = ident[start + 1..end].trim();
+        let time = time(ident[end + 1..].trim());
+        Some(Self {
+            name: ident[..start].trim().to_string(),
+            email: email.to_string(),
+            address: EmailAddress::parse_with(email, &options.for_unbracketed()),
+            time,
+        })
+    }
+
+    /// Returns the name, which may be empty.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the address as written by git, without the angle brackets.
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+
+    /// Returns the parsed address, or the error from parsing it.
+    pub fn address(&self) -> Result<&EmailAddress, &Error> {
+        self.address.as_ref()
+    }
+
+    ///
+    /// Returns `true` if the domain of the address is one git makes up when it cannot find the
+    /// domain of the host, such as `laptop.(none)`; the address is then not deliverable.
+    ///
+    pub fn has_placeholder_domain(&self) -> bool {
+        self.email.ends_with(PLACEHOLDER_DOMAIN)
+    }
+
+    ///
+    /// Returns the time, in seconds since the Unix epoch, and the UTC offset, in minutes, if
+    /// they follow the address.
+    ///
+    pub fn time(&self) -> Option<(i64, i32)> {
+        self.time
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn time(s: &str) -> Option<(i64, i32)> {
+    let (seconds, offset) = s.split_once(' ')?;
+    let seconds = seconds.parse().ok()?;
+    let offset = offset.trim();
+    let (sign, offset) = match (offset.strip_prefix('+'), offset.strip_prefix('-')) {
+        (Some(offset), _) => (1, offset),
+        (_, Some(offset)) => (-1, offset),
+        _ => return None,
+    };
+    if offset.len() != 4 || !offset.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = offset[..2].parse().ok()?;
+    let minutes: i32 = offset[2..].parse().ok()?;
+    Some((seconds, sign * (hours * 60 + minutes)))
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const PLACEHOLDER_DOMAIN: &str = ".(none)";
@@ -0,0 +1,396 @@
+/*!
+Translations of the messages of `Error`, so that a form can report a problem with an address in
+the language of its user; each message is identified by a stable key, returned by
+`Error::message_key`, for applications that keep their own translations. Requires the `i18n`
+feature.
+*/
+
+use crate::Error;
+use std::fmt::{Display, Formatter};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The languages that the messages of `Error` are translated into.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Language {
+    /// English, the language of `Error`'s `Display` implementation.
+    English,
+    /// German.
+    German,
+    /// French.
+    French,
+    /// Spanish.
+    Spanish,
+    /// Japanese.
+    Japanese,
+}
+
+///
+/// An `Error` displayed in a given language, returned by `Error::localized`.
+///
+#[derive(Debug, Clone, Copy)]
+pub struct Localized<'a> {
+    error: &'a Error,
+    language: Language,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn localized(error: &Error, language: Language) -> Localized<'_> {
+    Localized { error, language }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Language {
+    ///
+    /// Returns the language of a BCP 47 language tag, such as `de` or `fr-CA`, by its primary
+    /// subtag, or `None` if there is no translation into that language.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// assert_eq!(Language::from_tag("de-AT"), Some(Language::German));
+    /// assert_eq!(Language::from_tag("JA"), Some(Language::Japanese));
+    /// assert_eq!(Language::from_tag("sv"), None);
+    /// ```
+    ///
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next().unwrap_or_default();
+        LANGUAGES
+            .iter()
+            .find(|(subtag, _)| primary.eq_ignore_ascii_case(subtag))
+            .map(|(_, language)| *language)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Localized<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let key = self.error.message_key();
+        let template = MESSAGES
+            .iter()
+            .find(|(message_key, _)| *message_key == key)
+            .and_then(|(_, translations)| {
+                let index = match self.language {
+                    Language::English => return None,
+                    Language::German => 0,
+                    Language::French => 1,
+                    Language::Spanish => 2,
+                    Language::Japanese => 3,
+                };
+                Some(translations[index])
+            });
+        match template {
+            Some(template) => {
+                let (c, index) = arguments(self.error);
+                let mut message = template.to_string();
+                if let Some(c) = c {
+                    message = message.replace(CHARACTER, &format!("{:?}", c));
+                }
+                if let Some(index) = index {
+                    message = message.replace(POSITION, &index.to_string());
+                }
+                f.write_str(&message)
+            }
+            None => Display::fmt(self.error, f),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn arguments(error: &Error) -> (Option<char>, Option<usize>) {
+    match error {
+        Error::LocalPartInvalidCharacter(c, index)
+        | Error::QuotedStringInvalidCharacter(c, index)
+        | Error::DomainInvalidCharacter(c, index)
+        | Error::DomainLiteralInvalidCharacter(c, index) => (Some(*c), Some(*index)),
+        Error::InvalidUtf8(index) | Error::InvalidXtext(index) => (None, Some(*index)),
+        _ => (None, None),
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const CHARACTER: &str = "{c}";
+
+const POSITION: &str = "{index}";
+
+const LANGUAGES: &[(&str, Language)] = &[
+    ("en", Language::English),
+    ("de", Language::German),
+    ("fr", Language::French),
+    ("es", Language::Spanish),
+    ("ja", Language::Japanese),
+];
+
+//
+// The translations of each message, by key, in the order German, French, Spanish, Japanese.
+//
+const MESSAGES: &[(&str, [&str; 4])] = &[
+    (
+        "local-part-invalid-character",
+        [
+            "Ungültiges Zeichen {c} im lokalen Teil an Position {index}.",
+            "Caractère {c} invalide dans la partie locale à la position {index}.",
+            "Carácter {c} no válido en la parte local en la posición {index}.",
+            "ローカル部の位置 {index} に無効な文字 {c} があります。",
+        ],
+    ),
+    (
+        "quoted-string-invalid-character",
+        [
+            "Ungültiges Zeichen {c} in einer Zeichenkette in Anführungszeichen an Position {index}.",
+            "Caractère {c} invalide dans une chaîne entre guillemets à la position {index}.",
+            "Carácter {c} no válido en una cadena entre comillas en la posición {index}.",
+            "引用符で囲まれた文字列の位置 {index} に無効な文字 {c} があります。",
+        ],
+    ),
+    (
+        "domain-invalid-character",
+        [
+            "Ungültiges Zeichen {c} in der Domain an Position {index}.",
+            "Caractère {c} invalide dans le domaine à la position {index}.",
+            "Carácter {c} no válido en el dominio en la posición {index}.",
+            "ドメインの位置 {index} に無効な文字 {c} があります。",
+        ],
+    ),
+    (
+        "domain-literal-invalid-character",
+        [
+            "Ungültiges Zeichen {c} in einem Domain-Literal an Position {index}.",
+            "Caractère {c} invalide dans un littéral de domaine à la position {index}.",
+            "Carácter {c} no válido en un literal de dominio en la posición {index}.",
+            "ドメインリテラルの位置 {index} に無効な文字 {c} があります。",
+        ],
+    ),
+    (
+        "local-part-empty",
+        [
+            "Der lokale Teil ist leer.",
+            "La partie locale est vide.",
+            "La parte local está vacía.",
+            "ローカル部が空です。",
+        ],
+    ),
+    (
+        "local-part-too-long",
+        [
+            "Der lokale Teil ist zu lang.",
+            "La partie locale est trop longue.",
+            "La parte local es demasiado larga.",
+            "ローカル部が長すぎます。",
+        ],
+    ),
+    (
+        "domain-empty",
+        [
+            "Die Domain ist leer.",
+            "Le domaine est vide.",
+            "El dominio está vacío.",
+            "ドメインが空です。",
+        ],
+    ),
+    (
+        "domain-too-long",
+        [
+            "Die Domain ist zu lang.",
+            "Le domaine est trop long.",
+            "El dominio es demasiado largo.",
+            "ドメインが長すぎます。",
+        ],
+    ),
+    (
+        "sub-domain-too-long",
+        [
+            "Eine Subdomain ist zu lang.",
+            "Un sous-domaine est trop long.",
+            "Un subdominio es demasiado largo.",
+            "サブドメインが長すぎます。",
+        ],
+    ),
+    (
+        "address-too-long",
+        [
+            "Die Adresse ist zu lang.",
+            "L'adresse est trop longue.",
+            "La dirección es demasiado larga.",
+            "アドレスが長すぎます。",
+        ],
+    ),
+    (
+        "missing-separator",
+        [
+            "Das Trennzeichen '@' fehlt.",
+            "Le séparateur '@' est manquant.",
+            "Falta el separador '@'.",
+            "区切り文字 '@' がありません。",
+        ],
+    ),
+    (
+        "domain-too-few",
+        [
+            "Die Domain hat zu wenige Teile.",
+            "Le domaine comporte trop peu de parties.",
+            "El dominio tiene muy pocas partes.",
+            "ドメインの部分が少なすぎます。",
+        ],
+    ),
+    (
+        "domain-too-many",
+        [
+            "Die Domain hat zu viele Teile.",
+            "Le domaine comporte trop de parties.",
+            "El dominio tiene demasiadas partes.",
+            "ドメインの部分が多すぎます。",
+        ],
+    ),
+    (
+        "too-many-tag-separators",
+        [
+            "Zu viele aufeinanderfolgende Unteradress-Trennzeichen im lokalen Teil.",
+            "Trop de séparateurs de sous-adresse consécutifs dans la partie locale.",
+            "Demasiados separadores de subdirección consecutivos en la parte local.",
+            "ローカル部に連続するサブアドレス区切り文字が多すぎます。",
+        ],
+    ),
+    (
+        "percent-hack-routing",
+        [
+            "Routing mit '%' im lokalen Teil ist nicht erlaubt.",
+            "Le routage par '%' dans la partie locale n'est pas autorisé.",
+            "El enrutamiento con '%' en la parte local no está permitido.",
+            "ローカル部での '%' によるルーティングは許可されていません。",
+        ],
+    ),
+    (
+        "bang-path-routing",
+        [
+            "Routing mit '!' im lokalen Teil ist nicht erlaubt.",
+            "Le routage par '!' dans la partie locale n'est pas autorisé.",
+            "El enrutamiento con '!' en la parte local no está permitido.",
+            "ローカル部での '!' によるルーティングは許可されていません。",
+        ],
+    ),
+    (
+        "domain-invalid-separator",
+        [
+            "Ungültige Position des Domain-Trennzeichens '.'.",
+            "Position invalide du séparateur de domaine '.'.",
+            "Posición no válida del separador de dominio '.'.",
+            "ドメイン区切り文字 '.' の位置が無効です。",
+        ],
+    ),
+    (
+        "invalid-ip-address",
+        [
+            "Ungültige IP-Adresse als Domain angegeben.",
+            "Adresse IP invalide indiquée pour le domaine.",
+            "Dirección IP no válida especificada para el dominio.",
+            "ドメインに無効な IP アドレスが指定されています。",
+        ],
+    ),
+    (
+        "unbalanced-quotes",
+        [
+            "Die Anführungszeichen um den lokalen Teil sind nicht ausgeglichen.",
+            "Les guillemets autour de la partie locale ne sont pas équilibrés.",
+            "Las comillas alrededor de la parte local no están equilibradas.",
+            "ローカル部を囲む引用符の対応が取れていません。",
+        ],
+    ),
+    (
+        "invalid-comment",
+        [
+            "Ein Kommentar ist fehlerhaft.",
+            "Un commentaire est mal formé.",
+            "Un comentario está mal formado.",
+            "コメントの形式が正しくありません。",
+        ],
+    ),
+    (
+        "unsupported-quoted-local-part",
+        [
+            "Lokale Teile in Anführungszeichen sind nicht erlaubt.",
+            "Les parties locales entre guillemets ne sont pas autorisées.",
+            "No se permiten partes locales entre comillas.",
+            "引用符で囲まれたローカル部は許可されていません。",
+        ],
+    ),
+    (
+        "unsupported-domain-literal",
+        [
+            "Domain-Literale sind nicht erlaubt.",
+            "Les littéraux de domaine ne sont pas autorisés.",
+            "No se permiten literales de dominio.",
+            "ドメインリテラルは許可されていません。",
+        ],
+    ),
+    (
+        "invalid-utf8",
+        [
+            "Ungültiges UTF-8 an Position {index}.",
+            "UTF-8 invalide à la position {index}.",
+            "UTF-8 no válido en la posición {index}.",
+            "位置 {index} の UTF-8 が無効です。",
+        ],
+    ),
+    (
+        "missing-angle-brackets",
+        [
+            "Die spitzen Klammern '<' und '>' fehlen.",
+            "Les chevrons '<' et '>' sont manquants.",
+            "Faltan los corchetes angulares '<' y '>'.",
+            "山括弧 '<' と '>' がありません。",
+        ],
+    ),
+    (
+        "invalid-xtext",
+        [
+            "Ungültiges xtext an Position {index}.",
+            "xtext invalide à la position {index}.",
+            "xtext no válido en la posición {index}.",
+            "位置 {index} の xtext が無効です。",
+        ],
+    ),
+    (
+        "internal-domain",
+        [
+            "Interne Domains sind nicht erlaubt.",
+            "Les domaines internes ne sont pas autorisés.",
+            "No se permiten dominios internos.",
+            "内部ドメインは許可されていません。",
+        ],
+    ),
+    (
+        "denied-domain",
+        [
+            "Die Domain ist nicht erlaubt.",
+            "Le domaine n'est pas autorisé.",
+            "El dominio no está permitido.",
+            "このドメインは許可されていません。",
+        ],
+    ),
+    (
+        "non-global-address-literal",
+        [
+            "Das Adressliteral ist keine global routbare IP-Adresse.",
+            "Le littéral d'adresse n'est pas une adresse IP routable globalement.",
+            "El literal de dirección no es una dirección IP enrutable globalmente.",
+            "アドレスリテラルはグローバルにルーティング可能な IP アドレスではありません。",
+        ],
+    ),
+];
@@ -0,0 +1,130 @@
+/*!
+Domain interning for parsing large numbers of addresses, in which the same few domains repeat
+heavily; each distinct domain is stored once, and shared by every address at that domain.
+*/
+
+use crate::{EmailAddress, Error, Options};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A parser that interns the domains of the addresses it parses.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::sync::Arc;
+///
+/// let mut interner = Interner::default();
+/// let simon = interner.parse("simon@example.com").unwrap();
+/// let jane = interner.parse("jane@example.com").unwrap();
+///
+/// assert!(Arc::ptr_eq(simon.shared_domain(), jane.shared_domain()));
+/// assert_eq!(interner.len(), 1);
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    options: Options,
+    domains: HashSet<Arc<str>>,
+}
+
+///
+/// An address whose domain is shared with other addresses parsed by the same `Interner`.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct InternedAddress {
+    local: Box<str>,
+    domain: Arc<str>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Interner {
+    ///
+    /// Parse addresses using the rules selected by `options`, rather than the defaults.
+    ///
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    ///
+    /// Parse `address`, sharing its domain with any address parsed earlier with the same
+    /// domain; domains are only shared if they are written identically, including case.
+    ///
+    pub fn parse(&mut self, address: &str) -> Result<InternedAddress, Error> {
+        let email = EmailAddress::parse_with(address, &self.options)?;
+        Ok(self.intern(email))
+    }
+
+    ///
+    /// Convert `email`, sharing its domain with any address parsed earlier with the same domain.
+    ///
+    pub fn intern(&mut self, email: EmailAddress) -> InternedAddress {
+        let domain = match self.domains.get(email.domain()) {
+            Some(domain) => domain.clone(),
+            None => {
+                let domain: Arc<str> = Arc::from(email.domain());
+                self.domains.insert(domain.clone());
+                domain
+            }
+        };
+        InternedAddress {
+            local: email.local_part().into(),
+            domain,
+        }
+    }
+
+    /// Returns the number of distinct domains interned.
+    pub fn len(&self) -> usize {
+        self.domains.len()
+    }
+
+    /// Returns `true` if no domains have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.domains.is_empty()
+    }
+
+    ///
+    /// Forget the interned domains; addresses already parsed keep their domains.
+    ///
+    pub fn clear(&mut self) {
+        self.domains.clear();
+    }
+}
+
+impl Display for InternedAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.local, self.domain)
+    }
+}
+
+impl From<InternedAddress> for EmailAddress {
+    fn from(email: InternedAddress) -> Self {
+        EmailAddress::from_parts(&email.local, &email.domain)
+    }
+}
+
+impl InternedAddress {
+    /// Returns the local part of the address.
+    pub fn local_part(&self) -> &str {
+        &self.local
+    }
+
+    /// Returns the domain of the address.
+    pub fn domain(&self) -> &str {
+        &self.domain
+    }
+
+    /// Returns the shared domain of the address.
+    pub fn shared_domain(&self) -> &Arc<str> {
+        &self.domain
+    }
+}
@@ -0,0 +1,105 @@
+/*!
+Addresses as the keys of maps and sets, compared by their normal form under an `Equivalence`
+rather than by the address as given.
+*/
+
+use crate::{EmailAddress, Equivalence};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An address with its normal form under an `Equivalence`, where equality, hashing, and
+/// ordering are those of the normal form, so that addresses taken to be the same mailbox are
+/// the same key. The address as given is kept, for display to a user or for sending to.
+///
+/// Keys are only meaningfully compared with keys made under the same `Equivalence`.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::collections::HashMap;
+/// use std::str::FromStr;
+///
+/// let mut contacts = HashMap::new();
+/// let email = EmailAddress::from_str("simon+news@Example.COM").unwrap();
+/// contacts.insert(EmailKey::new(email, Equivalence::IgnoreTags), "Simon");
+///
+/// let email = EmailAddress::from_str("simon@example.com").unwrap();
+/// let key = EmailKey::new(email, Equivalence::IgnoreTags);
+/// assert_eq!(contacts.get(&key), Some(&"Simon"));
+/// assert_eq!(key.normalized().as_str(), "simon@example.com");
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct EmailKey {
+    normalized: EmailAddress,
+    email: EmailAddress,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl PartialEq for EmailKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.normalized == other.normalized
+    }
+}
+
+impl Eq for EmailKey {}
+
+impl Hash for EmailKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized.hash(state);
+    }
+}
+
+impl PartialOrd for EmailKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EmailKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.normalized.as_str().cmp(other.normalized.as_str())
+    }
+}
+
+impl Display for EmailKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.normalized, f)
+    }
+}
+
+impl From<EmailKey> for EmailAddress {
+    fn from(key: EmailKey) -> Self {
+        key.email
+    }
+}
+
+impl EmailKey {
+    ///
+    /// Create a key for `email`, normalized under the rules of `equivalence`.
+    ///
+    pub fn new(email: EmailAddress, equivalence: Equivalence) -> Self {
+        Self {
+            normalized: equivalence.key(&email),
+            email,
+        }
+    }
+
+    /// Returns the normal form of the address, by which keys are compared.
+    pub fn normalized(&self) -> &EmailAddress {
+        &self.normalized
+    }
+
+    /// Returns the address as it was given.
+    pub fn email(&self) -> &EmailAddress {
+        &self.email
+    }
+}
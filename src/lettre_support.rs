@@ -0,0 +1,66 @@
+/*!
+Conversions between `EmailAddress` and the address types of the `lettre` crate, so that
+addresses validated here can be used to build messages; requires the `lettre` feature.
+
+The conversions are fallible in both directions, as each crate validates addresses with its own
+rules, and so may reject an address that the other accepts.
+*/
+
+use crate::{EmailAddress, Error};
+use lettre::address::AddressError;
+use lettre::message::Mailbox;
+use lettre::Address;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl TryFrom<&EmailAddress> for Address {
+    type Error = AddressError;
+
+    fn try_from(email: &EmailAddress) -> Result<Self, Self::Error> {
+        Address::new(email.local_part(), email.domain())
+    }
+}
+
+impl TryFrom<EmailAddress> for Address {
+    type Error = AddressError;
+
+    fn try_from(email: EmailAddress) -> Result<Self, Self::Error> {
+        Address::try_from(&email)
+    }
+}
+
+impl TryFrom<&EmailAddress> for Mailbox {
+    type Error = AddressError;
+
+    fn try_from(email: &EmailAddress) -> Result<Self, Self::Error> {
+        Ok(Mailbox::new(None, Address::try_from(email)?))
+    }
+}
+
+impl TryFrom<EmailAddress> for Mailbox {
+    type Error = AddressError;
+
+    fn try_from(email: EmailAddress) -> Result<Self, Self::Error> {
+        Mailbox::try_from(&email)
+    }
+}
+
+impl TryFrom<&Address> for EmailAddress {
+    type Error = Error;
+
+    fn try_from(address: &Address) -> Result<Self, Self::Error> {
+        EmailAddress::from_str(address.as_ref())
+    }
+}
+
+impl TryFrom<Address> for EmailAddress {
+    type Error = Error;
+
+    fn try_from(address: Address) -> Result<Self, Self::Error> {
+        EmailAddress::try_from(&address)
+    }
+}
@@ -4,8 +4,8 @@ A Rust crate providing an implementation of an RFC-compliant `EmailAddress` newt
 Primarily for validation, the `EmailAddress` type is constructed with `FromStr::from_str` which will raise any
 parsing errors. Prior to constructions the functions `is_valid`, `is_valid_local_part`, and `is_valid_domain` may
 also be used to test for validity without constructing an instance. This supports all of the RFC ASCII and UTF-8
-character set rules, quoted and unquoted local parts but does not yet support all of the productions required for SMTP
-headers; folding whitespace, comments, etc.
+character set rules, quoted and unquoted local parts. The productions required for SMTP headers; folding whitespace,
+comments, and obsolete forms, are supported by `EmailAddress::parse_with` with the `Profile::Rfc5322` options.
 
 # Example
 
@@ -46,6 +46,23 @@ assert_eq!(
 );
 ```
 
+# Length Limits
+
+The limits on the length of the `local-part` (64), the `domain` (254), and each `sub-domain` (63)
+are measured in octets of the UTF-8 encoded string, as required by RFC 6531, and not in characters.
+With the `idna` feature enabled, `Options::with_a_label_lengths` measures the domain in its A-label
+(punycode) form instead.
+
+# Error Reports
+
+With the `miette` feature enabled, both `Error` and `ParseError` implement `miette::Diagnostic`;
+a `ParseError` labels the span of the address in error, so that a report including the address as
+its source code points to the problem.
+
+```rust,ignore
+let error = EmailAddress::parse_spanned(address, &Options::default()).unwrap_err();
+return Err(miette::Report::new(error).with_source_code(address.to_string()));
+```
 
 # Specifications
 
@@ -235,35 +252,168 @@ An informal description can be found on [Wikipedia](https://en.wikipedia.org/wik
     rust_2018_idioms
 )]
 
+// The `email!` macro names this crate by path, so it can be used within it.
+#[cfg(all(test, feature = "macros"))]
+extern crate self as email_address;
+
 #[cfg(feature = "serde_support")]
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt::{Debug, Display, Formatter};
-use std::ops::Deref;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
 use std::str::FromStr;
+use std::sync::Arc;
+
+mod batv;
+mod borrowed;
+mod canonical;
+pub mod corpus;
+mod dedupe;
+mod diagnostic;
+#[cfg(feature = "differential")]
+mod differential;
+#[cfg(feature = "dns")]
+mod dns;
+mod domains;
+mod envelope;
+#[cfg(feature = "garde")]
+mod garde_support;
+mod git;
+#[cfg(feature = "i18n")]
+mod i18n;
+mod intern;
+mod key;
+#[cfg(feature = "lettre")]
+mod lettre_support;
+mod lines;
+mod list;
+#[cfg(any(feature = "mail-builder", feature = "mail-parser"))]
+mod mail_support;
+mod message_id;
+mod openpgp;
+mod parser;
+mod parts;
+mod path;
+#[cfg(feature = "psl")]
+mod psl;
+#[cfg(feature = "pyo3")]
+mod pyo3_support;
+#[cfg(feature = "serde_support")]
+pub mod serde_lowercase;
+mod shared;
+#[cfg(feature = "smtp")]
+mod smtp;
+mod suggest;
+#[cfg(feature = "validator")]
+mod validator_support;
+mod verify;
+#[cfg(feature = "web")]
+mod web;
+mod xtext;
+
+pub use batv::Batv;
+pub use borrowed::BorrowedEmailAddress;
+pub use canonical::{ProviderRule, ProviderRules};
+pub use dedupe::{Deduplicated, Equivalence};
+pub use diagnostic::{Category, Diagnostic};
+#[cfg(feature = "differential")]
+pub use differential::{Differential, Disagreement};
+#[cfg(feature = "dns")]
+pub use dns::DnsCheck;
+pub use domains::{DomainList, DomainSuffixSet};
+///
+/// Parse a string literal as an `EmailAddress` at compile time; requires the `macros` feature.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let email: EmailAddress = email!("ops@example.com");
+/// assert_eq!(email.domain(), "example.com");
+/// ```
+///
+/// An invalid address does not compile:
+///
+/// ```rust,compile_fail
+/// use email_address::*;
+///
+/// let email = email!("ops@exa mple.com");
+/// ```
+///
+#[cfg(feature = "macros")]
+pub use email_address_macros::email;
+pub use envelope::{Envelope, EnvelopeError};
+#[cfg(feature = "garde")]
+pub use garde_support::garde_email_address;
+pub use git::GitIdent;
+#[cfg(feature = "i18n")]
+pub use i18n::{Language, Localized};
+pub use intern::{InternedAddress, Interner};
+pub use key::EmailKey;
+pub use lines::AddressLines;
+pub use list::EmailAddressList;
+pub use message_id::MessageId;
+pub use openpgp::UserId;
+use parser::Collector;
+pub use parts::{Domain, DomainName, DomainPart, IpScope, LocalPart, LocalPartKind};
+pub use path::{ForwardPath, Path, ReversePath};
+#[cfg(feature = "pyo3")]
+pub use pyo3_support::{EmailAddressError, PyEmailAddress};
+pub use shared::SharedEmailAddress;
+#[cfg(feature = "smtp")]
+pub use smtp::{Callout, CalloutResult, Reply};
+pub use suggest::Suggester;
+#[cfg(feature = "validator")]
+pub use validator_support::validate_email_address;
+pub use verify::{
+    Check, CheckReport, CheckStatus, Policy, Reason, Verdict, VerificationReport, Verifier,
+};
+#[cfg(feature = "web")]
+pub use web::{EmailField, FieldError, FieldErrors};
 
 // ------------------------------------------------------------------------------------------------
 // Public Types
 // ------------------------------------------------------------------------------------------------
 
 ///
-/// Error type used when parsing an address.
+/// Error type used when parsing an address. The variants that report an invalid character
+/// include the character and its byte offset in the parsed string.
+///
+/// With the `serde_support` feature enabled an error is serialized with its kind, as a stable
+/// kebab-case name, and any details, as in `{"error":"local-part-invalid-character",
+/// "details":[" ",3]}`.
 ///
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(Serialize, Deserialize),
+    serde(tag = "error", content = "details", rename_all = "kebab-case")
+)]
+#[non_exhaustive]
 pub enum Error {
-    /// An invalid character was found in some component of the address.
-    InvalidCharacter,
+    /// An invalid character was found in an unquoted `local-part`.
+    LocalPartInvalidCharacter(char, usize),
+    /// An invalid character was found in a quoted string in the `local-part`.
+    QuotedStringInvalidCharacter(char, usize),
+    /// An invalid character was found in a `sub-domain` of the `domain`.
+    DomainInvalidCharacter(char, usize),
+    /// An invalid character was found in a `domain-literal`.
+    DomainLiteralInvalidCharacter(char, usize),
     /// The separator character between `local-part` and `domain` (character: '@') was missing.
     MissingSeparator,
     /// The `local-part` is an empty string.
     LocalPartEmpty,
-    /// The `local-part` is is too long.
+    /// The `local-part` is longer than the limit set in `Options`, by default 64 octets.
     LocalPartTooLong,
     /// The `domain` is an empty string.
     DomainEmpty,
-    /// The `domain` is is too long.
+    /// The `domain` is longer than the limit set in `Options`, by default 254 octets.
     DomainTooLong,
-    /// A `sub-domain` within the `domain` is is too long.
+    /// A `sub-domain` within the `domain` is longer than the limit set in `Options`, by default
+    /// 63 octets.
     SubDomainTooLong,
+    /// The address as a whole is longer than the limit set in `Options`, by default 254 octets.
+    AddressTooLong,
     /// Too few `sub-domain`s in `domain`.
     DomainTooFew,
     /// Invalid placement of the domain separator (character: '.').
@@ -273,35 +423,301 @@ pub enum Error {
     /// A Comment within the either the `local-part`, or `domain`, was malformed.
     InvalidComment,
     /// An IP address in a `domain-literal` was malformed.
+    #[cfg_attr(feature = "serde_support", serde(rename = "invalid-ip-address"))]
     InvalidIPAddress,
-    /// This can't happen
-    CantHappen,
+    /// The `local-part` is a quoted string, which is not allowed by the `Options` used.
+    UnsupportedQuotedLocalPart,
+    /// The `domain` is a `domain-literal`, which is not allowed by the `Options` used.
+    UnsupportedDomainLiteral,
+    /// The byte at the given offset of the parsed bytes does not begin a valid UTF-8 sequence.
+    InvalidUtf8(usize),
+    /// The angle brackets (characters: '<' and '>') required around a value are missing.
+    MissingAngleBrackets,
+    /// The character at the given offset is not valid in RFC 3461 `xtext`.
+    InvalidXtext(usize),
+    /// The `domain` is internal, such as `localhost`, a single label, or a `.local` or
+    /// `.internal` name, which is not allowed by the `Options` used.
+    InternalDomain,
+    /// The `domain` is, or is a sub-domain of, a domain denied by the `Options` used.
+    DeniedDomain,
+    /// The `domain` is an address literal whose IP address is not globally routable, which is
+    /// not allowed by the `Options` used.
+    NonGlobalAddressLiteral,
+    /// The `domain` has more `sub-domain`s than the `Options` used allow.
+    DomainTooMany,
+    /// The `local-part` has a longer run of sub-address separators, such as '+', than the
+    /// `Options` used allow.
+    TooManyTagSeparators,
+    /// The `local-part` contains a '%', which some relays use to route to the host that
+    /// follows it, the "percent hack", which is not allowed by the `Options` used.
+    PercentHackRouting,
+    /// The `local-part` contains a '!', which some relays use to route a UUCP-style bang path,
+    /// which is not allowed by the `Options` used.
+    BangPathRouting,
+}
+
+///
+/// An `Error` as plain data with a C-compatible layout, for FFI and WASM bindings and for
+/// metrics; returned by `Error::error_code`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct ErrorCode {
+    /// The stable code of the error, as returned by `Error::code`.
+    pub code: u16,
+    /// The invalid character, as a Unicode scalar value, or 0 if the error has none.
+    pub character: u32,
+    /// The byte offset reported by the error, or `ErrorCode::NO_POSITION` if it has none.
+    pub position: usize,
+}
+
+///
+/// An `Error` together with the span, as byte offsets into the parsed string, of the part of the
+/// address that caused it. This is returned by `EmailAddress::parse_spanned` so that a user
+/// interface can highlight the problem.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize, Deserialize))]
+pub struct ParseError {
+    error: Error,
+    span: Range<usize>,
+}
+
+///
+/// A machine-applicable fix for a `ParseError`; replace the text in `span` of the parsed string
+/// with `replacement`. Suggestions are returned by `ParseError::suggestions`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde_support", derive(Serialize))]
+pub struct Suggestion {
+    message: &'static str,
+    span: Range<usize>,
+    replacement: &'static str,
+}
+
+///
+/// Describes something about a valid address that is unusual, or discouraged, and that a user
+/// interface may wish to question. These are returned by `EmailAddress::parse_with_diagnostics`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde_support",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+#[non_exhaustive]
+pub enum Warning {
+    /// The `local-part` is a quoted string; these are valid, but are rejected by many systems.
+    QuotedLocalPart,
+    /// The `domain` is a `domain-literal`, such as an IP address, rather than a domain name.
+    DomainLiteral,
+    /// The `domain` has a single `sub-domain`, and so no top-level domain.
+    DotlessDomain,
+    /// The top-level domain is entirely numeric, which no registered top-level domain is.
+    NumericTld,
+    /// A `sub-domain` does not follow the letter-digit-hyphen rule, and so is not a valid host name.
+    NonLdhDomain,
+    /// The address contains a comment, which has been removed.
+    Comment,
+    /// The address contains folding white space, which has been removed.
+    FoldingWhiteSpace,
+    /// The address uses the obsolete syntax of RFC 5322, §4.4.
+    ObsoleteSyntax,
+    /// The `local-part` is longer than the 64 octets allowed by RFC 5321.
+    LocalPartTooLong,
+    /// The `domain` is longer than the 254 octets allowed by RFC 5321.
+    DomainTooLong,
+    /// A `sub-domain` is longer than the 63 octets allowed by RFC 5321.
+    SubDomainTooLong,
+    /// The address is longer than the 254 octets allowed by RFC 5321.
+    AddressTooLong,
 }
 
 ///
-/// Type representing a single email address. This is basically a wrapper around a String, the
-/// email address is parsed for correctness with `FromStr::from_str`, which is the only want to
-/// create an instance. The various components of the email _are not_ parsed out to be accessible
-/// independently.
+/// Type representing a single email address. This is basically a wrapper around a String, and
+/// an instance is only created by parsing a string for correctness, with `FromStr::from_str`,
+/// `parse_with`, or one of the other `parse_` methods, such as `parse_bytes`, or with
+/// `from_xtext`; methods such as `to_lowercase` derive one valid address from another. The
+/// address is stored as a single string, with the position of the '@'
+/// that separates the local part and domain, so `as_str`, `local_part`, and `domain` are all
+/// views into the same buffer. With the `compact_str` feature enabled addresses of up to 24
+/// octets, which are most addresses, are stored inline without allocating.
+///
+/// When the stored form differs from the string that was parsed, such as when comments were
+/// removed or the domain was folded to lowercase, the parsed string is also kept, and returned
+/// by `original`; addresses are compared and hashed by their stored form only.
 ///
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde_support", derive(Deserialize, Serialize))]
+#[derive(Clone)]
 pub struct EmailAddress {
-    local: String,
-    domain: String,
+    address: Buffer,
+    at: usize,
+    original: Option<Box<str>>,
+}
+
+///
+/// Options that control how an address string is parsed by `EmailAddress::parse_with`. The
+/// default value applies exactly the same rules as `FromStr::from_str`, other values are
+/// constructed by calling the `with_` and `without_` methods on the default.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let options = Options::default()
+///     .without_utf8()
+///     .without_domain_literal()
+///     .with_required_tld();
+///
+/// assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+/// assert_eq!(
+///     EmailAddress::parse_with("simon@localhost", &options),
+///     Error::DomainTooFew.into()
+/// );
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    check_syntax: bool,
+    allow_utf8: bool,
+    allow_quoted_local_part: bool,
+    allow_domain_literal: bool,
+    allow_cfws: bool,
+    allow_obsolete: bool,
+    trim: bool,
+    mailto_prefix: bool,
+    lowercase_domain: bool,
+    angle_brackets: AngleBrackets,
+    ldh_domain: bool,
+    strict_domain_literal: bool,
+    minimum_sub_domains: usize,
+    max_sub_domains: Option<usize>,
+    max_consecutive_tag_separators: Option<usize>,
+    tag_separators: Cow<'static, [char]>,
+    allow_local_routing: bool,
+    allow_internal_domain: bool,
+    denied_domains: Option<Arc<DomainList>>,
+    allow_non_global_literal: bool,
+    max_local_part_length: Option<usize>,
+    max_domain_length: Option<usize>,
+    max_sub_domain_length: Option<usize>,
+    max_address_length: Option<usize>,
+    #[cfg(feature = "idna")]
+    a_label_lengths: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AngleBrackets {
+    Allowed,
+    Forbidden,
+    Required,
+}
+
+///
+/// The standards an address may be validated against, these differ in the grammar they accept and
+/// in the limits they impose. Each profile selects a complete set of parsing `Options`.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let options = Options::from(Profile::Rfc5322);
+///
+/// assert!(EmailAddress::parse_with("john (the man) . smith@example.com", &options).is_ok());
+/// assert!(!EmailAddress::is_valid("john (the man) . smith@example.com"));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Profile {
+    /// The `Mailbox` production from RFC 5321, §4.1.2, as used in the SMTP `MAIL` and `RCPT`
+    /// commands. ASCII only, no comments or folding white space, domains must follow the
+    /// letter-digit-hyphen rule, domain literals must be an IP address or tagged address, and the
+    /// length limits of §4.5.3.1 apply.
+    Rfc5321,
+    /// The `addr-spec` production from RFC 5322, §3.4.1, as used in message headers. ASCII only,
+    /// allows comments, folding white space, and the obsolete forms of §4.4, imposes no length
+    /// limits.
+    Rfc5322,
+    /// The `Mailbox` production from RFC 5321, as extended by RFC 6531, §3.3, to allow UTF-8 in
+    /// the `local-part` and U-labels in the `domain`.
+    Rfc6531,
+    /// The `rfc822Name` of an X.509 subject alternative name from RFC 5280, §4.2.1.6, as used in
+    /// certificates. As `Rfc5321`, but quoted local parts and domain literals are not allowed, as
+    /// certificate authorities and name constraints do not support them in practice.
+    X509,
+}
+
+///
+/// Curated sets of `Options` for the most common needs, so that most users need not understand
+/// each of the individual options.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let strict = Options::from(Preset::Strict);
+///
+/// assert!(EmailAddress::is_valid("admin@mailserver1"));
+/// assert!(EmailAddress::parse_with("admin@mailserver1", &strict).is_err());
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// Only checks that there is a separator ('@') with a non-empty `local-part` before it and a
+    /// non-empty `domain` after it.
+    Minimal,
+    /// The rules applied by `FromStr::from_str`, and by `Options::default()`.
+    Rfc,
+    /// Rules oriented toward deliverable addresses; a text `domain` with a top-level domain, where
+    /// each `sub-domain` follows the letter-digit-hyphen rule, no domain literals, and no angle
+    /// brackets around the address.
+    Strict,
+}
+
+///
+/// Whether `EmailAddress::normalized` preserves the case of the local part.
+///
+#[cfg(feature = "normalize")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalPartCase {
+    ///
+    /// Preserve the case of the local part, which RFC 5321 leaves to the receiving host to
+    /// interpret.
+    ///
+    Preserve,
+    /// Lowercase the local part, as almost all receiving hosts ignore its case.
+    Lowercase,
+}
+
+///
+/// The identifier alignment modes of RFC 7489 (DMARC), §3.1, used by `EmailAddress::aligned_with`.
+///
+#[cfg(feature = "psl")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlignmentMode {
+    /// The domains must have the same Organizational Domain.
+    Relaxed,
+    /// The domains must be identical.
+    Strict,
 }
 
 // ------------------------------------------------------------------------------------------------
 // Implementations
 // ------------------------------------------------------------------------------------------------
 
+///
+/// The storage of an `EmailAddress`.
+///
+#[cfg(not(feature = "compact_str"))]
+type Buffer = String;
+
+#[cfg(feature = "compact_str")]
+type Buffer = compact_str::CompactString;
+
+// All length limits are in octets, i.e. the length of the UTF-8 encoding, not in characters.
 const LOCAL_PART_MAX_LENGTH: usize = 64;
 const DOMAIN_MAX_LENGTH: usize = 254; // see: https://www.rfc-editor.org/errata_search.php?rfc=3696&eid=1690
 const SUB_DOMAIN_MAX_LENGTH: usize = 63;
+// RFC 5321 limits a `Path` to 256 octets, less the two angle brackets.
+const ADDRESS_MAX_LENGTH: usize = 254;
 
-#[allow(dead_code)]
 const CR: char = '\r';
-#[allow(dead_code)]
 const LF: char = '\n';
 const SP: char = ' ';
 const HTAB: char = '\t';
@@ -312,45 +728,132 @@ const DOT: char = '.';
 const DQUOTE: char = '"';
 const LBRACKET: char = '[';
 const RBRACKET: char = ']';
-#[allow(dead_code)]
 const LPAREN: char = '(';
-#[allow(dead_code)]
 const RPAREN: char = ')';
 const LT: char = '<';
 const GT: char = '>';
+const PERCENT_HACK: char = '%';
+const BANG_PATH: char = '!';
+
+///
+/// The separator of a sub-address tag used by `EmailAddress::tag`, as in `simon+news`.
+///
+const DEFAULT_TAG_SEPARATORS: &[char] = &['+'];
 
 const UTF8_START: char = '\u{0080}';
 
 const MAILTO_URI_PREFIX: &str = "mailto:";
 
+///
+/// The second-level domains and top-level domains reserved by RFC 2606, §2 and §3.
+///
+const RESERVED_DOMAINS: &[&str] = &[
+    "example.com",
+    "example.net",
+    "example.org",
+    "example",
+    "invalid",
+    "localhost",
+    "test",
+];
+
+///
+/// The domains, and top-level domains, only used within a private network; `localhost` from
+/// RFC 2606, `local` from RFC 6762 for multicast DNS, and `internal`, reserved by ICANN.
+///
+const INTERNAL_DOMAINS: &[&str] = &["localhost", "local", "internal"];
+
+const POSTMASTER: &str = "Postmaster";
+
+const SMTP_MAIL_FROM: &str = "MAIL FROM:";
+
+const SMTP_RCPT_TO: &str = "RCPT TO:";
+
+const SMTP_UTF8_PARAMETER: &str = "SMTPUTF8";
+
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            Error::InvalidCharacter => write!(f, "Invalid character."),
+            Error::LocalPartInvalidCharacter(c, index) => write!(
+                f,
+                "Invalid character {:?} in the local part at position {}.",
+                c, index
+            ),
+            Error::QuotedStringInvalidCharacter(c, index) => write!(
+                f,
+                "Invalid character {:?} in a quoted string at position {}.",
+                c, index
+            ),
+            Error::DomainInvalidCharacter(c, index) => write!(
+                f,
+                "Invalid character {:?} in the domain at position {}.",
+                c, index
+            ),
+            Error::DomainLiteralInvalidCharacter(c, index) => write!(
+                f,
+                "Invalid character {:?} in a domain literal at position {}.",
+                c, index
+            ),
             Error::LocalPartEmpty => write!(f, "Local part is empty."),
             Error::LocalPartTooLong => write!(
                 f,
-                "Local part is too long. Length limit: {}",
+                "Local part is too long. Length limit: {} octets by default.",
                 LOCAL_PART_MAX_LENGTH
             ),
             Error::DomainEmpty => write!(f, "Domain is empty."),
-            Error::DomainTooLong => {
-                write!(f, "Domain is too long. Length limit: {}", DOMAIN_MAX_LENGTH)
-            }
+            Error::DomainTooLong => write!(
+                f,
+                "Domain is too long. Length limit: {} octets by default.",
+                DOMAIN_MAX_LENGTH
+            ),
             Error::SubDomainTooLong => write!(
                 f,
-                "A sub-domain is too long. Length limit: {}",
+                "A sub-domain is too long. Length limit: {} octets by default.",
                 SUB_DOMAIN_MAX_LENGTH
             ),
+            Error::AddressTooLong => write!(
+                f,
+                "Address is too long. Length limit: {} octets by default.",
+                ADDRESS_MAX_LENGTH
+            ),
             Error::MissingSeparator => write!(f, "Missing separator character '{}'.", AT),
             Error::DomainTooFew => write!(f, "Too few parts in the domain"),
+            Error::DomainTooMany => write!(f, "Too many parts in the domain."),
+            Error::TooManyTagSeparators => write!(
+                f,
+                "Too many consecutive sub-address separators in the local part."
+            ),
+            Error::PercentHackRouting => write!(
+                f,
+                "Percent hack routing, '{}', in the local part is not allowed.",
+                PERCENT_HACK
+            ),
+            Error::BangPathRouting => write!(
+                f,
+                "Bang path routing, '{}', in the local part is not allowed.",
+                BANG_PATH
+            ),
             Error::DomainInvalidSeparator => {
                 write!(f, "Invalid placement of the domain separator '{:?}", DOT)
             }
             Error::InvalidIPAddress => write!(f, "Invalid IP Address specified for domain."),
             Error::UnbalancedQuotes => write!(f, "Quotes around the local-part are unbalanced."),
             Error::InvalidComment => write!(f, "A comment was badly formed."),
-            Error::CantHappen => write!(f, "An impossible error was encountered."),
+            Error::UnsupportedQuotedLocalPart => write!(f, "Quoted local parts are not allowed."),
+            Error::UnsupportedDomainLiteral => write!(f, "Domain literals are not allowed."),
+            Error::InvalidUtf8(index) => write!(f, "Invalid UTF-8 at position {}.", index),
+            Error::MissingAngleBrackets => {
+                write!(f, "Missing angle brackets '{}' and '{}'.", LT, GT)
+            }
+            Error::InvalidXtext(index) => write!(f, "Invalid xtext at position {}.", index),
+            Error::InternalDomain => write!(f, "Internal domains are not allowed."),
+            Error::DeniedDomain => write!(f, "The domain is not allowed."),
+            Error::NonGlobalAddressLiteral => {
+                write!(
+                    f,
+                    "The address literal is not a globally routable IP address."
+                )
+            }
         }
     }
 }
@@ -361,592 +864,6953 @@ unsafe impl Sync for Error {}
 
 impl std::error::Error for Error {}
 
-impl<T> Into<std::result::Result<T, Error>> for Error {
-    fn into(self) -> Result<T, Error> {
-        Err(self)
-    }
-}
-
-// ------------------------------------------------------------------------------------------------
-
-impl Display for EmailAddress {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_string())
-    }
-}
-
-impl FromStr for EmailAddress {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        parse_address(s)
-    }
-}
-
-impl EmailAddress {
+impl Error {
     ///
-    /// Determine whether the `address` string is a valid email address. Note this is equivalent to
-    /// the following:
+    /// Returns the stable numeric code of the kind of this error, whatever its arguments. Codes
+    /// start at 1, so that 0 may mean no error, and are never reused or changed; a new kind of
+    /// error is given the next unused code. With the `miette` feature enabled, this method
+    /// is called in preference to `miette::Diagnostic::code`, which must be called by its path.
     ///
     /// ```rust
     /// use email_address::*;
     /// use std::str::FromStr;
     ///
-    /// let is_valid = EmailAddress::from_str("johnstonskj@gmail.com").is_ok();
+    /// assert_eq!(Error::MissingSeparator.code(), 5);
+    /// assert_eq!(EmailAddress::from_str("@example.com").unwrap_err().code(), 6);
     /// ```
     ///
-    pub fn is_valid(address: &str) -> bool {
-        Self::from_str(address).is_ok()
+    pub fn code(&self) -> u16 {
+        match self {
+            Error::LocalPartInvalidCharacter(_, _) => 1,
+            Error::QuotedStringInvalidCharacter(_, _) => 2,
+            Error::DomainInvalidCharacter(_, _) => 3,
+            Error::DomainLiteralInvalidCharacter(_, _) => 4,
+            Error::MissingSeparator => 5,
+            Error::LocalPartEmpty => 6,
+            Error::LocalPartTooLong => 7,
+            Error::DomainEmpty => 8,
+            Error::DomainTooLong => 9,
+            Error::SubDomainTooLong => 10,
+            Error::AddressTooLong => 11,
+            Error::DomainTooFew => 12,
+            Error::DomainInvalidSeparator => 13,
+            Error::UnbalancedQuotes => 14,
+            Error::InvalidComment => 15,
+            Error::InvalidIPAddress => 16,
+            Error::UnsupportedQuotedLocalPart => 17,
+            Error::UnsupportedDomainLiteral => 18,
+            Error::InvalidUtf8(_) => 19,
+            Error::MissingAngleBrackets => 20,
+            Error::InvalidXtext(_) => 21,
+            Error::InternalDomain => 22,
+            Error::DeniedDomain => 23,
+            Error::NonGlobalAddressLiteral => 24,
+            Error::DomainTooMany => 25,
+            Error::TooManyTagSeparators => 26,
+            Error::PercentHackRouting => 27,
+            Error::BangPathRouting => 28,
+        }
     }
 
     ///
-    /// Determine whether the `part` string would be a valid `local-part` if it were in an
-    /// email address.
+    /// Returns this error as an `ErrorCode`, with its code and any character and position.
     ///
-    pub fn is_valid_local_part(part: &str) -> bool {
-        parse_local_part(part).is_ok()
-    }
-
+    /// ```rust
+    /// use email_address::*;
     ///
-    /// Determine whether the `part` string would be a valid `domain` if it were in an
-    /// email address.
+    /// let code = Error::DomainInvalidCharacter('_', 9).error_code();
+    /// assert_eq!(code.code, 3);
+    /// assert_eq!(code.character, '_' as u32);
+    /// assert_eq!(code.position, 9);
     ///
-    pub fn is_valid_domain(part: &str) -> bool {
-        parse_domain(part).is_ok()
+    /// let code = Error::DomainEmpty.error_code();
+    /// assert_eq!((code.character, code.position), (0, ErrorCode::NO_POSITION));
+    /// ```
+    ///
+    pub fn error_code(&self) -> ErrorCode {
+        let (character, position) = match self {
+            Error::LocalPartInvalidCharacter(c, index)
+            | Error::QuotedStringInvalidCharacter(c, index)
+            | Error::DomainInvalidCharacter(c, index)
+            | Error::DomainLiteralInvalidCharacter(c, index) => (u32::from(*c), *index),
+            Error::InvalidUtf8(index) | Error::InvalidXtext(index) => (0, *index),
+            _ => (0, ErrorCode::NO_POSITION),
+        };
+        ErrorCode {
+            code: self.code(),
+            character,
+            position,
+        }
     }
 
     ///
-    /// Return this email address formatted as a URI. This will also URI-encode the email
-    /// address itself. So, `name@example.org` becomes `mailto:name%40example.org`.
+    /// Returns a stable key identifying the message of this error, whatever its arguments, for
+    /// looking up a translation. Requires the `i18n` feature.
     ///
-    pub fn to_uri(&self) -> String {
-        let encoded = encode(&self.to_string());
-        format!("{}{}", MAILTO_URI_PREFIX, encoded)
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// assert_eq!(Error::LocalPartEmpty.message_key(), "local-part-empty");
+    /// assert_eq!(
+    ///     Error::DomainInvalidCharacter(' ', 9).message_key(),
+    ///     "domain-invalid-character"
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "i18n")]
+    pub fn message_key(&self) -> &'static str {
+        match self {
+            Error::LocalPartInvalidCharacter(_, _) => "local-part-invalid-character",
+            Error::QuotedStringInvalidCharacter(_, _) => "quoted-string-invalid-character",
+            Error::DomainInvalidCharacter(_, _) => "domain-invalid-character",
+            Error::DomainLiteralInvalidCharacter(_, _) => "domain-literal-invalid-character",
+            Error::LocalPartEmpty => "local-part-empty",
+            Error::LocalPartTooLong => "local-part-too-long",
+            Error::DomainEmpty => "domain-empty",
+            Error::DomainTooLong => "domain-too-long",
+            Error::SubDomainTooLong => "sub-domain-too-long",
+            Error::AddressTooLong => "address-too-long",
+            Error::MissingSeparator => "missing-separator",
+            Error::DomainTooFew => "domain-too-few",
+            Error::DomainTooMany => "domain-too-many",
+            Error::TooManyTagSeparators => "too-many-tag-separators",
+            Error::PercentHackRouting => "percent-hack-routing",
+            Error::BangPathRouting => "bang-path-routing",
+            Error::DomainInvalidSeparator => "domain-invalid-separator",
+            Error::InvalidIPAddress => "invalid-ip-address",
+            Error::UnbalancedQuotes => "unbalanced-quotes",
+            Error::InvalidComment => "invalid-comment",
+            Error::UnsupportedQuotedLocalPart => "unsupported-quoted-local-part",
+            Error::UnsupportedDomainLiteral => "unsupported-domain-literal",
+            Error::InvalidUtf8(_) => "invalid-utf8",
+            Error::MissingAngleBrackets => "missing-angle-brackets",
+            Error::InvalidXtext(_) => "invalid-xtext",
+            Error::InternalDomain => "internal-domain",
+            Error::DeniedDomain => "denied-domain",
+            Error::NonGlobalAddressLiteral => "non-global-address-literal",
+        }
     }
 
     ///
-    /// Return a string formatted as a display email with the user name. This is commonly used
-    /// in email headers and other locations where a display name is associated with the
-    /// address.
+    /// Returns this error, for display in `language`. Requires the `i18n` feature.
     ///
-    /// So, `("name@example.org", "My Name")` becomes `"My Name <name@example.org>"`.
+    /// ```rust
+    /// use email_address::*;
     ///
-    pub fn to_display(&self, display_name: &str) -> String {
-        format!("{} <{}>", display_name, self)
+    /// let error = Error::LocalPartInvalidCharacter(' ', 3);
+    /// assert_eq!(
+    ///     error.localized(Language::German).to_string(),
+    ///     "Ungültiges Zeichen ' ' im lokalen Teil an Position 3."
+    /// );
+    /// assert_eq!(
+    ///     error.localized(Language::English).to_string(),
+    ///     error.to_string()
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "i18n")]
+    pub fn localized(&self, language: Language) -> Localized<'_> {
+        i18n::localized(self, language)
     }
 
-    /// Returns a String for the email address
-    pub fn to_string(&self) -> String {
-        [&self.local, "@", &self.domain].concat().to_string()
+    ///
+    /// Returns this error with any position in it moved on by `by`, for errors found in a part
+    /// of a larger string, starting at `by`.
+    ///
+    pub(crate) fn offset(self, by: usize) -> Self {
+        match self {
+            Error::LocalPartInvalidCharacter(c, index) => {
+                Error::LocalPartInvalidCharacter(c, index + by)
+            }
+            Error::QuotedStringInvalidCharacter(c, index) => {
+                Error::QuotedStringInvalidCharacter(c, index + by)
+            }
+            Error::DomainInvalidCharacter(c, index) => Error::DomainInvalidCharacter(c, index + by),
+            Error::DomainLiteralInvalidCharacter(c, index) => {
+                Error::DomainLiteralInvalidCharacter(c, index + by)
+            }
+            Error::InvalidUtf8(index) => Error::InvalidUtf8(index + by),
+            Error::InvalidXtext(index) => Error::InvalidXtext(index + by),
+            error => error,
+        }
     }
+}
 
-    /// Returns the local part of the EmailAddress
-    pub fn local_part(self) -> String {
-        self.local
-    }
-    /// Returns the domain part of the EmailAddress
-    pub fn domain(self) -> String {
-        self.domain
+impl From<&Error> for ErrorCode {
+    fn from(error: &Error) -> Self {
+        error.error_code()
     }
 }
 
-// ------------------------------------------------------------------------------------------------
-// Private Functions
+impl ErrorCode {
+    /// The `position` of an error that reports no position.
+    pub const NO_POSITION: usize = usize::MAX;
+}
+
 // ------------------------------------------------------------------------------------------------
 
-fn encode(address: &str) -> String {
-    let mut result = String::new();
-    for c in address.chars() {
-        if is_uri_reserved(c) {
-            result.push_str(&format!("%{:02X}", c as u8))
-        } else {
-            result.push(c);
-        }
+impl<T> From<Error> for std::result::Result<T, Error> {
+    fn from(error: Error) -> Self {
+        Err(error)
     }
-    result
 }
 
-fn is_uri_reserved(c: char) -> bool {
-    c == '!'
-        || c == '#'
-        || c == '$'
-        || c == '%'
-        || c == '&'
-        || c == '\''
-        || c == '('
-        || c == ')'
-        || c == '*'
-        || c == '+'
-        || c == ','
-        || c == '/'
-        || c == ':'
-        || c == ';'
-        || c == '='
-        || c == '?'
-        || c == '@'
-        || c == '['
-        || c == ']'
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} (at {}..{})",
+            self.error, self.span.start, self.span.end
+        )
+    }
 }
 
-fn parse_address(address: &str) -> Result<EmailAddress, Error> {
-    let address = if address.starts_with(LT) && address.ends_with(GT) {
-        &address[1..address.len() - 1]
-    } else {
-        address
-    };
-    //
-    // Deals with cases of '@' in `local-part`, if it is quoted they are legal, if
-    // not then they'll return an `InvalidCharacter` error later.
-    //
-    let parts: Vec<&str> = address.rsplitn(2, AT).collect::<Vec<&str>>();
-    if parts.len() != 2 {
-        return Err(Error::MissingSeparator.into());
-    }
-    let local = parts.last().ok_or(Error::CantHappen)?.deref();
-    let domain = parts.first().ok_or(Error::CantHappen)?.deref();
-    parse_local_part(local)?;
-    parse_domain(domain)?;
-
-    Ok(EmailAddress {
-        local: local.into(),
-        domain: domain.into(),
-    })
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
 }
 
-fn parse_local_part(part: &str) -> Result<(), Error> {
-    if part.is_empty() {
-        return Err(Error::LocalPartEmpty);
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        error.error
     }
-    if part.len() > LOCAL_PART_MAX_LENGTH {
-        return Err(Error::LocalPartTooLong);
+}
+
+impl ParseError {
+    pub(crate) fn new(error: Error, span: Range<usize>) -> Self {
+        Self { error, span }
     }
-    if part.starts_with(DQUOTE) && part.ends_with(DQUOTE) {
-        if part.len() == 2 {
-            return Err(Error::LocalPartEmpty);
-        } else {
-            parse_quoted_local_part(&part[1..part.len() - 1])?
-        }
-    } else {
-        parse_unquoted_local_part(part)?
+
+    /// Returns the error found.
+    pub fn error(&self) -> &Error {
+        &self.error
     }
-    Ok(())
-}
 
-fn parse_quoted_local_part(part: &str) -> Result<(), Error> {
-    if is_qcontent(part) {
-        return Ok(());
-    } else {
+    ///
+    /// Returns the byte offsets, within the parsed string, of the part of the address that
+    /// caused the error. The span is empty where something is missing, such as the `local-part`
+    /// of `"@example.com"`.
+    ///
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
     }
-    Error::InvalidCharacter.into()
-}
 
-fn parse_unquoted_local_part(part: &str) -> Result<(), Error> {
-    if is_dot_atom_text(part) {
-        return Ok(());
+    ///
+    /// Returns any fixes that would correct this error; the fixed address may still contain
+    /// other errors.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let address = "simon@example.com.";
+    /// let error = EmailAddress::parse_spanned(address, &Options::default()).unwrap_err();
+    /// let suggestions = error.suggestions();
+    ///
+    /// assert_eq!(suggestions[0].message(), "remove the trailing or repeated '.'");
+    /// assert_eq!(suggestions[0].apply(address), "simon@example.com");
+    /// ```
+    ///
+    pub fn suggestions(&self) -> Vec<Suggestion> {
+        let span = self.span();
+        let suggestion = match self.error {
+            Error::LocalPartInvalidCharacter(c, _) | Error::DomainInvalidCharacter(c, _) => match c
+            {
+                DOT => Suggestion::new("remove the trailing or repeated '.'", span, ""),
+                ',' => Suggestion::new("replace ',' with '.'", span, "."),
+                AT => Suggestion::new("did you mean a single '@'?", span, ""),
+                LT | GT => Suggestion::new("remove the stray angle bracket", span, ""),
+                SP | HTAB => Suggestion::new("remove the white space", span, ""),
+                _ => return Vec::new(),
+            },
+            Error::UnbalancedQuotes => {
+                Suggestion::new("close the quoted string", span.end..span.end, "\"")
+            }
+            Error::InvalidComment => Suggestion::new("close the comment", span.end..span.end, ")"),
+            _ => return Vec::new(),
+        };
+        vec![suggestion]
     }
-    Error::InvalidCharacter.into()
 }
 
-fn parse_domain(part: &str) -> Result<(), Error> {
-    if part.is_empty() {
-        Error::DomainEmpty.into()
-    } else if part.len() > DOMAIN_MAX_LENGTH {
-        Error::DomainTooLong.into()
-    } else if part.starts_with(LBRACKET) && part.ends_with(RBRACKET) {
-        parse_literal_domain(&part[1..part.len() - 1])
-    } else {
-        parse_text_domain(part)
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
     }
 }
 
-fn parse_text_domain(part: &str) -> Result<(), Error> {
-    if is_dot_atom_text(part) {
-        for sub_part in part.split(DOT) {
-            if sub_part.len() > SUB_DOMAIN_MAX_LENGTH {
-                return Error::SubDomainTooLong.into();
-            }
+impl Suggestion {
+    fn new(message: &'static str, span: Range<usize>, replacement: &'static str) -> Self {
+        Self {
+            message,
+            span,
+            replacement,
         }
-        return Ok(());
     }
-    Error::InvalidCharacter.into()
-}
 
-fn parse_literal_domain(part: &str) -> Result<(), Error> {
-    if part.chars().all(is_dtext_char) {
-        return Ok(());
+    /// Returns a description of the fix, suitable for display to a user.
+    pub fn message(&self) -> &str {
+        self.message
     }
-    Error::InvalidCharacter.into()
-}
 
-// ------------------------------------------------------------------------------------------------
+    /// Returns the byte offsets, within the parsed string, of the text to replace.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
 
-fn is_atext(c: char) -> bool {
-    c.is_alphanumeric()
-        || c == '!'
-        || c == '#'
-        || c == '$'
-        || c == '%'
-        || c == '&'
-        || c == '\''
-        || c == '*'
-        || c == '+'
-        || c == '-'
-        || c == '/'
-        || c == '='
-        || c == '?'
-        || c == '^'
-        || c == '_'
-        || c == '`'
-        || c == '{'
-        || c == '|'
-        || c == '}'
-        || c == '~'
-        || is_uchar(c)
-}
-
-#[allow(dead_code)]
-fn is_special(c: char) -> bool {
-    c == '('
-        || c == ')'
-        || c == '<'
-        || c == '>'
-        || c == '['
-        || c == ']'
-        || c == ':'
-        || c == ';'
-        || c == '@'
-        || c == '\\'
-        || c == ','
-        || c == '.'
-        || c == DQUOTE
+    /// Returns the text to replace the span with; empty if the span is to be removed.
+    pub fn replacement(&self) -> &str {
+        self.replacement
+    }
+
+    ///
+    /// Apply this fix to `address`, which must be the string that was parsed.
+    ///
+    pub fn apply(&self, address: &str) -> String {
+        [
+            &address[..self.span.start],
+            self.replacement,
+            &address[self.span.end..],
+        ]
+        .concat()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Warning::QuotedLocalPart => write!(f, "Local part is a quoted string."),
+            Warning::DomainLiteral => write!(f, "Domain is an address literal."),
+            Warning::DotlessDomain => write!(f, "Domain has no top-level domain."),
+            Warning::NumericTld => write!(f, "Top-level domain is numeric."),
+            Warning::NonLdhDomain => write!(f, "Domain is not a valid host name."),
+            Warning::Comment => write!(f, "Address contains a comment."),
+            Warning::FoldingWhiteSpace => write!(f, "Address contains folding white space."),
+            Warning::ObsoleteSyntax => write!(f, "Address uses obsolete syntax."),
+            Warning::LocalPartTooLong => write!(f, "Local part is too long for SMTP."),
+            Warning::DomainTooLong => write!(f, "Domain is too long for SMTP."),
+            Warning::SubDomainTooLong => write!(f, "A sub-domain is too long for SMTP."),
+            Warning::AddressTooLong => write!(f, "Address is too long for SMTP."),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for EmailAddress {
+    ///
+    /// Writes the address as stored, honoring any width, fill, and alignment, such as `{:>40}`;
+    /// this does not allocate.
+    ///
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.address)
+    }
 }
 
-fn is_uchar(c: char) -> bool {
-    c >= UTF8_START
+impl Debug for EmailAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EmailAddress")
+            .field("local", &self.local_part())
+            .field("domain", &self.domain())
+            .finish()
+    }
 }
 
-fn is_atom(s: &str) -> bool {
-    !s.is_empty() && s.chars().all(is_atext)
+impl AsRef<str> for EmailAddress {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
 }
 
-fn is_dot_atom_text(s: &str) -> bool {
-    s.split(DOT).all(is_atom)
+impl PartialEq for EmailAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.address == other.address
+    }
 }
 
-fn is_vchar(c: char) -> bool {
-    c >= '\x21' && c <= '\x7E'
+impl Eq for EmailAddress {}
+
+impl Hash for EmailAddress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.address.hash(state);
+    }
 }
 
-fn is_wsp(c: char) -> bool {
-    c == SP || c == HTAB
+impl From<EmailAddress> for String {
+    // Only a conversion when the `compact_str` feature is enabled.
+    #[allow(clippy::useless_conversion)]
+    fn from(email: EmailAddress) -> Self {
+        email.address.into()
+    }
 }
 
-fn is_qtext_char(c: char) -> bool {
-    c == '\x21' || (c >= '\x23' && c <= '\x5B') || (c >= '\x5D' && c <= '\x7E') || is_uchar(c)
+//
+// The serialized form is a structure of the local part and domain, as it was when these were
+// stored separately.
+//
+#[cfg(feature = "serde_support")]
+#[derive(Deserialize, Serialize)]
+#[serde(rename = "EmailAddress")]
+struct SerializedEmailAddress<'a> {
+    #[serde(borrow)]
+    local: Cow<'a, str>,
+    #[serde(borrow)]
+    domain: Cow<'a, str>,
 }
 
-fn is_qcontent(s: &str) -> bool {
-    let mut char_iter = s.chars();
-    while let Some(c) = &char_iter.next() {
-        if c == &ESC {
-            // quoted-pair
-            match char_iter.next() {
-                Some(c2) if is_vchar(c2) => (),
-                _ => return false,
-            }
-        } else if !(is_wsp(*c) || is_qtext_char(*c)) {
-            // qtext
-            return false;
+#[cfg(feature = "serde_support")]
+impl Serialize for EmailAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedEmailAddress {
+            local: Cow::Borrowed(self.local_part()),
+            domain: Cow::Borrowed(self.domain()),
         }
+        .serialize(serializer)
     }
-    true
 }
 
-fn is_dtext_char(c: char) -> bool {
-    (c >= '\x21' && c <= '\x5A') || (c >= '\x5E' && c <= '\x7E')
+#[cfg(feature = "serde_support")]
+impl<'de> Deserialize<'de> for EmailAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let email = SerializedEmailAddress::deserialize(deserializer)?;
+        Ok(EmailAddress::from_parts(&email.local, &email.domain))
+    }
 }
 
-#[allow(dead_code)]
-fn is_ctext_char(c: char) -> bool {
-    (c >= '\x21' && c == '\x27') || (c >= '\x2A' && c <= '\x5B') || (c >= '\x5D' && c <= '\x7E')
+impl FromStr for EmailAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_address(s, &Options::default(), &mut Collector::default()).map_err(Error::from)
+    }
 }
 
-#[allow(dead_code)]
-fn is_ctext(s: &str) -> bool {
-    s.chars().all(is_ctext_char)
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            check_syntax: true,
+            allow_utf8: true,
+            allow_quoted_local_part: true,
+            allow_domain_literal: true,
+            allow_cfws: false,
+            allow_obsolete: false,
+            trim: false,
+            mailto_prefix: false,
+            lowercase_domain: false,
+            angle_brackets: AngleBrackets::Allowed,
+            ldh_domain: false,
+            strict_domain_literal: false,
+            minimum_sub_domains: 0,
+            max_sub_domains: None,
+            max_consecutive_tag_separators: None,
+            tag_separators: Cow::Borrowed(DEFAULT_TAG_SEPARATORS),
+            allow_local_routing: true,
+            allow_internal_domain: true,
+            denied_domains: None,
+            allow_non_global_literal: true,
+            max_local_part_length: Some(LOCAL_PART_MAX_LENGTH),
+            max_domain_length: Some(DOMAIN_MAX_LENGTH),
+            max_sub_domain_length: Some(SUB_DOMAIN_MAX_LENGTH),
+            max_address_length: Some(ADDRESS_MAX_LENGTH),
+            #[cfg(feature = "idna")]
+            a_label_lengths: false,
+        }
+    }
 }
 
-// ------------------------------------------------------------------------------------------------
-// Unit Tests
-// ------------------------------------------------------------------------------------------------
+impl From<Profile> for Options {
+    fn from(profile: Profile) -> Self {
+        Self::default().with_profile(profile)
+    }
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl From<Preset> for Options {
+    fn from(preset: Preset) -> Self {
+        match preset {
+            Preset::Minimal => Self::default()
+                .without_syntax_checks()
+                .without_length_limits(),
+            Preset::Rfc => Self::default(),
+            Preset::Strict => Self::default()
+                .with_required_tld()
+                .with_ldh_domain()
+                .without_domain_literal()
+                .without_angle_brackets(),
+        }
+    }
+}
 
-    fn is_valid(address: &str, test_case: Option<&str>) {
-        if let Some(test_case) = test_case {
-            println!(">> test case: {}", test_case);
-            println!("     <{}>", address);
-        } else {
-            println!(">> <{}>", address);
+impl Options {
+    ///
+    /// Check the syntax of the `local-part` and `domain` against the grammar selected by the other
+    /// options. This is the default.
+    ///
+    pub fn with_syntax_checks(mut self) -> Self {
+        self.check_syntax = true;
+        self
+    }
+
+    ///
+    /// Only check that the address has a separator ('@') with a non-empty `local-part` and
+    /// `domain`, any length limits are still checked.
+    ///
+    pub fn without_syntax_checks(mut self) -> Self {
+        self.check_syntax = false;
+        self
+    }
+
+    ///
+    /// Set all of the grammar and length options to those required by `profile`. Options set
+    /// before this call are overwritten, so any adjustments to the profile must be made after it.
+    ///
+    pub fn with_profile(self, profile: Profile) -> Self {
+        match profile {
+            Profile::Rfc5321 | Profile::Rfc6531 => {
+                let options = Self {
+                    allow_cfws: false,
+                    allow_obsolete: false,
+                    ldh_domain: true,
+                    strict_domain_literal: true,
+                    max_local_part_length: Some(LOCAL_PART_MAX_LENGTH),
+                    max_domain_length: Some(DOMAIN_MAX_LENGTH),
+                    max_sub_domain_length: Some(SUB_DOMAIN_MAX_LENGTH),
+                    max_address_length: Some(ADDRESS_MAX_LENGTH),
+                    ..self
+                };
+                if profile == Profile::Rfc6531 {
+                    options.with_utf8()
+                } else {
+                    options.without_utf8()
+                }
+            }
+            Profile::Rfc5322 => Self {
+                allow_utf8: false,
+                allow_cfws: true,
+                allow_obsolete: true,
+                ldh_domain: false,
+                strict_domain_literal: false,
+                ..self
+            }
+            .without_length_limits(),
+            Profile::X509 => self
+                .with_profile(Profile::Rfc5321)
+                .without_quoted_local_part()
+                .without_domain_literal(),
         }
-        assert!(EmailAddress::is_valid(address));
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_01() {
-        is_valid("simple@example.com", None);
+    ///
+    /// Allow non-ASCII characters in the address, as described in RFC 6531 and RFC 6532. This
+    /// is the default.
+    ///
+    pub fn with_utf8(mut self) -> Self {
+        self.allow_utf8 = true;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_02() {
-        is_valid("very.common@example.com", None);
+    ///
+    /// Only allow ASCII characters in the address, as described in RFC 5321 and RFC 5322.
+    ///
+    pub fn without_utf8(mut self) -> Self {
+        self.allow_utf8 = false;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_03() {
-        is_valid("disposable.style.email.with+symbol@example.com", None);
+    ///
+    /// Allow a `local-part` that is a quoted string, such as `"john..doe"`. This is the default.
+    ///
+    pub fn with_quoted_local_part(mut self) -> Self {
+        self.allow_quoted_local_part = true;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_04() {
-        is_valid("other.email-with-hyphen@example.com", None);
+    ///
+    /// Do not allow a `local-part` that is a quoted string, these will return the error
+    /// `Error::UnsupportedQuotedLocalPart`. This applies to a quoted word in an obsolete
+    /// `local-part`, and to one that is percent-encoded after a `mailto:` prefix, as well. Few
+    /// providers accept quoted local parts, and their spaces and escapes are easily mishandled
+    /// by code that later splits or interpolates the address.
+    ///
+    pub fn without_quoted_local_part(mut self) -> Self {
+        self.allow_quoted_local_part = false;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_05() {
-        is_valid("fully-qualified-domain@example.com", None);
+    ///
+    /// Allow a `domain` that is a `domain-literal`, such as `[192.168.2.1]`. This is the default.
+    ///
+    pub fn with_domain_literal(mut self) -> Self {
+        self.allow_domain_literal = true;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_06() {
-        is_valid(
-            "user.name+tag+sorting@example.com",
-            Some(" may go to user.name@example.com inbox depending on mail server"),
-        );
+    ///
+    /// Do not allow a `domain` that is a `domain-literal`, these will return the error
+    /// `Error::UnsupportedDomainLiteral`.
+    ///
+    pub fn without_domain_literal(mut self) -> Self {
+        self.allow_domain_literal = false;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_07() {
-        is_valid("x@example.com", Some("one-letter local-part"));
+    ///
+    /// Allow comments and folding white space (`CFWS`) before and after the `local-part` and
+    /// `domain`, as described in RFC 5322. These are removed from the parsed address, so that
+    /// `(comment)simon@example.com` has the local part `simon`.
+    ///
+    pub fn with_cfws(mut self) -> Self {
+        self.allow_cfws = true;
+        self
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_08() {
-        is_valid("example-indeed@strange-example.com", None);
+    ///
+    /// Do not allow comments or folding white space. This is the default.
+    ///
+    pub fn without_cfws(mut self) -> Self {
+        self.allow_cfws = false;
+        self
+    }
+
+    ///
+    /// Allow the obsolete syntax described in RFC 5322, §4.4; a `local-part` made of a mix of
+    /// atoms and quoted strings, `CFWS` around the '.' separators, and control characters in
+    /// quoted strings, comments, and domain literals.
+    ///
+    pub fn with_obsolete(mut self) -> Self {
+        self.allow_obsolete = true;
+        self
+    }
+
+    ///
+    /// Do not allow the obsolete syntax. This is the default.
+    ///
+    pub fn without_obsolete(mut self) -> Self {
+        self.allow_obsolete = false;
+        self
+    }
+
+    ///
+    /// Ignore any white space, including line breaks, before and after the address, as is
+    /// common in user input, rather than returning an invalid character error. The white space
+    /// is not part of the parsed address, and the positions in any error remain those of the
+    /// characters of the untrimmed input.
+    ///
+    pub fn with_trimming(mut self) -> Self {
+        self.trim = true;
+        self
+    }
+
+    ///
+    /// Treat any white space before or after the address as part of it. This is the default.
+    ///
+    pub fn without_trimming(mut self) -> Self {
+        self.trim = false;
+        self
+    }
+
+    ///
+    /// Fold a domain name to lowercase in the stored form of the address, as domain names are
+    /// compared without regard to case; the `local-part`, which may be case-sensitive, and any
+    /// domain literal are kept as they were parsed. The string that was parsed is available
+    /// from `EmailAddress::original`.
+    ///
+    pub fn with_lowercase_domain(mut self) -> Self {
+        self.lowercase_domain = true;
+        self
+    }
+
+    ///
+    /// Store the domain with the case it was parsed with. This is the default.
+    ///
+    pub fn without_lowercase_domain(mut self) -> Self {
+        self.lowercase_domain = false;
+        self
+    }
+
+    ///
+    /// Accept a `mailto:` URI, RFC 6068, as pasted from a link, parsing the address it holds;
+    /// the prefix, in any case, is removed, as is any query such as `?subject=Hello`, and any
+    /// percent-encoded characters are decoded. If the address was percent-encoded, the positions
+    /// in any error are those of the characters of the decoded address.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_mailto_prefix();
+    ///
+    /// let email = EmailAddress::parse_with("mailto:simon%2Bnews@example.com", &options).unwrap();
+    /// assert_eq!(email.as_str(), "simon+news@example.com");
+    /// assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+    /// ```
+    ///
+    pub fn with_mailto_prefix(mut self) -> Self {
+        self.mailto_prefix = true;
+        self
+    }
+
+    ///
+    /// Treat a `mailto:` prefix as part of the address, where the ':' is an invalid character.
+    /// This is the default.
+    ///
+    pub fn without_mailto_prefix(mut self) -> Self {
+        self.mailto_prefix = false;
+        self
+    }
+
+    ///
+    /// Allow, but do not require, angle brackets around the address, as in
+    /// `<simon@example.com>`; they are not part of the parsed address. This is the default.
+    ///
+    pub fn with_angle_brackets(mut self) -> Self {
+        self.angle_brackets = AngleBrackets::Allowed;
+        self
+    }
+
+    ///
+    /// Do not allow angle brackets around the address; the '<' is reported as an invalid
+    /// character.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().without_angle_brackets();
+    ///
+    /// assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("<simon@example.com>", &options),
+    ///     Error::LocalPartInvalidCharacter('<', 0).into()
+    /// );
+    /// ```
+    ///
+    pub fn without_angle_brackets(mut self) -> Self {
+        self.angle_brackets = AngleBrackets::Forbidden;
+        self
+    }
+
+    ///
+    /// Require angle brackets around the address, as in the path arguments of the SMTP `MAIL`
+    /// and `RCPT` commands; an address without them returns the error
+    /// `Error::MissingAngleBrackets`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_required_angle_brackets();
+    ///
+    /// assert!(EmailAddress::parse_with("<simon@example.com>", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon@example.com", &options),
+    ///     Error::MissingAngleBrackets.into()
+    /// );
+    /// ```
+    ///
+    pub fn with_required_angle_brackets(mut self) -> Self {
+        self.angle_brackets = AngleBrackets::Required;
+        self
+    }
+
+    ///
+    /// These options, for an address whose angle brackets, if any, have already been removed.
+    ///
+    pub(crate) fn for_unbracketed(&self) -> Cow<'_, Self> {
+        match self.angle_brackets {
+            AngleBrackets::Required => Cow::Owned(self.clone().with_angle_brackets()),
+            _ => Cow::Borrowed(self),
+        }
+    }
+
+    ///
+    /// Require each `sub-domain` in a text `domain` to follow the letter-digit-hyphen rule of
+    /// RFC 1123 and RFC 5321, §4.1.2, instead of allowing any `atext` character.
+    ///
+    pub fn with_ldh_domain(mut self) -> Self {
+        self.ldh_domain = true;
+        self
+    }
+
+    ///
+    /// Allow any `atext` character in a `sub-domain`, as RFC 5322 does. This is the default.
+    ///
+    pub fn without_ldh_domain(mut self) -> Self {
+        self.ldh_domain = false;
+        self
+    }
+
+    ///
+    /// Require the content of a `domain-literal` to be an RFC 5321 `address-literal`; an IPv4
+    /// address, an IPv6 address tagged `IPv6:`, or a general tagged address. Invalid content
+    /// returns the error `Error::InvalidIPAddress`.
+    ///
+    pub fn with_strict_domain_literal(mut self) -> Self {
+        self.strict_domain_literal = true;
+        self
+    }
+
+    ///
+    /// Allow any `dtext` content in a `domain-literal`. This is the default.
+    ///
+    pub fn without_strict_domain_literal(mut self) -> Self {
+        self.strict_domain_literal = false;
+        self
+    }
+
+    ///
+    /// Require at least `minimum` `sub-domain`s in a text `domain`, domains with fewer return
+    /// the error `Error::DomainTooFew`. The default is zero, so that `admin@mailserver1` is valid.
+    ///
+    pub fn with_minimum_sub_domains(mut self, minimum: usize) -> Self {
+        self.minimum_sub_domains = minimum;
+        self
+    }
+
+    ///
+    /// Require a text `domain` to have at least two `sub-domain`s, i.e. a top-level domain, this
+    /// is the same as `with_minimum_sub_domains(2)`.
+    ///
+    pub fn with_required_tld(self) -> Self {
+        self.with_minimum_sub_domains(2)
+    }
+
+    ///
+    /// Allow at most `max` `sub-domain`s in a text `domain`, domains with more return the error
+    /// `Error::DomainTooMany`. The default is no limit, other than that of the domain's length.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_max_sub_domains(4);
+    ///
+    /// assert!(EmailAddress::parse_with("simon@mail.example.co.uk", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon@a.b.c.d.example.com", &options),
+    ///     Error::DomainTooMany.into()
+    /// );
+    /// ```
+    ///
+    pub fn with_max_sub_domains(mut self, max: usize) -> Self {
+        self.max_sub_domains = Some(max);
+        self
+    }
+
+    ///
+    /// Allow at most `max` consecutive sub-address separators, as set by `with_tag_separators`,
+    /// in the `local-part`, longer runs return the error `Error::TooManyTagSeparators`. The
+    /// default is no limit.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_max_consecutive_tag_separators(1);
+    ///
+    /// assert!(EmailAddress::parse_with("simon+news+daily@example.com", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon+++news@example.com", &options),
+    ///     Error::TooManyTagSeparators.into()
+    /// );
+    /// ```
+    ///
+    pub fn with_max_consecutive_tag_separators(mut self, max: usize) -> Self {
+        self.max_consecutive_tag_separators = Some(max);
+        self
+    }
+
+    ///
+    /// Use `separators` as the sub-address separators limited by
+    /// `with_max_consecutive_tag_separators`; such as '-' for Qmail, or '=' for some other
+    /// systems. The default is '+'.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default()
+    ///     .with_tag_separators(&['+', '-'])
+    ///     .with_max_consecutive_tag_separators(1);
+    ///
+    /// assert!(EmailAddress::parse_with("simon-news@example.com", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon-+news@example.com", &options),
+    ///     Error::TooManyTagSeparators.into()
+    /// );
+    /// ```
+    ///
+    pub fn with_tag_separators(mut self, separators: &[char]) -> Self {
+        self.tag_separators = Cow::Owned(separators.to_vec());
+        self
+    }
+
+    ///
+    /// Allow a `local-part` containing the routing characters '%' and '!', which are valid but
+    /// are interpreted by some relays as a route to another host. This is the default.
+    ///
+    pub fn with_local_routing(mut self) -> Self {
+        self.allow_local_routing = true;
+        self
+    }
+
+    ///
+    /// Do not allow a `local-part` containing the routing characters that some relays act on:
+    /// the "percent hack", as in `user%victim.example@relay.example`, returns the error
+    /// `Error::PercentHackRouting`, and a bang path, as in `host!user@relay.example`, returns
+    /// the error `Error::BangPathRouting`. Either may be used to relay mail through a host that
+    /// would not otherwise accept it.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().without_local_routing();
+    ///
+    /// assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon%victim.example@relay.example", &options),
+    ///     Error::PercentHackRouting.into()
+    /// );
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("host!simon@relay.example", &options),
+    ///     Error::BangPathRouting.into()
+    /// );
+    /// ```
+    ///
+    pub fn without_local_routing(mut self) -> Self {
+        self.allow_local_routing = false;
+        self
+    }
+
+    ///
+    /// Allow domains that can only be reached within a private network: `localhost`, single
+    /// labels such as `mailserver1`, and names ending in `.local` or `.internal`. This is the
+    /// default, suitable for intranet deployments.
+    ///
+    pub fn with_internal_domains(mut self) -> Self {
+        self.allow_internal_domain = true;
+        self
+    }
+
+    ///
+    /// Do not allow domains that can only be reached within a private network, these return the
+    /// error `Error::InternalDomain`; intended for public-facing forms.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().without_internal_domains();
+    ///
+    /// assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+    /// for address in ["simon@localhost", "simon@mailserver1", "simon@printer.local"] {
+    ///     assert_eq!(
+    ///         EmailAddress::parse_with(address, &options),
+    ///         Error::InternalDomain.into()
+    ///     );
+    /// }
+    /// ```
+    ///
+    pub fn without_internal_domains(mut self) -> Self {
+        self.allow_internal_domain = false;
+        self
+    }
+
+    ///
+    /// Do not allow the domains of the IANA Special-Use Domain Names registry, RFC 6761, or any
+    /// of their sub-domains, these return the error `Error::DeniedDomain`. This replaces any list
+    /// set by `with_denied_domains`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().without_special_use_domains();
+    ///
+    /// assert!(EmailAddress::parse_with("simon@example.co.uk", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon@hidden.onion", &options),
+    ///     Error::DeniedDomain.into()
+    /// );
+    /// ```
+    ///
+    pub fn without_special_use_domains(mut self) -> Self {
+        self.denied_domains = Some(domains::shared_special_use().clone());
+        self
+    }
+
+    ///
+    /// Do not allow the domains of `domains`, or any of their sub-domains, these return the
+    /// error `Error::DeniedDomain`. This replaces any list set by `without_special_use_domains`;
+    /// to deny both, add the domains of `DomainList::special_use` to `domains`.
+    ///
+    pub fn with_denied_domains(mut self, domains: DomainList) -> Self {
+        self.denied_domains = Some(Arc::new(domains));
+        self
+    }
+
+    ///
+    /// Allow address literals of any IP address. This is the default.
+    ///
+    pub fn with_non_global_address_literals(mut self) -> Self {
+        self.allow_non_global_literal = true;
+        self
+    }
+
+    ///
+    /// Do not allow address literals whose IP address is not globally routable, such as private,
+    /// loopback, link-local, or documentation addresses, these return the error
+    /// `Error::NonGlobalAddressLiteral`; so that an address cannot be used to direct mail to a
+    /// host on an internal network. General address literals are still allowed.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().without_non_global_address_literals();
+    ///
+    /// assert!(EmailAddress::parse_with("simon@[8.8.8.8]", &options).is_ok());
+    /// assert_eq!(
+    ///     EmailAddress::parse_with("simon@[127.0.0.1]", &options),
+    ///     Error::NonGlobalAddressLiteral.into()
+    /// );
+    /// ```
+    ///
+    pub fn without_non_global_address_literals(mut self) -> Self {
+        self.allow_non_global_literal = false;
+        self
+    }
+
+    ///
+    /// Allow any domain, removing the list set by `without_special_use_domains` or
+    /// `with_denied_domains`. This is the default.
+    ///
+    pub fn without_denied_domains(mut self) -> Self {
+        self.denied_domains = None;
+        self
+    }
+
+    ///
+    /// Set the maximum length, in octets, of the `local-part`. The default is 64.
+    ///
+    pub fn with_max_local_part_length(mut self, max_length: usize) -> Self {
+        self.max_local_part_length = Some(max_length);
+        self
+    }
+
+    ///
+    /// Set the maximum length, in octets, of the `domain`. The default is 254.
+    ///
+    pub fn with_max_domain_length(mut self, max_length: usize) -> Self {
+        self.max_domain_length = Some(max_length);
+        self
+    }
+
+    ///
+    /// Set the maximum length, in octets, of each `sub-domain` in the `domain`. The default
+    /// is 63.
+    ///
+    pub fn with_max_sub_domain_length(mut self, max_length: usize) -> Self {
+        self.max_sub_domain_length = Some(max_length);
+        self
+    }
+
+    ///
+    /// Do not check the length of the address, or of any of its components.
+    ///
+    pub fn without_length_limits(mut self) -> Self {
+        self.max_local_part_length = None;
+        self.max_domain_length = None;
+        self.max_sub_domain_length = None;
+        self.max_address_length = None;
+        self
+    }
+
+    ///
+    /// Set the maximum length, in octets, of the complete address; `local-part`, separator, and
+    /// `domain`. The default is 254, the longest address that fits in an RFC 5321 `Path`.
+    ///
+    pub fn with_max_address_length(mut self, max_length: usize) -> Self {
+        self.max_address_length = Some(max_length);
+        self
+    }
+
+    ///
+    /// Do not check the length of the complete address, only the lengths of its components.
+    ///
+    pub fn without_max_address_length(mut self) -> Self {
+        self.max_address_length = None;
+        self
+    }
+
+    ///
+    /// Measure the length of the `domain`, and of each `sub-domain`, after conversion to its
+    /// A-label (punycode) form rather than as UTF-8 octets. This is the form in which the domain
+    /// is transmitted to a server that does not support the `SMTPUTF8` extension, and so it is
+    /// the form the 254 and 63 octet limits apply to in DNS.
+    ///
+    #[cfg(feature = "idna")]
+    pub fn with_a_label_lengths(mut self) -> Self {
+        self.a_label_lengths = true;
+        self
+    }
+}
+
+impl EmailAddress {
+    ///
+    /// Determine whether the `address` string is a valid email address. Note this is equivalent to
+    /// the following, but does not allocate unless the address contains comments or folding
+    /// white space:
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let is_valid = EmailAddress::from_str("johnstonskj@gmail.com").is_ok();
+    /// ```
+    ///
+    pub fn is_valid(address: &str) -> bool {
+        check_address(address, &Options::default(), &mut Collector::default()).is_ok()
+    }
+
+    ///
+    /// Determine whether the `address` string is valid in the common ASCII subset of addresses;
+    /// a `dot-atom` local part, and a domain of letter-digit-hyphen labels. Any address this
+    /// accepts is also accepted by `is_valid`, but not the reverse. As a `const fn` this can check
+    /// addresses in constants and static tables when they are compiled.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// const SUPPORT: &str = "support@example.com";
+    /// const _: () = assert!(EmailAddress::is_valid_ascii(SUPPORT));
+    ///
+    /// assert!(!EmailAddress::is_valid_ascii("\"support\"@example.com"));
+    /// assert!(EmailAddress::is_valid("\"support\"@example.com"));
+    /// ```
+    ///
+    pub const fn is_valid_ascii(address: &str) -> bool {
+        parser::is_ascii_address(address.as_bytes())
+    }
+
+    ///
+    /// Parse the `address` string using the rules selected by `options`. Calling this with
+    /// `Options::default()` is equivalent to calling `FromStr::from_str`.
+    ///
+    pub fn parse_with(address: &str, options: &Options) -> Result<Self, Error> {
+        Self::parse_spanned(address, options).map_err(Error::from)
+    }
+
+    ///
+    /// Parse an address with the same rules as `FromStr::from_str`, ignoring any white space
+    /// before and after it, as `Options::with_trimming`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let email = EmailAddress::parse_trimmed(" simon@example.com\n").unwrap();
+    /// assert_eq!(email.as_str(), "simon@example.com");
+    ///
+    /// assert_eq!(
+    ///     EmailAddress::parse_trimmed(" simon @example.com"),
+    ///     Err(Error::LocalPartInvalidCharacter(' ', 6))
+    /// );
+    /// ```
+    ///
+    pub fn parse_trimmed(address: &str) -> Result<Self, Error> {
+        Self::parse_with(address, &Options::default().with_trimming())
+    }
+
+    ///
+    /// Parse an address from `bytes`, such as the raw bytes of a message header, with the same
+    /// rules as `FromStr::from_str`. Non-ASCII bytes must be UTF-8, as RFC 6532 requires; an
+    /// invalid sequence is reported as `Error::InvalidUtf8` with its offset.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let email = EmailAddress::parse_bytes("jöran@example.com".as_bytes()).unwrap();
+    /// assert_eq!(email.local_part(), "jöran");
+    ///
+    /// assert_eq!(
+    ///     EmailAddress::parse_bytes(b"j\xF6ran@example.com"),
+    ///     Err(Error::InvalidUtf8(1))
+    /// );
+    /// ```
+    ///
+    pub fn parse_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Self::parse_bytes_with(bytes, &Options::default())
+    }
+
+    ///
+    /// Parse an address from `bytes` using the rules selected by `options`.
+    ///
+    pub fn parse_bytes_with(bytes: &[u8], options: &Options) -> Result<Self, Error> {
+        let address =
+            std::str::from_utf8(bytes).map_err(|e| Error::InvalidUtf8(e.valid_up_to()))?;
+        Self::parse_with(address, options)
+    }
+
+    ///
+    /// Parse the `address` string using the rules selected by `options`, returning an error that
+    /// includes the span of the address it was found in.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let error = EmailAddress::parse_spanned("simon@exa mple.com", &Options::default())
+    ///     .unwrap_err();
+    ///
+    /// assert_eq!(error.error(), &Error::DomainInvalidCharacter(' ', 9));
+    /// assert_eq!(error.span(), 9..10);
+    /// ```
+    ///
+    pub fn parse_spanned(address: &str, options: &Options) -> Result<Self, ParseError> {
+        parse_address(address, options, &mut Collector::default())
+    }
+
+    ///
+    /// Parse the `address` string using the rules selected by `options`, also returning any
+    /// warnings about parts of the address that are valid but unusual.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let (email, warnings) =
+    ///     EmailAddress::parse_with_diagnostics("\"simon\"@localhost", &Options::default())
+    ///         .unwrap();
+    ///
+    /// assert_eq!(email.to_string(), "\"simon\"@localhost");
+    /// assert_eq!(warnings, vec![Warning::QuotedLocalPart, Warning::DotlessDomain]);
+    /// ```
+    ///
+    pub fn parse_with_diagnostics(
+        address: &str,
+        options: &Options,
+    ) -> Result<(Self, Vec<Warning>), Error> {
+        let mut collector = Collector::default().with_warnings();
+        let email = parse_address(address, options, &mut collector)?;
+        Ok((email, collector.into_warnings()))
+    }
+
+    ///
+    /// Diagnose the `address` string using the rules selected by `options`, returning the most
+    /// severe finding as an is_email compatible `Diagnostic`; `Diagnostic::Valid` if there are
+    /// none.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default();
+    /// assert_eq!(EmailAddress::diagnose("simon@example.com", &options), Diagnostic::Valid);
+    /// assert_eq!(EmailAddress::diagnose("\"simon\"@example.com", &options).code(), 11);
+    /// assert_eq!(
+    ///     EmailAddress::diagnose("@example.com", &options).category(),
+    ///     Category::Error
+    /// );
+    /// ```
+    ///
+    pub fn diagnose(address: &str, options: &Options) -> Diagnostic {
+        match Self::parse_with_diagnostics(address, options) {
+            Ok((_, warnings)) => warnings
+                .iter()
+                .map(Diagnostic::from)
+                .max()
+                .unwrap_or(Diagnostic::Valid),
+            Err(error) => Diagnostic::from(&error),
+        }
+    }
+
+    ///
+    /// Validate the `address` string using the default options. Unlike `from_str`, which stops
+    /// at the first error, this continues past each error and returns all of those found.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// assert_eq!(
+    ///     EmailAddress::validate("a,b@exa mple.com"),
+    ///     Err(vec![
+    ///         Error::LocalPartInvalidCharacter(',', 1),
+    ///         Error::DomainInvalidCharacter(' ', 7)
+    ///     ])
+    /// );
+    /// ```
+    ///
+    pub fn validate(address: &str) -> Result<Self, Vec<Error>> {
+        Self::validate_with(address, &Options::default())
+    }
+
+    ///
+    /// Validate the `address` string using the rules selected by `options`, returning all of
+    /// the errors found rather than only the first.
+    ///
+    pub fn validate_with(address: &str, options: &Options) -> Result<Self, Vec<Error>> {
+        let mut collector = Collector::default().with_errors();
+        parse_address(address, options, &mut collector).map_err(|_| {
+            collector
+                .into_errors()
+                .into_iter()
+                .map(Error::from)
+                .collect()
+        })
+    }
+
+    ///
+    /// Parse each of `addresses` using the rules selected by `options`, returning the index of
+    /// each address in `addresses` with the result of parsing it, in order; for cleaning large
+    /// lists of addresses.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let results = EmailAddress::validate_bulk(
+    ///     vec!["simon@example.com", "simon", "jane@example.org"],
+    ///     &Options::default(),
+    /// );
+    /// let invalid: Vec<usize> = results
+    ///     .iter()
+    ///     .filter(|(_, result)| result.is_err())
+    ///     .map(|(index, _)| *index)
+    ///     .collect();
+    ///
+    /// assert_eq!(invalid, vec![1]);
+    /// ```
+    ///
+    pub fn validate_bulk<I, S>(addresses: I, options: &Options) -> Vec<(usize, Result<Self, Error>)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        addresses
+            .into_iter()
+            .map(|address| Self::parse_with(address.as_ref(), options))
+            .enumerate()
+            .collect()
+    }
+
+    ///
+    /// As `validate_bulk`, but parsing the addresses in parallel on the `rayon` thread pool. The
+    /// results are still returned in the order of `addresses`.
+    ///
+    #[cfg(feature = "rayon")]
+    pub fn par_validate_bulk<S>(
+        addresses: &[S],
+        options: &Options,
+    ) -> Vec<(usize, Result<Self, Error>)>
+    where
+        S: AsRef<str> + Sync,
+    {
+        use rayon::prelude::*;
+        addresses
+            .par_iter()
+            .map(|address| Self::parse_with(address.as_ref(), options))
+            .enumerate()
+            .collect()
+    }
+
+    ///
+    /// As `validate_bulk`, but parsing the addresses of an asynchronous stream as they arrive,
+    /// each as a task spawned on `executor`, such as a `futures::executor::ThreadPool`; at most
+    /// `limit` parses run at once. The results are returned in the order of `addresses`. An
+    /// address that cannot be spawned, as the executor has shut down, is parsed by the stream
+    /// itself.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use futures::{executor::LocalPool, stream, StreamExt};
+    ///
+    /// let mut pool = LocalPool::new();
+    /// let addresses = stream::iter(vec!["simon@example.com".to_string(), "simon".to_string()]);
+    /// let stream = EmailAddress::validate_stream(addresses, &Options::default(), pool.spawner(), 16);
+    /// let results: Vec<(usize, Result<EmailAddress, Error>)> = pool.run_until(stream.collect());
+    ///
+    /// assert!(results[0].1.is_ok());
+    /// assert_eq!(results[1], (1, Err(Error::MissingSeparator)));
+    /// ```
+    ///
+    #[cfg(feature = "futures")]
+    pub fn validate_stream<S, E>(
+        addresses: S,
+        options: &Options,
+        executor: E,
+        limit: usize,
+    ) -> impl futures::Stream<Item = (usize, Result<Self, Error>)>
+    where
+        S: futures::Stream,
+        S::Item: AsRef<str> + Send + Sync + 'static,
+        E: futures::task::Spawn,
+    {
+        use futures::future::{self, Either};
+        use futures::task::SpawnExt;
+        use futures::StreamExt;
+        let options = Arc::new(options.clone());
+        addresses
+            .map(move |address| {
+                let address = Arc::new(address);
+                let task = {
+                    let address = address.clone();
+                    let options = options.clone();
+                    async move { Self::parse_with((*address).as_ref(), &options) }
+                };
+                match executor.spawn_with_handle(task) {
+                    Ok(handle) => Either::Left(handle),
+                    Err(_) => Either::Right(future::ready(Self::parse_with(
+                        (*address).as_ref(),
+                        &options,
+                    ))),
+                }
+            })
+            .buffered(limit.max(1))
+            .enumerate()
+    }
+
+    ///
+    /// Deduplicate `addresses`, taking two addresses to be the same mailbox under the rules of
+    /// `equivalence`; the first of each is kept, in order, and each later one is returned as a
+    /// duplicate with the index of the address it was taken to be the same as.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let addresses = ["simon@example.com", "simon+news@Example.COM", "jane@example.org"]
+    ///     .iter()
+    ///     .map(|s| EmailAddress::from_str(s).unwrap());
+    /// let deduplicated = EmailAddress::dedupe(addresses, Equivalence::IgnoreTags);
+    ///
+    /// assert_eq!(
+    ///     deduplicated.unique().to_string(),
+    ///     "simon@example.com, jane@example.org"
+    /// );
+    /// assert_eq!(deduplicated.duplicates()[0].0.as_str(), "simon+news@Example.COM");
+    /// assert_eq!(deduplicated.duplicates()[0].1, 0);
+    /// ```
+    ///
+    pub fn dedupe<I>(addresses: I, equivalence: Equivalence) -> Deduplicated
+    where
+        I: IntoIterator<Item = EmailAddress>,
+    {
+        dedupe::dedupe(addresses, equivalence)
+    }
+
+    ///
+    /// Determine whether the `part` string would be a valid `local-part` if it were in an
+    /// email address.
+    ///
+    pub fn is_valid_local_part(part: &str) -> bool {
+        parse_local_part(part, &Options::default()).is_ok()
+    }
+
+    ///
+    /// Determine whether the `part` string would be a valid `domain` if it were in an
+    /// email address.
+    ///
+    pub fn is_valid_domain(part: &str) -> bool {
+        parse_domain(part, &Options::default()).is_ok()
+    }
+
+    ///
+    /// Returns `true` if this address contains characters outside ASCII, and so may only be sent
+    /// to a server that supports the `SMTPUTF8` extension of RFC 6531.
+    ///
+    pub fn requires_smtputf8(&self) -> bool {
+        !self.as_str().is_ascii()
+    }
+
+    ///
+    /// Returns `true` if this address, as written, is an RFC 5321 `Mailbox`, as extended by
+    /// RFC 6531, and so may be written into an SMTP command. An address parsed with
+    /// `Options::with_obsolete` may not be; its local part may be in the obsolete form, or a
+    /// quoted-pair or domain literal may contain control characters, such as CR and LF, that
+    /// would end the command early.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_obsolete();
+    /// let email = EmailAddress::parse_with("\"si mon\"@example.com", &options).unwrap();
+    /// assert!(email.is_smtp_mailbox());
+    ///
+    /// let email = EmailAddress::parse_with("\"a\\\r\\\nRSET\"@example.com", &options).unwrap();
+    /// assert!(!email.is_smtp_mailbox());
+    /// ```
+    ///
+    pub fn is_smtp_mailbox(&self) -> bool {
+        self.local_part_kind() != LocalPartKind::Obsolete
+            && !self.as_str().chars().any(|c| c.is_ascii_control())
+    }
+
+    ///
+    /// Return the SMTP `MAIL` command with this address as the reverse-path, without the
+    /// trailing CRLF, or `None` if this address is not an SMTP mailbox, as tested by
+    /// `is_smtp_mailbox`. If the address is internationalized, the `SMTPUTF8` parameter is
+    /// added, RFC 6531, §3.4.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@example.com").unwrap();
+    /// assert_eq!(
+    ///     email.to_mail_from_command().as_deref(),
+    ///     Some("MAIL FROM:<simon@example.com>")
+    /// );
+    ///
+    /// let email = EmailAddress::from_str("jöran@example.com").unwrap();
+    /// assert_eq!(
+    ///     email.to_mail_from_command().as_deref(),
+    ///     Some("MAIL FROM:<jöran@example.com> SMTPUTF8")
+    /// );
+    /// ```
+    ///
+    pub fn to_mail_from_command(&self) -> Option<String> {
+        if !self.is_smtp_mailbox() {
+            return None;
+        }
+        let mut command = format!("{}{}{}{}", SMTP_MAIL_FROM, LT, self, GT);
+        if self.requires_smtputf8() {
+            command.push(' ');
+            command.push_str(SMTP_UTF8_PARAMETER);
+        }
+        Some(command)
+    }
+
+    ///
+    /// Return the SMTP `RCPT` command with this address as the forward-path, without the
+    /// trailing CRLF, or `None` if this address is not an SMTP mailbox, as tested by
+    /// `is_smtp_mailbox`. There is no parameter for an internationalized recipient; the `MAIL`
+    /// command of the transaction must instead include `SMTPUTF8`, which is required if
+    /// `requires_smtputf8` returns `true`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@example.com").unwrap();
+    /// assert_eq!(
+    ///     email.to_rcpt_to_command().as_deref(),
+    ///     Some("RCPT TO:<simon@example.com>")
+    /// );
+    /// ```
+    ///
+    pub fn to_rcpt_to_command(&self) -> Option<String> {
+        if !self.is_smtp_mailbox() {
+            return None;
+        }
+        Some(format!("{}{}{}{}", SMTP_RCPT_TO, LT, self, GT))
+    }
+
+    ///
+    /// Return this email address encoded as RFC 3461 `xtext`, as in the `ORCPT` parameter of an
+    /// SMTP `RCPT` command requesting a Delivery Status Notification.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon+news@example.com").unwrap();
+    /// assert_eq!(email.to_xtext(), "simon+2Bnews@example.com");
+    /// ```
+    ///
+    pub fn to_xtext(&self) -> String {
+        xtext::encode(self.as_str())
+    }
+
+    ///
+    /// Decode an address from RFC 3461 `xtext`, such as the original recipient reported in a
+    /// Delivery Status Notification, with the same rules as `FromStr::from_str`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let orcpt = "rfc822;simon+2Bnews@example.com";
+    /// let xtext = orcpt.strip_prefix("rfc822;").unwrap();
+    /// let email = EmailAddress::from_xtext(xtext).unwrap();
+    /// assert_eq!(email.as_str(), "simon+news@example.com");
+    ///
+    /// assert_eq!(
+    ///     EmailAddress::from_xtext("simon+news@example.com"),
+    ///     Err(Error::InvalidXtext(5))
+    /// );
+    /// ```
+    ///
+    pub fn from_xtext(xtext: &str) -> Result<Self, Error> {
+        Self::from_xtext_with(xtext, &Options::default())
+    }
+
+    ///
+    /// Decode an address from RFC 3461 `xtext` using the rules selected by `options`. The
+    /// position in an `Error::InvalidXtext` is that of the character of `xtext`; the positions
+    /// in any other error are those of the decoded address.
+    ///
+    pub fn from_xtext_with(xtext: &str, options: &Options) -> Result<Self, Error> {
+        let decoded = xtext::decode(xtext)?;
+        Self::parse_bytes_with(&decoded, options)
+    }
+
+    ///
+    /// Return this email address formatted as a URI. This will also URI-encode the email
+    /// address itself. So, `name@example.org` becomes `mailto:name%40example.org`.
+    ///
+    pub fn to_uri(&self) -> String {
+        let encoded = encode(self.as_str());
+        format!("{}{}", MAILTO_URI_PREFIX, encoded)
+    }
+
+    ///
+    /// Return a string formatted as a display email with the user name. This is commonly used
+    /// in email headers and other locations where a display name is associated with the
+    /// address.
+    ///
+    /// So, `("name@example.org", "My Name")` becomes `"My Name <name@example.org>"`.
+    ///
+    pub fn to_display(&self, display_name: &str) -> String {
+        format!("{} <{}>", display_name, self)
+    }
+
+    ///
+    /// Returns this address, with `display_name`, as a `lettre` `Mailbox`; requires the `lettre`
+    /// feature.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("name@example.org").unwrap();
+    /// let mailbox = email.to_mailbox("My Name").unwrap();
+    /// assert_eq!(mailbox.to_string(), "My Name <name@example.org>");
+    /// ```
+    ///
+    #[cfg(feature = "lettre")]
+    pub fn to_mailbox(
+        &self,
+        display_name: &str,
+    ) -> Result<lettre::message::Mailbox, lettre::address::AddressError> {
+        use std::convert::TryFrom;
+        Ok(lettre::message::Mailbox::new(
+            Some(display_name.to_string()),
+            lettre::Address::try_from(self)?,
+        ))
+    }
+
+    /// Returns the email address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the local part of the EmailAddress
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// Returns the domain part of the EmailAddress
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+
+    ///
+    /// Returns the length of the address, in octets of UTF-8, as measured against the limits of
+    /// RFC 5321 and as needed to size a byte-oriented column. An address is never empty.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("jöran@example.com").unwrap();
+    /// assert_eq!(email.len(), 18);
+    /// assert_eq!(email.chars_len(), 17);
+    /// assert_eq!(email.local_part_len(), 6);
+    /// assert_eq!(email.domain_len(), 11);
+    /// ```
+    ///
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.address.len()
+    }
+
+    ///
+    /// Returns the length of the address in characters, Unicode scalar values, as needed to size
+    /// a character-oriented column.
+    ///
+    pub fn chars_len(&self) -> usize {
+        self.address.chars().count()
+    }
+
+    /// Returns the length of the local part, in octets of UTF-8.
+    pub fn local_part_len(&self) -> usize {
+        self.at
+    }
+
+    ///
+    /// Returns the length of the domain, in octets of UTF-8, as stored; a domain with U-labels
+    /// is longer when converted to A-labels.
+    ///
+    pub fn domain_len(&self) -> usize {
+        self.address.len() - self.at - 1
+    }
+
+    ///
+    /// Used by the `email!` macro to construct an address it has already parsed; this is not
+    /// part of the public API.
+    ///
+    #[doc(hidden)]
+    pub fn __from_parts_unchecked(local: &str, domain: &str) -> Self {
+        Self::from_parts(local, domain)
+    }
+
+    ///
+    /// An address of a `local` part and `domain` that have already been parsed.
+    ///
+    pub(crate) fn from_parts(local: &str, domain: &str) -> Self {
+        let mut address = Buffer::with_capacity(local.len() + 1 + domain.len());
+        address.push_str(local);
+        address.push(AT);
+        address.push_str(domain);
+        EmailAddress {
+            address,
+            at: local.len(),
+            original: None,
+        }
+    }
+
+    ///
+    /// Returns exactly the string this address was parsed from, which may differ from `as_str`,
+    /// such as by surrounding angle brackets or white space, comments, or the case of the
+    /// domain; for addresses that were not parsed from a string, this is the same as `as_str`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let options = Options::default().with_lowercase_domain();
+    /// let email = EmailAddress::parse_with("<Simon@Example.COM>", &options).unwrap();
+    /// assert_eq!(email.as_str(), "Simon@example.com");
+    /// assert_eq!(email.original(), "<Simon@Example.COM>");
+    /// ```
+    ///
+    pub fn original(&self) -> &str {
+        self.original.as_deref().unwrap_or(&self.address)
+    }
+
+    ///
+    /// Returns `true` if the domain of this address is `domain`, or is a sub-domain of `domain`.
+    /// Domains are compared without regard to case or a trailing dot, and only whole labels
+    /// match, so that `notexample.com` is not a sub-domain of `example.com`. A domain literal
+    /// is only equal to, and never a sub-domain of, another domain.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@Mail.Example.com").unwrap();
+    /// assert!(email.is_subdomain_of("example.com"));
+    /// assert!(email.is_subdomain_of("mail.example.com."));
+    /// assert!(!email.is_subdomain_of("ample.com"));
+    /// ```
+    ///
+    pub fn is_subdomain_of(&self, domain: &str) -> bool {
+        let own = self.domain().trim_end_matches(DOT).to_lowercase();
+        let domain = domain.trim_end_matches(DOT).to_lowercase();
+        if domain.is_empty() || own.starts_with(LBRACKET) || domain.starts_with(LBRACKET) {
+            return !domain.is_empty() && own == domain;
+        }
+        match own.strip_suffix(domain.as_str()) {
+            Some(rest) => rest.is_empty() || rest.ends_with(DOT),
+            None => false,
+        }
+    }
+
+    ///
+    /// Returns a key ordering addresses by domain first, so that sorting groups them by
+    /// organization: the lowercase labels of the domain from the top-level domain down,
+    /// followed by the local part. A domain literal is a single label.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let mut addresses: Vec<EmailAddress> = [
+    ///     "simon@mail.example.com",
+    ///     "jane@example.org",
+    ///     "alice@Example.com",
+    ///     "bob@example-x.com",
+    /// ]
+    /// .iter()
+    /// .map(|s| EmailAddress::from_str(s).unwrap())
+    /// .collect();
+    /// addresses.sort_by_cached_key(EmailAddress::sort_key);
+    ///
+    /// assert_eq!(
+    ///     addresses.iter().map(EmailAddress::as_str).collect::<Vec<_>>(),
+    ///     vec![
+    ///         "alice@Example.com",
+    ///         "simon@mail.example.com",
+    ///         "bob@example-x.com",
+    ///         "jane@example.org",
+    ///     ]
+    /// );
+    /// ```
+    ///
+    pub fn sort_key(&self) -> (Vec<String>, String) {
+        let domain = self.domain().to_lowercase();
+        let labels = if domain.starts_with(LBRACKET) {
+            vec![domain]
+        } else {
+            domain.rsplit(DOT).map(String::from).collect()
+        };
+        (labels, self.local_part().to_string())
+    }
+
+    ///
+    /// Returns `true` if this address is permitted by the `rfc822Name` name constraint
+    /// `constraint`, following RFC 5280, §4.2.1.10. A constraint may be a mailbox, which this
+    /// address must equal, a host, which must be the domain of this address, or a domain
+    /// starting with '.', of which the domain of this address must be a sub-domain. Local parts
+    /// are compared with regard to case, and domains without regard to case, as in §7.5. An
+    /// address with a domain literal matches no constraint.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@mail.Example.com").unwrap();
+    /// assert!(email.matches_name_constraint("simon@mail.example.com"));
+    /// assert!(email.matches_name_constraint("mail.example.com"));
+    /// assert!(email.matches_name_constraint(".example.com"));
+    /// assert!(!email.matches_name_constraint("example.com"));
+    /// assert!(!email.matches_name_constraint(".mail.example.com"));
+    /// assert!(!email.matches_name_constraint("Simon@mail.example.com"));
+    /// ```
+    ///
+    pub fn matches_name_constraint(&self, constraint: &str) -> bool {
+        let domain = self.domain();
+        if domain.starts_with(LBRACKET) {
+            return false;
+        }
+        match constraint.rsplit_once(AT) {
+            Some((local_part, host)) => {
+                local_part == self.local_part() && host.eq_ignore_ascii_case(domain)
+            }
+            None => match constraint.strip_prefix(DOT) {
+                Some(suffix) => {
+                    !suffix.is_empty()
+                        && domain.len() > constraint.len()
+                        && matches!(
+                            domain.get(domain.len() - constraint.len()..),
+                            Some(tail) if tail.eq_ignore_ascii_case(constraint)
+                        )
+                }
+                None => constraint.eq_ignore_ascii_case(domain),
+            },
+        }
+    }
+
+    ///
+    /// Returns the syntactic form of the local part of this address.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("\"simon johnston\"@example.com").unwrap();
+    /// assert_eq!(email.local_part_kind(), LocalPartKind::QuotedString);
+    /// assert!(email.is_quoted());
+    /// ```
+    ///
+    pub fn local_part_kind(&self) -> LocalPartKind {
+        parts::local_part_kind(self.local_part())
+    }
+
+    ///
+    /// Returns `true` if the local part of this address is, or in the obsolete form contains,
+    /// a quoted string.
+    ///
+    pub fn is_quoted(&self) -> bool {
+        self.local_part_kind() != LocalPartKind::DotAtom
+    }
+
+    ///
+    /// Returns the logical content of the local part of this address; the quotes around a quoted
+    /// string are removed, and each quoted pair is replaced by the character it quotes. The
+    /// result is borrowed unless the local part contains a quoted string, and is not in general
+    /// a valid `local-part`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str(r#""Joe.\\Blow"@example.com"#).unwrap();
+    /// assert_eq!(email.local_part_unescaped(), r"Joe.\Blow");
+    /// ```
+    ///
+    pub fn local_part_unescaped(&self) -> Cow<'_, str> {
+        parts::unescape_local_part(self.local_part())
+    }
+
+    ///
+    /// Returns `true` if the local part of this address is `Postmaster`, which RFC 5321, §4.5.1,
+    /// requires every domain that accepts mail to provide, and which is matched without regard to
+    /// case.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(EmailAddress::from_str("PostMaster@example.com").unwrap().is_postmaster());
+    /// assert!(!EmailAddress::from_str("postmaster+x@example.com").unwrap().is_postmaster());
+    /// ```
+    ///
+    pub fn is_postmaster(&self) -> bool {
+        self.local_part_unescaped().eq_ignore_ascii_case(POSTMASTER)
+    }
+
+    ///
+    /// Returns the shortest equivalent form of this address, for storage and comparison. If the
+    /// logical content of the local part, as returned by `local_part_unescaped`, is a valid
+    /// `dot-atom` then the local part is written as that `dot-atom`, otherwise as a single
+    /// quoted string in which only the characters that are neither `qtext` nor white space, such
+    /// as '"' and '\\', are quoted. Comments, which are
+    /// only allowed with `Options::with_cfws`, are already removed when an address is parsed.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("\"john.doe\"@x.com").unwrap();
+    /// assert_eq!(email.minimally_quoted().to_string(), "john.doe@x.com");
+    ///
+    /// let email = EmailAddress::from_str(r#""john\ doe"@x.com"#).unwrap();
+    /// assert_eq!(email.minimally_quoted().to_string(), "\"john doe\"@x.com");
+    /// ```
+    ///
+    pub fn minimally_quoted(&self) -> Self {
+        EmailAddress::from_parts(&parts::minimally_quote(self.local_part()), self.domain())
+    }
+
+    ///
+    /// Returns this address in lowercase, except for the content of any quoted string in the
+    /// local part, which may be case-sensitive, and any domain literal; unlike calling
+    /// `str::to_lowercase` on the address, this never changes the mailbox that a quoted local
+    /// part names.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("Simon@Example.COM").unwrap();
+    /// assert_eq!(email.to_lowercase().as_str(), "simon@example.com");
+    ///
+    /// let email = EmailAddress::from_str("\"Simon Johnston\"@Example.COM").unwrap();
+    /// assert_eq!(email.to_lowercase().as_str(), "\"Simon Johnston\"@example.com");
+    /// ```
+    ///
+    pub fn to_lowercase(&self) -> Self {
+        self.lowercased(false)
+    }
+
+    ///
+    /// Returns this address in lowercase, including the content of any quoted string in the
+    /// local part, but not any domain literal.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("\"Simon Johnston\"@Example.COM").unwrap();
+    /// assert_eq!(
+    ///     email.to_lowercase_including_quoted().as_str(),
+    ///     "\"simon johnston\"@example.com"
+    /// );
+    /// ```
+    ///
+    pub fn to_lowercase_including_quoted(&self) -> Self {
+        self.lowercased(true)
+    }
+
+    ///
+    /// Parse an address with the same rules as `FromStr::from_str`, returning it in lowercase as
+    /// `to_lowercase`.
+    ///
+    pub fn parse_lowercased(address: &str) -> Result<Self, Error> {
+        Self::parse_lowercased_with(address, &Options::default())
+    }
+
+    ///
+    /// Parse an address using the rules selected by `options`, returning it in lowercase as
+    /// `to_lowercase`.
+    ///
+    pub fn parse_lowercased_with(address: &str, options: &Options) -> Result<Self, Error> {
+        let mut email = Self::parse_with(address, options)?.to_lowercase();
+        if email.as_str() != address {
+            email.original = Some(address.into());
+        }
+        Ok(email)
+    }
+
+    ///
+    /// Returns the canonical byte encoding of this address, a stable form for hashing that
+    /// can be computed identically in other languages. Version 1 of the encoding, identified by
+    /// `CANONICAL_BYTES_VERSION`, is:
+    ///
+    /// 1. The version, as a single byte, `0x01`.
+    /// 2. The length in bytes of the local part, as a 32-bit unsigned big-endian integer.
+    /// 3. The UTF-8 bytes of the local part, minimally quoted, as by `minimally_quoted`, and
+    ///    lowercased, as by `to_lowercase`, so that the content of a quoted string keeps its
+    ///    case.
+    /// 4. The UTF-8 bytes of the domain, lowercased using the Unicode lowercase mapping; a
+    ///    domain literal is lowercased in the same way. The domain is not converted between
+    ///    U-labels and A-labels.
+    ///
+    /// No Unicode normalization is applied. The encoding of a given version never changes; a
+    /// change to the rules is made as a new version.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("\"Simon\"@Example.COM").unwrap();
+    /// assert_eq!(
+    ///     email.canonical_bytes(),
+    ///     b"\x01\x00\x00\x00\x05simonexample.com".to_vec()
+    /// );
+    /// ```
+    ///
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        let email = self.minimally_quoted().to_lowercase();
+        let local = email.local_part().as_bytes();
+        // `to_lowercase` leaves a domain literal as it is.
+        let domain = email.domain().to_lowercase();
+        let domain = domain.as_bytes();
+        let mut bytes = Vec::with_capacity(5 + local.len() + domain.len());
+        bytes.push(Self::CANONICAL_BYTES_VERSION);
+        bytes.extend_from_slice(&(local.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(local);
+        bytes.extend_from_slice(domain);
+        bytes
+    }
+
+    /// The version of the encoding returned by `canonical_bytes`.
+    pub const CANONICAL_BYTES_VERSION: u8 = 1;
+
+    ///
+    /// Returns the SHA-256 hash, as 64 lowercase hexadecimal digits, of this address in the
+    /// form used to match customer lists by advertising platforms: the whole address
+    /// lowercased. Surrounding whitespace, which those platforms also trim, is never part of a
+    /// parsed address; see `Options::with_trimming`. Requires the `hashing` feature.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("Test@Example.COM").unwrap();
+    /// assert_eq!(
+    ///     email.hashed_sha256(),
+    ///     "973dfe463ec85785f5f95af5ba3906eedb2d931c24e69824a89ea65dba4e813b"
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "hashing")]
+    pub fn hashed_sha256(&self) -> String {
+        Self::sha256_hex(&self.as_str().to_lowercase())
+    }
+
+    ///
+    /// As `hashed_sha256`, but first applying the provider `rules`, as by `canonicalize`; some
+    /// platforms, for example, require the dots to be removed from Gmail addresses, as done
+    /// by `ProviderRule::built_in`. Requires the `hashing` feature.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("John.Doe@gmail.com").unwrap();
+    /// assert_eq!(
+    ///     email.hashed_sha256_with(ProviderRule::built_in()),
+    ///     "06a240d11cc201676da976f7b49341181fd180da37cbe40a77432c0a366c80c3"
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "hashing")]
+    pub fn hashed_sha256_with<R: ProviderRules + ?Sized>(&self, rules: &R) -> String {
+        self.canonicalize(rules).hashed_sha256()
+    }
+
+    #[cfg(feature = "hashing")]
+    fn sha256_hex(s: &str) -> String {
+        use sha2::{Digest, Sha256};
+        Sha256::digest(s.as_bytes())
+            .iter()
+            .map(|octet| format!("{:02x}", octet))
+            .collect()
+    }
+
+    fn lowercased(&self, quoted: bool) -> Self {
+        let local = parts::lowercase_local_part(self.local_part(), quoted);
+        let domain = self.domain();
+        if domain.starts_with(LBRACKET) {
+            EmailAddress::from_parts(&local, domain)
+        } else {
+            EmailAddress::from_parts(&local, &domain.to_lowercase())
+        }
+    }
+
+    ///
+    /// Returns the normalized form of this address, so that equivalent addresses written in
+    /// different ways compare, and hash, as equal. The rules below are stable, and will not
+    /// change without a major version:
+    ///
+    /// 1. The local part is lowercased if `case` is `LocalPartCase::Lowercase`, using the Unicode
+    ///    lowercase mapping and including the content of quoted strings.
+    /// 2. The domain, including any domain literal, is lowercased using the Unicode lowercase
+    ///    mapping.
+    /// 3. Both parts are converted to Unicode Normalization Form C (NFC); the local part with any
+    ///    quoting removed, so that a quoted-pair does not separate a combining character from
+    ///    its base.
+    /// 4. The local part is minimally quoted, as by `minimally_quoted`.
+    ///
+    /// The domain is not converted between U-labels and A-labels.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("\"Simon\"@Example.COM").unwrap();
+    /// assert_eq!(
+    ///     email.normalized(LocalPartCase::Preserve).to_string(),
+    ///     "Simon@example.com"
+    /// );
+    /// assert_eq!(
+    ///     email.normalized(LocalPartCase::Lowercase).to_string(),
+    ///     "simon@example.com"
+    /// );
+    /// ```
+    ///
+    #[cfg(feature = "normalize")]
+    pub fn normalized(&self, case: LocalPartCase) -> Self {
+        use unicode_normalization::UnicodeNormalization;
+        let content = parts::unescape_local_part(self.local_part());
+        let content = match case {
+            LocalPartCase::Preserve => content.nfc().collect::<String>(),
+            LocalPartCase::Lowercase => content.to_lowercase().nfc().collect(),
+        };
+        let domain: String = self.domain().to_lowercase().nfc().collect();
+        EmailAddress::from_parts(&parts::quote_content(Cow::Owned(content)), &domain)
+    }
+
+    ///
+    /// Returns the tag of a sub-address, the part of the local part after the first '+'; or
+    /// `None` if the local part has no '+', the part before it is not a valid local part, or
+    /// the local part contains a quoted string.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon+news@example.com").unwrap();
+    /// assert_eq!(email.tag(), Some("news"));
+    /// assert_eq!(email.without_tag().to_string(), "simon@example.com");
+    /// ```
+    ///
+    pub fn tag(&self) -> Option<&str> {
+        self.tag_with(DEFAULT_TAG_SEPARATORS)
+    }
+
+    ///
+    /// Returns the tag of a sub-address, the part of the local part after the first of any of
+    /// `separators`; such as '-' for Qmail, or '=' for some other systems.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon-news@example.com").unwrap();
+    /// assert_eq!(email.tag(), None);
+    /// assert_eq!(email.tag_with(&['+', '-']), Some("news"));
+    /// ```
+    ///
+    pub fn tag_with(&self, separators: &[char]) -> Option<&str> {
+        split_tag(self.local_part(), separators).map(|(_, tag)| tag)
+    }
+
+    ///
+    /// Returns this address without the tag of a sub-address, or unchanged if it has no tag.
+    ///
+    pub fn without_tag(&self) -> Self {
+        self.without_tag_with(DEFAULT_TAG_SEPARATORS)
+    }
+
+    ///
+    /// Returns this address without the tag of a sub-address separated by any of `separators`,
+    /// or unchanged if it has no tag.
+    ///
+    pub fn without_tag_with(&self, separators: &[char]) -> Self {
+        match split_tag(self.local_part(), separators) {
+            Some((base, _)) => EmailAddress::from_parts(base, self.domain()),
+            None => self.clone(),
+        }
+    }
+
+    ///
+    /// Returns `true` if this address and `other` are the same once any tags are removed; the
+    /// domains are compared without regard to case.
+    ///
+    pub fn matches_ignoring_tag(&self, other: &EmailAddress) -> bool {
+        self.matches_ignoring_tag_with(other, DEFAULT_TAG_SEPARATORS)
+    }
+
+    ///
+    /// Returns `true` if this address and `other` are the same once any tags separated by any
+    /// of `separators` are removed; the domains are compared without regard to case.
+    ///
+    pub fn matches_ignoring_tag_with(&self, other: &EmailAddress, separators: &[char]) -> bool {
+        let base = |local| {
+            split_tag(local, separators)
+                .map(|(base, _)| base)
+                .unwrap_or(local)
+        };
+        base(self.local_part()) == base(other.local_part())
+            && self.domain().to_lowercase() == other.domain().to_lowercase()
+    }
+
+    ///
+    /// If this is a Bounce Address Tag Validation (BATV) address, such as
+    /// `prvs=0123a1b2c3=simon@example.com`, returns its tag and original address.
+    ///
+    pub fn batv(&self) -> Option<Batv> {
+        batv::parse(self)
+    }
+
+    ///
+    /// Returns the canonical form of this address under the first of `rules` that applies to
+    /// it, for detecting duplicate accounts. If no rule applies, or the local part contains a
+    /// quoted string, the address is returned unchanged.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("Simon.Johnston+news@googlemail.com").unwrap();
+    /// assert_eq!(
+    ///     email.canonicalize(ProviderRule::built_in()).to_string(),
+    ///     "simonjohnston@gmail.com"
+    /// );
+    /// ```
+    ///
+    pub fn canonicalize<R: ProviderRules + ?Sized>(&self, rules: &R) -> Self {
+        if self.is_quoted() {
+            return self.clone();
+        }
+        match rules.canonicalize(self.local_part(), self.domain()) {
+            Some((local, domain)) if parser::is_dot_atom(&local) => {
+                EmailAddress::from_parts(&local, &domain)
+            }
+            _ => self.clone(),
+        }
+    }
+
+    ///
+    /// Returns the local part of this address as a `LocalPart`.
+    ///
+    pub fn to_local_part(&self) -> LocalPart {
+        LocalPart::new_unchecked(self.local_part().to_string())
+    }
+
+    ///
+    /// Returns the kind of the domain of this address, a domain name or the address of a domain
+    /// literal.
+    ///
+    pub fn domain_part(&self) -> DomainPart<'_> {
+        parts::domain_part(self.domain())
+    }
+
+    ///
+    /// Returns the scope of the IP address if the domain of this address is an address literal,
+    /// as `DomainPart::ip_scope`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@[127.0.0.1]").unwrap();
+    /// assert_eq!(email.ip_scope(), Some(IpScope::Loopback));
+    ///
+    /// let email = EmailAddress::from_str("simon@example.com").unwrap();
+    /// assert_eq!(email.ip_scope(), None);
+    /// ```
+    ///
+    pub fn ip_scope(&self) -> Option<IpScope> {
+        self.domain_part().ip_scope()
+    }
+
+    ///
+    /// Returns the domain of this address as a `Domain`.
+    ///
+    pub fn to_domain(&self) -> Domain {
+        Domain::new_unchecked(self.domain().to_string())
+    }
+
+    ///
+    /// Returns the domain of this address as a `DomainName`, or `None` if the domain is a domain
+    /// literal.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@mail.example.com").unwrap();
+    /// assert_eq!(email.domain_name().unwrap().label_count(), 3);
+    /// ```
+    ///
+    pub fn domain_name(&self) -> Option<DomainName> {
+        if self.domain().starts_with(LBRACKET) {
+            None
+        } else {
+            Some(DomainName::new_unchecked(self.domain().to_string()))
+        }
+    }
+
+    ///
+    /// Returns the top-level domain, the last label of the domain, as written in the address;
+    /// or `None` if the domain is a domain literal or has only one label.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// assert_eq!(EmailAddress::from_str("simon@example.co.uk").unwrap().tld(), Some("uk"));
+    /// assert_eq!(EmailAddress::from_str("simon@localhost").unwrap().tld(), None);
+    /// assert_eq!(EmailAddress::from_str("simon@[192.168.2.1]").unwrap().tld(), None);
+    /// ```
+    ///
+    pub fn tld(&self) -> Option<&str> {
+        if self.domain().starts_with(LBRACKET) {
+            return None;
+        }
+        self.domain().rsplit_once(DOT).map(|(_, tld)| tld)
+    }
+
+    ///
+    /// If the domain of this address looks like a mistyped domain of one of the major mail
+    /// providers, return the provider's domain.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@gmial.com").unwrap();
+    /// assert_eq!(email.suggest_domain(), Some("gmail.com"));
+    ///
+    /// let email = EmailAddress::from_str("simon@gmail.com").unwrap();
+    /// assert_eq!(email.suggest_domain(), None);
+    /// ```
+    ///
+    pub fn suggest_domain(&self) -> Option<&'static str> {
+        suggest::suggest_domain(self.domain())
+    }
+
+    ///
+    /// If the top-level domain of this address looks like a mistyped top-level domain, return
+    /// the domain with the top-level domain corrected.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("simon@example.con").unwrap();
+    /// assert_eq!(email.suggest_tld().as_deref(), Some("example.com"));
+    ///
+    /// let email = EmailAddress::from_str("simon@example.couk").unwrap();
+    /// assert_eq!(email.suggest_tld().as_deref(), Some("example.co.uk"));
+    /// ```
+    ///
+    pub fn suggest_tld(&self) -> Option<String> {
+        suggest::suggest_tld(self.domain())
+    }
+
+    ///
+    /// Returns `true` if the domain of this address is one reserved by RFC 2606 for testing and
+    /// documentation, or is a sub-domain of one; these are `example.com`, `example.net`,
+    /// `example.org`, and the top-level domains `example`, `invalid`, `localhost`, and `test`.
+    /// No mail can be delivered to such an address.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(EmailAddress::from_str("simon@example.com").unwrap().is_reserved_domain());
+    /// assert!(EmailAddress::from_str("simon@mail.Example.ORG").unwrap().is_reserved_domain());
+    /// assert!(EmailAddress::from_str("simon@host.test").unwrap().is_reserved_domain());
+    /// assert!(!EmailAddress::from_str("simon@example.co.uk").unwrap().is_reserved_domain());
+    /// ```
+    ///
+    pub fn is_reserved_domain(&self) -> bool {
+        RESERVED_DOMAINS
+            .iter()
+            .any(|domain| self.is_subdomain_of(domain))
+    }
+
+    ///
+    /// Returns `true` if the domain of this address is that of a disposable, or temporary, email
+    /// service, according to the built-in list. To check against a list of your own, or one
+    /// refreshed from elsewhere, use `DomainList::matches`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(EmailAddress::from_str("simon@mailinator.com").unwrap().is_disposable());
+    /// assert!(!EmailAddress::from_str("simon@example.com").unwrap().is_disposable());
+    /// ```
+    ///
+    #[cfg(feature = "disposable")]
+    pub fn is_disposable(&self) -> bool {
+        DomainList::disposable().matches(self)
+    }
+
+    ///
+    /// Returns `true` if the domain of this address is that of a consumer webmail provider,
+    /// according to the built-in list, and so is unlikely to be a work address. To check against
+    /// a list of your own, or one refreshed from elsewhere, use `DomainList::matches`.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// assert!(EmailAddress::from_str("simon@gmail.com").unwrap().is_free_provider());
+    /// assert!(!EmailAddress::from_str("simon@example.com").unwrap().is_free_provider());
+    /// ```
+    ///
+    #[cfg(feature = "free_provider")]
+    pub fn is_free_provider(&self) -> bool {
+        DomainList::free_providers().matches(self)
+    }
+
+    ///
+    /// Returns the Public Suffix List embedded in this crate, for use with the `publicsuffix`
+    /// crate directly; note that its lookups, unlike those of `public_suffix`, are sensitive to
+    /// ASCII case.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use publicsuffix::Psl;
+    /// use std::convert::TryFrom;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("user@mail.corp.co.uk").unwrap();
+    /// let list = EmailAddress::public_suffix_list();
+    /// let domain = list.domain(email.domain().as_bytes()).unwrap();
+    /// assert_eq!(DomainName::try_from(domain).unwrap().as_ref(), "corp.co.uk");
+    /// ```
+    ///
+    #[cfg(feature = "psl")]
+    pub fn public_suffix_list() -> &'static publicsuffix::List {
+        psl::list()
+    }
+
+    ///
+    /// Returns the public suffix of the domain of this address, according to the Public Suffix
+    /// List; `None` if the domain is a domain literal.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("user@mail.corp.co.uk").unwrap();
+    /// assert_eq!(email.public_suffix(), Some("co.uk"));
+    /// ```
+    ///
+    #[cfg(feature = "psl")]
+    pub fn public_suffix(&self) -> Option<&str> {
+        if self.domain().starts_with(LBRACKET) {
+            return None;
+        }
+        psl::public_suffix(self.domain())
+    }
+
+    ///
+    /// Returns the registrable domain (eTLD+1) of the domain of this address; the public suffix
+    /// and the single label preceding it. This is `None` if the domain is a domain literal, or is
+    /// itself a public suffix.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let email = EmailAddress::from_str("user@mail.corp.co.uk").unwrap();
+    /// assert_eq!(email.registrable_domain(), Some("corp.co.uk"));
+    /// ```
+    ///
+    #[cfg(feature = "psl")]
+    pub fn registrable_domain(&self) -> Option<&str> {
+        if self.domain().starts_with(LBRACKET) {
+            return None;
+        }
+        psl::registrable_domain(self.domain())
+    }
+
+    ///
+    /// Returns `true` if the domain of this address is aligned with the domain of `other`, as
+    /// defined for DMARC identifier alignment in RFC 7489, §3.1. In `Relaxed` mode the domains
+    /// must share the same Organizational Domain, the registrable domain from the Public Suffix
+    /// List; in `Strict` mode they must be the same. Domains are compared without regard to
+    /// case, and a domain literal is never aligned.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let from = EmailAddress::from_str("simon@example.com").unwrap();
+    /// let bounce = EmailAddress::from_str("bounces@mail.example.com").unwrap();
+    ///
+    /// assert!(from.aligned_with(&bounce, AlignmentMode::Relaxed));
+    /// assert!(!from.aligned_with(&bounce, AlignmentMode::Strict));
+    /// ```
+    ///
+    #[cfg(feature = "psl")]
+    pub fn aligned_with(&self, other: &EmailAddress, mode: AlignmentMode) -> bool {
+        if self.domain().starts_with(LBRACKET) || other.domain().starts_with(LBRACKET) {
+            return false;
+        }
+        let (domain, other_domain) = match mode {
+            AlignmentMode::Strict => (self.domain(), other.domain()),
+            AlignmentMode::Relaxed => (
+                self.registrable_domain().unwrap_or(self.domain()),
+                other.registrable_domain().unwrap_or(other.domain()),
+            ),
+        };
+        domain.to_lowercase() == other_domain.to_lowercase()
+    }
+
+    ///
+    /// Look up, in DNS, how mail is delivered to the domain of this address; requires the `dns`
+    /// feature, and must be awaited within a Tokio runtime. The resolver is configured from the
+    /// system configuration, and shared by all checks. A domain that does not exist, or has no
+    /// records, is reported as `DnsCheck::NoRecords` rather than as an error.
+    ///
+    #[cfg(feature = "dns")]
+    pub async fn check_dns(&self) -> Result<DnsCheck, hickory_resolver::error::ResolveError> {
+        dns::check(self, dns::shared_resolver()?).await
+    }
+
+    ///
+    /// As `check_dns`, but making the lookups with `resolver`.
+    ///
+    #[cfg(feature = "dns")]
+    pub async fn check_dns_with(
+        &self,
+        resolver: &hickory_resolver::TokioAsyncResolver,
+    ) -> Result<DnsCheck, hickory_resolver::error::ResolveError> {
+        dns::check(self, resolver).await
+    }
+
+    ///
+    /// Returns `true` if mail can be delivered to the domain of this address; it has MX records,
+    /// or an A or AAAA record in their place, and has not published a null MX record. See
+    /// `check_dns` for the details.
+    ///
+    #[cfg(feature = "dns")]
+    pub async fn has_mx(&self) -> Result<bool, hickory_resolver::error::ResolveError> {
+        Ok(self.check_dns().await?.accepts_mail())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn encode(address: &str) -> String {
+    let mut result = String::new();
+    for c in address.chars() {
+        if is_uri_reserved(c) {
+            result.push_str(&format!("%{:02X}", c as u8))
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+///
+/// Returns the bytes of `s` with each percent-encoded octet, '%' followed by two hexadecimal
+/// digits, decoded; any other '%' is kept.
+///
+fn decode(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let hex = bytes
+            .get(index + 1..index + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .filter(|_| bytes[index] == b'%')
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match hex {
+            Some(b) => {
+                decoded.push(b);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+    decoded
+}
+
+fn is_uri_reserved(c: char) -> bool {
+    c == '!'
+        || c == '#'
+        || c == '$'
+        || c == '%'
+        || c == '&'
+        || c == '\''
+        || c == '('
+        || c == ')'
+        || c == '*'
+        || c == '+'
+        || c == ','
+        || c == '/'
+        || c == ':'
+        || c == ';'
+        || c == '='
+        || c == '?'
+        || c == '@'
+        || c == '['
+        || c == ']'
+}
+
+///
+/// The base and tag of a sub-address, if `local` is a `dot-atom` with one of `separators` after
+/// a base that is itself a `dot-atom`; so not empty, and not ending in '.'.
+///
+fn split_tag<'a>(local: &'a str, separators: &[char]) -> Option<(&'a str, &'a str)> {
+    if parts::local_part_kind(local) != LocalPartKind::DotAtom {
+        return None;
+    }
+    match local.find(separators) {
+        Some(0) | None => None,
+        Some(at) if local[..at].ends_with(DOT) => None,
+        Some(at) => {
+            let separator = local[at..].chars().next()?;
+            Some((&local[..at], &local[at + separator.len_utf8()..]))
+        }
+    }
+}
+
+fn parse_address(
+    address: &str,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<EmailAddress, ParseError> {
+    let parsed = check_address(address, options, collector)?;
+    let mut email = if options.lowercase_domain && !parsed.domain.starts_with(LBRACKET) {
+        EmailAddress::from_parts(&parsed.local, &parsed.domain.to_lowercase())
+    } else {
+        EmailAddress::from_parts(&parsed.local, &parsed.domain)
+    };
+    if email.as_str() != address {
+        email.original = Some(address.into());
+    }
+    Ok(email)
+}
+
+///
+/// Parse and check the lengths of `address`; the parsed components borrow from `address`
+/// unless comments or folding white space were removed.
+///
+fn check_address<'a>(
+    address: &'a str,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<parser::Parsed<'a>, ParseError> {
+    let mut range = 0..address.len();
+    if options.trim {
+        let trimmed = address.trim_start();
+        range.start = address.len() - trimmed.len();
+        range.end = range.start + trimmed.trim_end().len();
+    }
+    if options.mailto_prefix {
+        let uri = &address[range.clone()];
+        let prefix = MAILTO_URI_PREFIX.len();
+        if uri.len() >= prefix
+            && uri.is_char_boundary(prefix)
+            && uri[..prefix].eq_ignore_ascii_case(MAILTO_URI_PREFIX)
+        {
+            let to = &uri[prefix..];
+            let to = to.split_once('?').map_or(to, |(to, _)| to);
+            range = range.start + prefix..range.start + prefix + to.len();
+            if to.contains('%') {
+                return check_percent_encoded(to, range, options, collector);
+            }
+        }
+    }
+    let bracketed = address[range.clone()].starts_with(LT) && address[range.clone()].ends_with(GT);
+    match options.angle_brackets {
+        AngleBrackets::Allowed | AngleBrackets::Required if bracketed => {
+            range = range.start + 1..range.end - 1;
+        }
+        AngleBrackets::Required => {
+            collector.fail(Error::MissingAngleBrackets, range.clone())?;
+        }
+        _ => {}
+    }
+    //
+    // Deals with cases of '@' in `local-part`, if it is quoted they are legal, if
+    // not then they'll return a `LocalPartInvalidCharacter` error later.
+    //
+    let parsed = parser::parse_address(address, range.clone(), options, collector)?;
+    check_local_part_length(&parsed.local, parsed.local_span.clone(), options, collector)?;
+    check_local_part_policy(&parsed.local, parsed.local_span.clone(), options, collector)?;
+    let measured = check_domain_lengths(
+        &parsed.domain,
+        parsed.domain_span.clone(),
+        options,
+        collector,
+    )?;
+    check_domain_policy(
+        &parsed.domain,
+        parsed.domain_span.clone(),
+        options,
+        collector,
+    )?;
+    let length = parsed.local.len() + 1 + measured;
+    if exceeds(length, options.max_address_length) {
+        collector.fail(Error::AddressTooLong, range)?;
+    }
+    length_warnings(&parsed.local, &parsed.domain, length, collector);
+    collector.finish()?;
+    Ok(parsed)
+}
+
+///
+/// Decode and check the percent-encoded address `to` of a `mailto:` URI, found at `range` of the
+/// input; the parsed components are always owned.
+///
+fn check_percent_encoded<'a>(
+    to: &str,
+    range: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<parser::Parsed<'a>, ParseError> {
+    let decoded = decode(to);
+    let decoded = match std::str::from_utf8(&decoded) {
+        Ok(decoded) => decoded,
+        Err(e) => return Err(collector.abort(Error::InvalidUtf8(e.valid_up_to()), range)),
+    };
+    let options = options.clone().without_trimming().without_mailto_prefix();
+    let parsed = check_address(decoded, &options, collector)?;
+    Ok(parser::Parsed {
+        local: Cow::Owned(parsed.local.into_owned()),
+        local_span: range.clone(),
+        domain: Cow::Owned(parsed.domain.into_owned()),
+        domain_span: range,
+    })
+}
+
+fn parse_local_part<'a>(part: &'a str, options: &Options) -> Result<Cow<'a, str>, ParseError> {
+    let local = parser::parse_local_part(part, options)?;
+    let mut collector = Collector::default();
+    check_local_part_length(&local, 0..part.len(), options, &mut collector)?;
+    check_local_part_policy(&local, 0..part.len(), options, &mut collector)?;
+    Ok(local)
+}
+
+fn parse_domain<'a>(part: &'a str, options: &Options) -> Result<Cow<'a, str>, ParseError> {
+    let domain = parser::parse_domain(part, options)?;
+    let mut collector = Collector::default();
+    check_domain_lengths(&domain, 0..part.len(), options, &mut collector)?;
+    check_domain_policy(&domain, 0..part.len(), options, &mut collector)?;
+    Ok(domain)
+}
+
+fn check_local_part_length(
+    local: &str,
+    span: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<(), ParseError> {
+    if exceeds(local.len(), options.max_local_part_length) {
+        collector.fail(Error::LocalPartTooLong, span.clone())?;
+    }
+    if let Some(max) = options.max_consecutive_tag_separators {
+        let longest_run = local
+            .split(|c| !options.tag_separators.contains(&c))
+            .map(str::len)
+            .max()
+            .unwrap_or_default();
+        if longest_run > max {
+            collector.fail(Error::TooManyTagSeparators, span)?;
+        }
+    }
+    Ok(())
+}
+
+///
+/// Check the length limits on a valid `domain`, returning the measured length of the domain.
+/// Errors are reported against the `span` of the whole domain, as the measured form may not
+/// correspond to the input.
+///
+fn check_domain_lengths(
+    domain: &str,
+    span: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<usize, ParseError> {
+    if domain.starts_with(LBRACKET) {
+        if exceeds(domain.len(), options.max_domain_length) {
+            collector.fail(Error::DomainTooLong, span)?;
+        }
+        return Ok(domain.len());
+    }
+    let measured = measured_domain(domain, options);
+    if exceeds(measured.len(), options.max_domain_length) {
+        collector.fail(Error::DomainTooLong, span.clone())?;
+    }
+    if measured
+        .split(DOT)
+        .any(|sub_domain| exceeds(sub_domain.len(), options.max_sub_domain_length))
+    {
+        collector.fail(Error::SubDomainTooLong, span.clone())?;
+    }
+    let sub_domains = domain.split(DOT).count();
+    if sub_domains < options.minimum_sub_domains {
+        collector.fail(Error::DomainTooFew, span.clone())?;
+    }
+    if exceeds(sub_domains, options.max_sub_domains) {
+        collector.fail(Error::DomainTooMany, span)?;
+    }
+    Ok(measured.len())
+}
+
+fn check_local_part_policy(
+    local: &str,
+    span: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<(), ParseError> {
+    if !options.allow_local_routing {
+        if local.contains(PERCENT_HACK) {
+            collector.fail(Error::PercentHackRouting, span.clone())?;
+        }
+        if local.contains(BANG_PATH) {
+            collector.fail(Error::BangPathRouting, span)?;
+        }
+    }
+    Ok(())
+}
+
+fn check_domain_policy(
+    domain: &str,
+    span: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<(), ParseError> {
+    if !options.allow_internal_domain && is_internal_domain(domain) {
+        collector.fail(Error::InternalDomain, span.clone())?;
+    }
+    if !options.allow_non_global_literal
+        && matches!(parts::domain_part(domain).ip_scope(), Some(scope) if !scope.is_global())
+    {
+        collector.fail(Error::NonGlobalAddressLiteral, span.clone())?;
+    }
+    if let Some(denied) = &options.denied_domains {
+        if !domain.starts_with(LBRACKET) && denied.contains(domain) {
+            collector.fail(Error::DeniedDomain, span)?;
+        }
+    }
+    Ok(())
+}
+
+///
+/// Returns `true` if `domain` is a single label, or is, or ends in, one of `INTERNAL_DOMAINS`.
+///
+fn is_internal_domain(domain: &str) -> bool {
+    if domain.starts_with(LBRACKET) {
+        return false;
+    }
+    let domain = domain.trim_end_matches(DOT).to_lowercase();
+    !domain.contains(DOT)
+        || INTERNAL_DOMAINS.iter().any(|internal| {
+            matches!(domain.strip_suffix(internal), Some(rest) if rest.is_empty() || rest.ends_with(DOT))
+        })
+}
+
+///
+/// Warn about any lengths beyond the RFC 5321 limits, which will only be found when the options
+/// in use have relaxed those limits.
+///
+fn length_warnings(local: &str, domain: &str, length: usize, collector: &mut Collector) {
+    if local.len() > LOCAL_PART_MAX_LENGTH {
+        collector.warn(Warning::LocalPartTooLong);
+    }
+    if domain.len() > DOMAIN_MAX_LENGTH {
+        collector.warn(Warning::DomainTooLong);
+    }
+    if !domain.starts_with(LBRACKET)
+        && domain
+            .split(DOT)
+            .any(|sub_domain| sub_domain.len() > SUB_DOMAIN_MAX_LENGTH)
+    {
+        collector.warn(Warning::SubDomainTooLong);
+    }
+    if length > ADDRESS_MAX_LENGTH {
+        collector.warn(Warning::AddressTooLong);
+    }
+}
+
+#[inline]
+fn exceeds(length: usize, max_length: Option<usize>) -> bool {
+    max_length
+        .map(|max_length| length > max_length)
+        .unwrap_or(false)
+}
+
+///
+/// Returns the form of the domain that length limits are measured against; either the domain
+/// itself, or if requested its A-label form. Domains that cannot be converted are measured
+/// as-is.
+///
+#[allow(unused_variables)]
+fn measured_domain<'a>(part: &'a str, options: &Options) -> Cow<'a, str> {
+    #[cfg(feature = "idna")]
+    {
+        if options.a_label_lengths && !part.is_ascii() {
+            if let Ok(a_label) = idna::domain_to_ascii(part) {
+                return Cow::Owned(a_label);
+            }
+        }
+    }
+    Cow::Borrowed(part)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Unit Tests
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn is_valid(address: &str, test_case: Option<&str>) {
+        if let Some(test_case) = test_case {
+            println!(">> test case: {}", test_case);
+            println!("     <{}>", address);
+        } else {
+            println!(">> <{}>", address);
+        }
+        assert!(EmailAddress::is_valid(address));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_01() {
+        is_valid("simple@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_02() {
+        is_valid("very.common@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_03() {
+        is_valid("disposable.style.email.with+symbol@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_04() {
+        is_valid("other.email-with-hyphen@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_05() {
+        is_valid("fully-qualified-domain@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_06() {
+        is_valid(
+            "user.name+tag+sorting@example.com",
+            Some(" may go to user.name@example.com inbox depending on mail server"),
+        );
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_07() {
+        is_valid("x@example.com", Some("one-letter local-part"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_08() {
+        is_valid("example-indeed@strange-example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_09() {
+        is_valid("admin@mailserver1", Some("local domain name with no TLD, although ICANN highly discourages dotless email addresses"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_10() {
+        is_valid(
+            "example@s.example",
+            Some("see the List of Internet top-level domains"),
+        );
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_11() {
+        is_valid("\" \"@example.org", Some("space between the quotes"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_12() {
+        is_valid("\"john..doe\"@example.org", Some("quoted double dot"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_13() {
+        is_valid(
+            "mailhost!username@example.org",
+            Some("bangified host route used for uucp mailers"),
+        );
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_14() {
+        is_valid(
+            "user%example.com@example.org",
+            Some("% escaped mail route to user@example.com via example.org"),
+        );
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_15() {
+        is_valid("jsmith@[192.168.2.1]", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_16() {
+        is_valid("jsmith@[IPv6:2001:db8::1]", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_17() {
+        is_valid("user+mailbox/department=shipping@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_18() {
+        is_valid("!#$%&'*+-/=?^_`.{|}~@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_19() {
+        // '@' is allowed in a quoted local part. Sorry.
+        is_valid("\"Abc@def\"@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_20() {
+        is_valid("\"Joe.\\\\Blow\"@example.com", None);
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_21() {
+        is_valid("用户@例子.广告", Some("Chinese"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_22() {
+        is_valid("अजय@डाटा.भारत", Some("Hindi"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_23() {
+        is_valid("квіточка@пошта.укр", Some("Ukranian"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_24() {
+        is_valid("θσερ@εχαμπλε.ψομ", Some("Greek"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_25() {
+        is_valid("Dörte@Sörensen.example.com", Some("German"));
+    }
+
+    #[test]
+    fn test_good_examples_from_wikipedia_26() {
+        is_valid("коля@пример.рф", Some("Russian"));
+    }
+
+    // ------------------------------------------------------------------------------------------------
+
+    fn expect(address: &str, error: Error, test_case: Option<&str>) {
+        if let Some(test_case) = test_case {
+            println!(">> test case: {}", test_case);
+            println!("     <{}>, expecting {:?}", address, error);
+        } else {
+            println!(">> <{}>, expecting {:?}", address, error);
+        }
+        assert_eq!(EmailAddress::from_str(address), error.into());
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_00() {
+        expect(
+            "Abc.example.com",
+            Error::MissingSeparator,
+            Some("no @ character"),
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_01() {
+        expect(
+            "A@b@c@example.com",
+            Error::LocalPartInvalidCharacter('@', 1),
+            Some("only one @ is allowed outside quotation marks"),
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_02() {
+        expect("a\"b(c)d,e:f;g<h>i[j\\k]l@example.com",
+            Error::LocalPartInvalidCharacter('"', 1),
+        Some("none of the special characters in this local-part are allowed outside quotation marks")
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_03() {
+        expect(
+            "just\"not\"right@example.com",
+            Error::LocalPartInvalidCharacter('"', 4),
+            Some(
+                "quoted strings must be dot separated or the only element making up the local-part",
+            ),
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_04() {
+        expect("this is\"not\\allowed@example.com",
+            Error::LocalPartInvalidCharacter(' ', 4),
+        Some("spaces, quotes, and backslashes may only exist when within quoted strings and preceded by a backslash")
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_05() {
+        // ()
+        expect("this\\ still\"not\\allowed@example.com",
+            Error::LocalPartInvalidCharacter('\\', 4),
+        Some("even if escaped (preceded by a backslash), spaces, quotes, and backslashes must still be contained by quotes")
+        );
+    }
+
+    #[test]
+    fn test_bad_examples_from_wikipedia_06() {
+        expect(
+            "1234567890123456789012345678901234567890123456789012345678901234+x@example.com",
+            Error::LocalPartTooLong,
+            Some("local part is longer than 64 characters"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_01() {
+        expect(
+            "foo@example.v1234567890123456789012345678901234567890123456789012345678901234v.com",
+            Error::SubDomainTooLong,
+            Some("domain part is longer than 64 characters"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_02() {
+        expect(
+            "@example.com",
+            Error::LocalPartEmpty,
+            Some("local-part is empty"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_03() {
+        expect(
+            "\"\"@example.com",
+            Error::LocalPartEmpty,
+            Some("local-part is empty"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_04() {
+        expect(
+            "simon@example.com.",
+            Error::DomainInvalidCharacter('.', 17),
+            Some("rooted DNS syntax"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_05() {
+        expect("simon@", Error::DomainEmpty, Some("domain is empty"));
+    }
+
+    #[test]
+    fn test_bad_example_06() {
+        expect(
+            &format!("{}@example.com", "ö".repeat(33)),
+            Error::LocalPartTooLong,
+            Some("local part is 33 characters, but 66 octets"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_07() {
+        expect(
+            &format!("simon@{}.com", "ö".repeat(32)),
+            Error::SubDomainTooLong,
+            Some("sub-domain is 32 characters, but 64 octets"),
+        );
+    }
+
+    #[test]
+    fn test_bad_example_08() {
+        expect(
+            &format!(
+                "{}@{}.com",
+                "a".repeat(64),
+                "b".repeat(63) + "." + &"c".repeat(63) + "." + &"d".repeat(60)
+            ),
+            Error::AddressTooLong,
+            Some("local part and domain are within limits, but address is 255 octets"),
+        );
+    }
+
+    #[test]
+    fn test_max_address_length() {
+        let address = "simon@example.com";
+        let options = Options::default().with_max_address_length(16);
+        assert_eq!(
+            EmailAddress::parse_with(address, &options),
+            Error::AddressTooLong.into()
+        );
+        let options = options.with_max_address_length(17);
+        assert!(EmailAddress::parse_with(address, &options).is_ok());
+
+        let address = format!(
+            "{}@{}.com",
+            "a".repeat(64),
+            "b".repeat(63) + "." + &"c".repeat(63) + "." + &"d".repeat(60)
+        );
+        let options = Options::default().without_max_address_length();
+        assert!(EmailAddress::parse_with(&address, &options).is_ok());
+    }
+
+    #[test]
+    fn test_options_utf8() {
+        let options = Options::default().without_utf8();
+        assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("Dörte@example.com", &options),
+            Error::LocalPartInvalidCharacter('ö', 1).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@Sörensen.example.com", &options),
+            Error::DomainInvalidCharacter('ö', 7).into()
+        );
+    }
+
+    #[test]
+    fn test_options_quoted_local_part() {
+        let options = Options::default().without_quoted_local_part();
+        assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("\"john..doe\"@example.org", &options),
+            Error::UnsupportedQuotedLocalPart.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("<\"john\"@example.org>", &options),
+            Error::UnsupportedQuotedLocalPart.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("john.\"doe\"@example.org", &options.clone().with_obsolete()),
+            Error::UnsupportedQuotedLocalPart.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with(
+                "mailto:%22john%20doe%22@example.org",
+                &options.with_mailto_prefix()
+            ),
+            Error::UnsupportedQuotedLocalPart.into()
+        );
+    }
+
+    #[test]
+    fn test_options_local_routing() {
+        let options = Options::default().without_local_routing();
+        assert!(EmailAddress::parse_with("simon+news@example.com", &options).is_ok());
+        assert!(
+            EmailAddress::parse_with("simon%example.org@example.com", &Options::default()).is_ok()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon%example.org@example.com", &options),
+            Error::PercentHackRouting.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("\"simon%example.org\"@example.com", &options),
+            Error::PercentHackRouting.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("host!simon@example.com", &options),
+            Error::BangPathRouting.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_spanned("a!b@example.com", &options).map_err(|e| e.span()),
+            Err(0..3)
+        );
+    }
+
+    #[test]
+    fn test_options_domain_literal() {
+        let options = Options::default().without_domain_literal();
+        assert_eq!(
+            EmailAddress::parse_with("jsmith@[192.168.2.1]", &options),
+            Error::UnsupportedDomainLiteral.into()
+        );
+    }
+
+    #[test]
+    fn test_options_required_tld() {
+        let options = Options::default().with_required_tld();
+        assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+        assert!(EmailAddress::parse_with("jsmith@[192.168.2.1]", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("admin@mailserver1", &options),
+            Error::DomainTooFew.into()
+        );
+    }
+
+    #[test]
+    fn test_options_structural_limits() {
+        let options = Options::default().with_max_sub_domains(3);
+        assert!(EmailAddress::parse_with("simon@mail.example.com", &options).is_ok());
+        assert!(EmailAddress::parse_with("simon@[127.0.0.1]", &options).is_ok());
+        let labels = format!("simon@{}example.com", "a.".repeat(100));
+        assert!(EmailAddress::is_valid(&labels));
+        for address in ["simon@a.mail.example.com", labels.as_str()] {
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::DomainTooMany.into()
+            );
+        }
+        let error = EmailAddress::parse_spanned("simon@a.mail.example.com", &options).unwrap_err();
+        assert_eq!(error.span(), 6..24);
+        assert_eq!(
+            DomainName::parse_with("a.mail.example.com", &options),
+            Err(Error::DomainTooMany)
+        );
+
+        let options = Options::default().with_max_consecutive_tag_separators(2);
+        for address in [
+            "simon@example.com",
+            "simon++news@example.com",
+            "+a+b+c+@example.com",
+        ] {
+            assert!(
+                EmailAddress::parse_with(address, &options).is_ok(),
+                "{}",
+                address
+            );
+        }
+        for address in ["simon+++news@example.com", "\"simon++++\"@example.com"] {
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::TooManyTagSeparators.into(),
+                "{}",
+                address
+            );
+        }
+        let error = EmailAddress::parse_spanned("simon+++news@example.com", &options).unwrap_err();
+        assert_eq!(error.span(), 0..12);
+
+        let options = options.with_tag_separators(&['-', '=']);
+        assert!(EmailAddress::parse_with("simon+++news@example.com", &options).is_ok());
+        for address in ["simon---news@example.com", "simon-=-news@example.com"] {
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::TooManyTagSeparators.into(),
+                "{}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_options_trimming() {
+        for address in [
+            " simon@example.com",
+            "simon@example.com ",
+            "\tsimon@example.com\r\n",
+            "  <simon@example.com>\n",
+        ] {
+            assert!(!EmailAddress::is_valid(address), "{:?}", address);
+            assert_eq!(
+                EmailAddress::parse_trimmed(address).map(|email| email.to_string()),
+                Ok("simon@example.com".to_string()),
+                "{:?}",
+                address
+            );
+        }
+        assert_eq!(
+            EmailAddress::parse_trimmed("  si mon@example.com"),
+            Err(Error::LocalPartInvalidCharacter(' ', 4))
+        );
+        assert_eq!(
+            EmailAddress::parse_trimmed(" \n "),
+            Err(Error::MissingSeparator)
+        );
+        let options = Options::default().with_trimming();
+        let error = EmailAddress::parse_spanned(" simon@exa mple.com ", &options).unwrap_err();
+        assert_eq!(error.error(), &Error::DomainInvalidCharacter(' ', 10));
+        let email = BorrowedEmailAddress::parse_with(" simon@example.com\n", &options).unwrap();
+        assert_eq!(email.as_str(), "simon@example.com");
+        assert!(
+            EmailAddress::parse_with(" simon@example.com", &options.without_trimming()).is_err()
+        );
+    }
+
+    #[test]
+    fn test_options_angle_brackets() {
+        let allowed = Options::default();
+        let forbidden = Options::default().without_angle_brackets();
+        let required = Options::default().with_required_angle_brackets();
+        for options in [&allowed, &required] {
+            let email = EmailAddress::parse_with("<simon@example.com>", options).unwrap();
+            assert_eq!(email.as_str(), "simon@example.com");
+        }
+        for options in [&allowed, &forbidden] {
+            assert!(EmailAddress::parse_with("simon@example.com", options).is_ok());
+        }
+        assert_eq!(
+            EmailAddress::parse_with("<simon@example.com>", &forbidden),
+            Error::LocalPartInvalidCharacter('<', 0).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("<simon@example.com>", &Options::from(Preset::Strict)),
+            Error::LocalPartInvalidCharacter('<', 0).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@example.com", &required),
+            Error::MissingAngleBrackets.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("<simon@example.com", &required),
+            Error::MissingAngleBrackets.into()
+        );
+        let email =
+            EmailAddress::parse_with(" <simon@example.com> ", &required.clone().with_trimming())
+                .unwrap();
+        assert_eq!(email.as_str(), "simon@example.com");
+
+        assert!(Path::parse_with("<simon@example.com>", &required).is_ok());
+        assert!(UserId::parse_with("Simon <simon@example.com>", &required).is_ok());
+        let ident = GitIdent::parse_with("Simon <simon@example.com>", &required).unwrap();
+        assert!(ident.address().is_ok());
+    }
+
+    #[test]
+    fn test_options_mailto_prefix() {
+        let options = Options::default().with_mailto_prefix();
+        for (input, address) in [
+            ("simon@example.com", "simon@example.com"),
+            ("mailto:simon@example.com", "simon@example.com"),
+            ("MailTo:simon@example.com", "simon@example.com"),
+            (
+                "mailto:simon@example.com?subject=Hello%20there",
+                "simon@example.com",
+            ),
+            ("mailto:simon%2Bnews@example.com", "simon+news@example.com"),
+            (
+                "mailto:%22simon%40home%22@example.com",
+                "\"simon@home\"@example.com",
+            ),
+            ("mailto:j%C3%B6ran@example.com", "jöran@example.com"),
+            (
+                "mailto:user%foo.com@example.org",
+                "user%foo.com@example.org",
+            ),
+            ("mailto:<simon@example.com>", "simon@example.com"),
+        ] {
+            let email = EmailAddress::parse_with(input, &options);
+            assert_eq!(
+                email.as_ref().map(EmailAddress::as_str),
+                Ok(address),
+                "{}",
+                input
+            );
+        }
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        assert_eq!(
+            EmailAddress::parse_with(&email.to_uri(), &options),
+            Ok(email)
+        );
+
+        assert_eq!(
+            EmailAddress::parse_with("mailto:si mon@example.com", &options),
+            Err(Error::LocalPartInvalidCharacter(' ', 9))
+        );
+        assert_eq!(
+            EmailAddress::parse_with("mailto:si%20mon@example.com", &options),
+            Err(Error::LocalPartInvalidCharacter(' ', 2))
+        );
+        assert_eq!(
+            EmailAddress::parse_with("mailto:j%F6ran@example.com", &options),
+            Err(Error::InvalidUtf8(1))
+        );
+        assert_eq!(
+            EmailAddress::parse_with(
+                " mailto:simon@example.com\n",
+                &options.clone().with_trimming()
+            )
+            .map(|email| email.to_string()),
+            Ok("simon@example.com".to_string())
+        );
+        let email =
+            BorrowedEmailAddress::parse_with("mailto:simon@example.com?subject=Hi", &options)
+                .unwrap();
+        assert!(email.is_borrowed());
+        let email =
+            BorrowedEmailAddress::parse_with("mailto:simon%2Bnews@example.com", &options).unwrap();
+        assert_eq!(email.as_str(), "simon+news@example.com");
+        assert!(!EmailAddress::is_valid("mailto:simon@example.com"));
+        assert!(EmailAddress::parse_with(
+            "mailto:simon@example.com",
+            &options.without_mailto_prefix()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_original() {
+        let email = EmailAddress::from_str("Simon@Example.COM").unwrap();
+        assert_eq!(email.as_str(), "Simon@Example.COM");
+        assert_eq!(email.original(), "Simon@Example.COM");
+
+        let options = Options::default().with_lowercase_domain();
+        let lowered = EmailAddress::parse_with("Simon@Example.COM", &options).unwrap();
+        assert_eq!(lowered.as_str(), "Simon@example.com");
+        assert_eq!(lowered.domain(), "example.com");
+        assert_eq!(lowered.original(), "Simon@Example.COM");
+        assert_ne!(lowered, email);
+        assert_eq!(
+            lowered,
+            EmailAddress::from_str("Simon@example.com").unwrap()
+        );
+
+        let email = EmailAddress::parse_with("simon@BÜCHER.example", &options).unwrap();
+        assert_eq!(email.domain(), "bücher.example");
+        let email = EmailAddress::parse_with("simon@[IPv6:::1]", &options).unwrap();
+        assert_eq!(email.domain(), "[IPv6:::1]");
+
+        let input = " <simon (work) @example.com> ";
+        let options = Options::from(Profile::Rfc5322).with_trimming();
+        let email = EmailAddress::parse_with(input, &options).unwrap();
+        assert_eq!(email.as_str(), "simon@example.com");
+        assert_eq!(email.original(), input);
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(email);
+        assert!(set.contains(&EmailAddress::from_str("simon@example.com").unwrap()));
+    }
+
+    #[test]
+    fn test_to_lowercase() {
+        for (address, lowercase, including_quoted) in [
+            (
+                "simon@example.com",
+                "simon@example.com",
+                "simon@example.com",
+            ),
+            (
+                "Simon@Example.COM",
+                "simon@example.com",
+                "simon@example.com",
+            ),
+            (
+                "\"Simon\"@Example.COM",
+                "\"Simon\"@example.com",
+                "\"simon\"@example.com",
+            ),
+            (
+                "\"A\\\"B\"@EXAMPLE.com",
+                "\"A\\\"B\"@example.com",
+                "\"a\\\"b\"@example.com",
+            ),
+            (
+                "JÖRAN@BÜCHER.example",
+                "jöran@bücher.example",
+                "jöran@bücher.example",
+            ),
+            (
+                "Simon@[IPv6:ABCD::1]",
+                "simon@[IPv6:ABCD::1]",
+                "simon@[IPv6:ABCD::1]",
+            ),
+        ] {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert_eq!(email.to_lowercase().as_str(), lowercase, "{}", address);
+            assert_eq!(
+                email.to_lowercase_including_quoted().as_str(),
+                including_quoted,
+                "{}",
+                address
+            );
+        }
+        let options = Options::from(Profile::Rfc5322);
+        let email = EmailAddress::parse_with(
+            "Simon.\"A B\".SJ@Example.COM",
+            &options.clone().with_obsolete(),
+        )
+        .unwrap();
+        assert_eq!(
+            email.to_lowercase().as_str(),
+            "simon.\"A B\".sj@example.com"
+        );
+
+        let email = EmailAddress::parse_lowercased("\"Simon\"@Example.COM").unwrap();
+        assert_eq!(email.as_str(), "\"Simon\"@example.com");
+        assert_eq!(email.original(), "\"Simon\"@Example.COM");
+        assert_eq!(
+            EmailAddress::parse_lowercased("Simon"),
+            Err(Error::MissingSeparator)
+        );
+        let email = EmailAddress::parse_lowercased_with("<Simon@Example.COM>", &options).unwrap();
+        assert_eq!(email.as_str(), "simon@example.com");
+        assert_eq!(email.original(), "<Simon@Example.COM>");
+    }
+
+    #[test]
+    fn test_component_display() {
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        assert_eq!(email.to_string(), "simon@example.com");
+        assert_eq!(format!("[{:>20}]", email), "[   simon@example.com]");
+        assert_eq!(format!("[{:<20}]", email), "[simon@example.com   ]");
+        assert_eq!(format!("[{:.5}]", email), "[simon]");
+
+        let local = email.to_local_part();
+        assert_eq!(local.to_string(), "simon");
+        assert_eq!(format!("[{:^7}]", local), "[ simon ]");
+        assert_eq!(
+            format!("{:?}", local),
+            "LocalPart { local: \"simon\", kind: DotAtom }"
+        );
+
+        let domain = email.to_domain();
+        assert_eq!(domain.to_string(), "example.com");
+        assert_eq!(format!("[{:>12}]", domain), "[ example.com]");
+        assert_eq!(
+            format!("{:?}", domain),
+            "Domain { domain: \"example.com\", literal: false }"
+        );
+        let domain = Domain::from_str("[127.0.0.1]").unwrap();
+        assert_eq!(
+            format!("{:?}", domain),
+            "Domain { domain: \"[127.0.0.1]\", literal: true }"
+        );
+        assert_eq!(
+            format!("[{:>12}]", DomainName::from_str("example.com").unwrap()),
+            "[ example.com]"
+        );
+    }
+
+    #[test]
+    fn test_lengths() {
+        for (address, len, chars_len, local_part_len, domain_len) in [
+            ("simon@example.com", 17, 17, 5, 11),
+            ("jöran@example.com", 18, 17, 6, 11),
+            ("用户@例子.广告", 20, 8, 6, 13),
+            ("\"si mon\"@[127.0.0.1]", 20, 20, 8, 11),
+        ] {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert_eq!(
+                (
+                    email.len(),
+                    email.chars_len(),
+                    email.local_part_len(),
+                    email.domain_len()
+                ),
+                (len, chars_len, local_part_len, domain_len),
+                "{}",
+                address
+            );
+            assert_eq!(email.len(), email.as_str().len());
+            assert_eq!(email.local_part_len(), email.local_part().len());
+            assert_eq!(email.domain_len(), email.domain().len());
+        }
+    }
+
+    #[test]
+    fn test_options_length_limits() {
+        let options = Options::default()
+            .with_max_local_part_length(4)
+            .with_max_sub_domain_length(7);
+        assert_eq!(
+            EmailAddress::parse_with("simon@example.com", &options),
+            Error::LocalPartTooLong.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("sim@examples.com", &options),
+            Error::SubDomainTooLong.into()
+        );
+        let options = options.with_max_domain_length(10);
+        assert_eq!(
+            EmailAddress::parse_with("sim@example.com", &options),
+            Error::DomainTooLong.into()
+        );
+        let long_local = format!("{}@example.com", "a".repeat(100));
+        assert!(EmailAddress::parse_with(&long_local, &options.without_length_limits()).is_ok());
+    }
+
+    #[test]
+    fn test_profile_rfc5322() {
+        let options = Options::from(Profile::Rfc5322);
+        let email = EmailAddress::parse_with(
+            "(comment) john (the man) . smith@example.com (me)",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(email.to_string(), "john.smith@example.com".to_string());
+        assert!(EmailAddress::parse_with("\"john\".smith@example.com", &options).is_ok());
+        assert!(EmailAddress::parse_with("simon@(nested (comment))example.com", &options).is_ok());
+        assert!(EmailAddress::parse_with("simon@[ 192.168.2.1 ]", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("simon(unclosed@example.com", &options),
+            Error::InvalidComment.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("Dörte@example.com", &options),
+            Error::LocalPartInvalidCharacter('ö', 1).into()
+        );
+        let long_local = format!("{}@example.com", "a".repeat(100));
+        assert!(EmailAddress::parse_with(&long_local, &options).is_ok());
+    }
+
+    #[test]
+    fn test_profile_rfc5321() {
+        let options = Options::from(Profile::Rfc5321);
+        assert!(EmailAddress::parse_with("simon@example-1.com", &options).is_ok());
+        assert!(EmailAddress::parse_with("\"john..doe\"@example.org", &options).is_ok());
+        assert!(EmailAddress::parse_with("jsmith@[192.168.2.1]", &options).is_ok());
+        assert!(EmailAddress::parse_with("jsmith@[IPv6:2001:db8::1]", &options).is_ok());
+        assert!(EmailAddress::parse_with("jsmith@[x-tag:some-address]", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("simon@-example.com", &options),
+            Error::DomainInvalidCharacter('-', 6).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@exa_mple.com", &options),
+            Error::DomainInvalidCharacter('_', 9).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("jsmith@[192.168.2.256]", &options),
+            Error::InvalidIPAddress.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("jsmith@[IPv6:2001:db8::g]", &options),
+            Error::InvalidIPAddress.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("(comment)simon@example.com", &options),
+            Error::LocalPartInvalidCharacter('(', 0).into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("用户@例子.广告", &options),
+            Error::LocalPartInvalidCharacter('用', 0).into()
+        );
+    }
+
+    #[test]
+    fn test_profile_rfc6531() {
+        let options = Options::from(Profile::Rfc6531);
+        assert!(EmailAddress::parse_with("用户@例子.广告", &options).is_ok());
+        assert!(EmailAddress::parse_with("Dörte@Sörensen.example.com", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("Dörte@Sör_ensen.example.com", &options),
+            Error::DomainInvalidCharacter('_', 11).into()
+        );
+    }
+
+    #[test]
+    fn test_preset_minimal() {
+        let options = Options::from(Preset::Minimal);
+        assert!(EmailAddress::parse_with("any thing@at all", &options).is_ok());
+        assert!(EmailAddress::parse_with("a@b@c", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("Abc.example.com", &options),
+            Error::MissingSeparator.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("@example.com", &options),
+            Error::LocalPartEmpty.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@", &options),
+            Error::DomainEmpty.into()
+        );
+    }
+
+    #[test]
+    fn test_preset_rfc() {
+        assert_eq!(Options::from(Preset::Rfc), Options::default());
+    }
+
+    #[test]
+    fn test_preset_strict() {
+        let options = Options::from(Preset::Strict);
+        assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("admin@mailserver1", &options),
+            Error::DomainTooFew.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("jsmith@[192.168.2.1]", &options),
+            Error::UnsupportedDomainLiteral.into()
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@example-.com", &options),
+            Error::DomainInvalidCharacter('-', 13).into()
+        );
+    }
+
+    fn warnings(address: &str, options: &Options) -> Vec<Warning> {
+        EmailAddress::parse_with_diagnostics(address, options)
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    fn test_warnings() {
+        let options = Options::default();
+        assert!(warnings("simon@example.com", &options).is_empty());
+        assert_eq!(
+            warnings("jsmith@[192.168.2.1]", &options),
+            vec![Warning::DomainLiteral]
+        );
+        assert_eq!(
+            warnings("simon@example.123", &options),
+            vec![Warning::NumericTld]
+        );
+        assert_eq!(
+            warnings("simon@exa_mple.com", &options),
+            vec![Warning::NonLdhDomain]
+        );
+    }
+
+    #[test]
+    fn test_warnings_rfc5322() {
+        let options = Options::from(Profile::Rfc5322);
+        assert_eq!(
+            warnings("(comment)simon@example.com", &options),
+            vec![Warning::Comment]
+        );
+        assert_eq!(
+            warnings("simon @example.com", &options),
+            vec![Warning::FoldingWhiteSpace]
+        );
+        assert_eq!(
+            warnings("\"john\".smith@example.com", &options),
+            vec![Warning::QuotedLocalPart, Warning::ObsoleteSyntax]
+        );
+        assert_eq!(
+            warnings("john . smith@example . com", &options),
+            vec![Warning::FoldingWhiteSpace, Warning::ObsoleteSyntax]
+        );
+        assert_eq!(
+            warnings(&format!("{}@example.com", "a".repeat(65)), &options),
+            vec![Warning::LocalPartTooLong]
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(EmailAddress::validate("simon@example.com").is_ok());
+        assert_eq!(
+            EmailAddress::validate("simon"),
+            Err(vec![Error::MissingSeparator])
+        );
+        assert_eq!(
+            EmailAddress::validate("a\"b(c)d,e:f;g<h>i[j\\k]l@example.com"),
+            Err("a\"b(c)d,e:f;g<h>i[j\\k]l"
+                .char_indices()
+                .filter(|(i, _)| i % 2 == 1)
+                .map(|(i, c)| Error::LocalPartInvalidCharacter(c, i))
+                .collect())
+        );
+        assert_eq!(
+            EmailAddress::validate(&format!("{}@example..com", "a".repeat(65))),
+            Err(vec![
+                Error::DomainInvalidCharacter('.', 74),
+                Error::LocalPartTooLong
+            ])
+        );
+        assert_eq!(
+            EmailAddress::validate_with(
+                "\"simon\"@[example]",
+                &Options::default()
+                    .without_quoted_local_part()
+                    .without_domain_literal()
+            ),
+            Err(vec![
+                Error::UnsupportedQuotedLocalPart,
+                Error::UnsupportedDomainLiteral
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_character_display() {
+        let error = EmailAddress::from_str("simon@exam<ple.com").unwrap_err();
+        assert_eq!(error, Error::DomainInvalidCharacter('<', 10));
+        assert_eq!(
+            error.to_string(),
+            "Invalid character '<' in the domain at position 10."
+        );
+    }
+
+    #[test]
+    fn test_invalid_character_context() {
+        assert_eq!(
+            EmailAddress::from_str("si<mon@example.com"),
+            Error::LocalPartInvalidCharacter('<', 2).into()
+        );
+        assert_eq!(
+            EmailAddress::from_str("\"si\\\u{7}mon\"@example.com"),
+            Error::QuotedStringInvalidCharacter('\u{7}', 4).into()
+        );
+        assert_eq!(
+            EmailAddress::from_str("simon@exam<ple.com"),
+            Error::DomainInvalidCharacter('<', 10).into()
+        );
+        assert_eq!(
+            EmailAddress::from_str("simon@[exam[ple]"),
+            Error::DomainLiteralInvalidCharacter('[', 11).into()
+        );
+        assert_eq!(
+            EmailAddress::from_str("simon@[example.com"),
+            Error::DomainLiteralInvalidCharacter('[', 6).into()
+        );
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let options = Options::from(Profile::Rfc5322);
+        let diagnose = |address| EmailAddress::diagnose(address, &options);
+        assert_eq!(diagnose("simon@example.com"), Diagnostic::Valid);
+        assert_eq!(diagnose("simon@localhost"), Diagnostic::Rfc5321Tld);
+        assert_eq!(diagnose("simon@example.123"), Diagnostic::Rfc5321TldNumeric);
+        assert_eq!(
+            diagnose("simon@[192.168.2.1]"),
+            Diagnostic::Rfc5321AddressLiteral
+        );
+        assert_eq!(
+            diagnose("(comment)simon@example.com"),
+            Diagnostic::CfwsComment
+        );
+        assert_eq!(diagnose("simon@exa_mple.com"), Diagnostic::Rfc5322Domain);
+        assert_eq!(diagnose("simon"), Diagnostic::NoDomain);
+        assert_eq!(
+            diagnose("\"simon@example.com"),
+            Diagnostic::UnclosedQuotedString
+        );
+        assert_eq!(diagnose("si,mon@example.com"), Diagnostic::ExpectingAtext);
+        assert_eq!(
+            diagnose("\"simon\"(comment)@localhost"),
+            Diagnostic::CfwsComment
+        );
+
+        let options = Options::default()
+            .without_quoted_local_part()
+            .without_domain_literal()
+            .with_required_tld();
+        for (options, address, diagnostic) in [
+            (
+                &options,
+                "\"simon\"@example.com",
+                Diagnostic::QuotedStringNotAllowed,
+            ),
+            (
+                &options,
+                "simon@[192.168.2.1]",
+                Diagnostic::DomainLiteralNotAllowed,
+            ),
+            (&options, "simon@localhost", Diagnostic::TldRequired),
+            (
+                &Options::default().without_internal_domains(),
+                "simon@localhost",
+                Diagnostic::InternalDomain,
+            ),
+            (
+                &Options::default()
+                    .with_denied_domains(DomainList::from_str("example.org").unwrap()),
+                "simon@mail.example.org",
+                Diagnostic::DeniedDomain,
+            ),
+            (
+                &Options::default().without_non_global_address_literals(),
+                "simon@[127.0.0.1]",
+                Diagnostic::NonGlobalAddressLiteral,
+            ),
+            (
+                &Options::default().with_max_consecutive_tag_separators(1),
+                "simon++news@example.com",
+                Diagnostic::TooManyTagSeparators,
+            ),
+            (
+                &Options::default().without_local_routing(),
+                "simon%example.org@example.com",
+                Diagnostic::LocalPartRouting,
+            ),
+            (
+                &Options::default().without_local_routing(),
+                "host!simon@relay.example",
+                Diagnostic::LocalPartRouting,
+            ),
+            (
+                &Options::default().with_max_sub_domains(2),
+                "simon@mail.example.com",
+                Diagnostic::TooManyLabels,
+            ),
+        ] {
+            assert!(EmailAddress::parse_with(address, options).is_err());
+            let found = EmailAddress::diagnose(address, options);
+            assert_eq!(found, diagnostic, "{:?}", address);
+            assert!(found.is_error(), "{:?}", address);
+        }
+    }
+
+    #[test]
+    fn test_diagnostic_codes() {
+        assert_eq!(Diagnostic::from_code(0), Some(Diagnostic::Valid));
+        assert_eq!(Diagnostic::from_code(131), Some(Diagnostic::NoDomain));
+        assert_eq!(Diagnostic::from_code(1), None);
+        assert_eq!(Diagnostic::Rfc5321Tld.category(), Category::Rfc5321);
+        assert_eq!(Diagnostic::CfwsFws.category().code(), 31);
+        assert_eq!(Diagnostic::Rfc5322TooLong.category(), Category::Rfc5322);
+        assert!(Diagnostic::Rfc5322TooLong.is_error());
+        assert!(!Diagnostic::DeprecatedLocalPart.is_error());
+        for code in 0..=u8::MAX {
+            if let Some(diagnostic) = Diagnostic::from_code(code) {
+                assert_eq!(diagnostic.code(), code);
+                assert!(diagnostic.code() <= diagnostic.category().code());
+            }
+        }
+    }
+
+    #[cfg(feature = "miette")]
+    #[test]
+    fn test_miette_diagnostic() {
+        use miette::Diagnostic as _;
+        let error =
+            EmailAddress::parse_spanned("si,mon@example.com", &Options::default()).unwrap_err();
+        assert_eq!(
+            error.code().unwrap().to_string(),
+            "email_address::ExpectingAtext"
+        );
+        assert!(error.help().is_some());
+        let labels: Vec<miette::LabeledSpan> = error.labels().unwrap().collect();
+        assert_eq!(labels.len(), 1);
+        assert_eq!(labels[0].offset(), 2);
+        assert_eq!(labels[0].len(), 1);
+        assert_eq!(
+            miette::Diagnostic::code(&Error::MissingSeparator)
+                .unwrap()
+                .to_string(),
+            "email_address::NoDomain"
+        );
+    }
+
+    fn fix(address: &str, options: &Options) -> Option<String> {
+        let error = EmailAddress::parse_spanned(address, options).unwrap_err();
+        error
+            .suggestions()
+            .first()
+            .map(|suggestion| suggestion.apply(address))
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let options = Options::default();
+        assert_eq!(
+            fix("simon@example.com.", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("simon@example..com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix(".simon@example.com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("simon@example,com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("simon@@example.com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("simon@exa mple.com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("<simon@example.com", &options).as_deref(),
+            Some("simon@example.com")
+        );
+        assert_eq!(
+            fix("\"simon@example.com", &options).as_deref(),
+            Some("\"simon\"@example.com")
+        );
+        assert_eq!(
+            fix(
+                "simon(comment@example.com",
+                &Options::from(Profile::Rfc5322)
+            )
+            .as_deref(),
+            Some("simon(comment)@example.com")
+        );
+        assert_eq!(fix("simon", &options), None);
+        assert_eq!(fix("si%mon@exa[mple.com", &options), None);
+    }
+
+    fn suggest(address: &str) -> Option<&'static str> {
+        EmailAddress::from_str(address).unwrap().suggest_domain()
+    }
+
+    #[test]
+    fn test_suggest_domain() {
+        assert_eq!(suggest("simon@gmial.com"), Some("gmail.com"));
+        assert_eq!(suggest("simon@gmail.co"), Some("gmail.com"));
+        assert_eq!(suggest("simon@gnail.com"), Some("gmail.com"));
+        assert_eq!(suggest("simon@hotmial.com"), Some("hotmail.com"));
+        assert_eq!(suggest("simon@outlok.com"), Some("outlook.com"));
+        assert_eq!(suggest("simon@yaho.com"), Some("yahoo.com"));
+        assert_eq!(suggest("simon@GMIAL.COM"), Some("gmail.com"));
+        assert_eq!(suggest("simon@gmail.com"), None);
+        assert_eq!(suggest("simon@GMail.com"), None);
+        assert_eq!(suggest("simon@example.com"), None);
+        assert_eq!(suggest("simon@ox.ac.uk"), None);
+        assert_eq!(suggest("simon@protonmial.con"), Some("protonmail.com"));
+        assert_eq!(suggest("simon@[192.168.2.1]"), None);
+    }
+
+    fn suggest_tld(address: &str) -> Option<String> {
+        EmailAddress::from_str(address).unwrap().suggest_tld()
+    }
+
+    #[test]
+    fn test_suggest_tld() {
+        assert_eq!(
+            suggest_tld("simon@example.con").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            suggest_tld("simon@example.cmo").as_deref(),
+            Some("example.com")
+        );
+        assert_eq!(
+            suggest_tld("simon@example.ogr").as_deref(),
+            Some("example.org")
+        );
+        assert_eq!(
+            suggest_tld("simon@example.nte").as_deref(),
+            Some("example.net")
+        );
+        assert_eq!(
+            suggest_tld("simon@Example.CON").as_deref(),
+            Some("Example.com")
+        );
+        assert_eq!(
+            suggest_tld("simon@example.couk").as_deref(),
+            Some("example.co.uk")
+        );
+        assert_eq!(
+            suggest_tld("simon@example.comau").as_deref(),
+            Some("example.com.au")
+        );
+        assert_eq!(suggest_tld("simon@example.com"), None);
+        assert_eq!(suggest_tld("simon@example.CO.UK"), None);
+        assert_eq!(suggest_tld("simon@example.museum"), None);
+        assert_eq!(suggest_tld("simon@example.xn--p1ai"), None);
+        assert_eq!(suggest_tld("simon@localhost"), None);
+        assert_eq!(suggest_tld("simon@[192.168.2.1]"), None);
+        assert_eq!(suggest_tld("simon@example.qqqqqq"), None);
+    }
+
+    #[test]
+    fn test_single_buffer() {
+        let email = EmailAddress::from_str("<\"a@b\"@example.com>").unwrap();
+        assert_eq!(email.as_str(), "\"a@b\"@example.com");
+        assert_eq!(email.local_part(), "\"a@b\"");
+        assert_eq!(email.domain(), "example.com");
+        assert_eq!(
+            format!("{:?}", email),
+            "EmailAddress { local: \"\\\"a@b\\\"\", domain: \"example.com\" }"
+        );
+        assert_eq!(String::from(email.clone()), email.to_string());
+        assert_eq!(email.as_ref(), email.as_str());
+    }
+
+    #[test]
+    fn test_shared_email_address() {
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        let shared = SharedEmailAddress::from(email.clone());
+        assert_eq!(shared.as_str(), "simon@example.com");
+        assert_eq!(shared.local_part(), "simon");
+        assert_eq!(shared.domain(), "example.com");
+        assert_eq!(shared.to_string(), email.to_string());
+        assert_eq!(EmailAddress::from(&shared), email);
+        assert_eq!(
+            format!("{:?}", shared),
+            "SharedEmailAddress { local: \"simon\", domain: \"example.com\" }"
+        );
+        let clones: Vec<SharedEmailAddress> = (0..100).map(|_| shared.clone()).collect();
+        assert_eq!(allocations(|| shared.clone() == clones[99]), 0);
+        let handle = std::thread::spawn(move || clones[0].domain().to_string());
+        assert_eq!(handle.join().unwrap(), "example.com");
+        assert_eq!(
+            SharedEmailAddress::from_str("simon"),
+            Err(Error::MissingSeparator)
+        );
+    }
+
+    #[test]
+    fn test_parse_bytes() {
+        assert_eq!(
+            EmailAddress::parse_bytes(b"simon@example.com"),
+            EmailAddress::from_str("simon@example.com")
+        );
+        assert_eq!(
+            EmailAddress::parse_bytes("simon@bücher.example".as_bytes())
+                .unwrap()
+                .domain(),
+            "bücher.example"
+        );
+        assert_eq!(
+            EmailAddress::parse_bytes("😀@example.com".as_bytes())
+                .unwrap()
+                .local_part(),
+            "😀"
+        );
+        assert_eq!(
+            EmailAddress::parse_bytes(b"simon@exa mple.com"),
+            Err(Error::DomainInvalidCharacter(' ', 9))
+        );
+        assert_eq!(
+            EmailAddress::parse_bytes_with(b"simon@example.com", &Options::from(Preset::Strict))
+                .unwrap()
+                .as_str(),
+            "simon@example.com"
+        );
+        // A lone continuation byte, an overlong encoding, a surrogate, a code point above
+        // U+10FFFF, and a truncated sequence.
+        for bytes in [
+            &b"si\x80mon@example.com"[..],
+            b"si\xC0\xAFmon@example.com",
+            b"si\xED\xA0\x80mon@example.com",
+            b"si\xF4\x90\x80\x80mon@example.com",
+            b"simon@example.com\xE2\x82",
+        ] {
+            let index = std::str::from_utf8(bytes).unwrap_err().valid_up_to();
+            assert_eq!(
+                EmailAddress::parse_bytes(bytes),
+                Err(Error::InvalidUtf8(index))
+            );
+        }
+    }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_validate_stream() {
+        use futures::executor::{LocalPool, ThreadPool};
+        use futures::{stream, StreamExt};
+        let pool = ThreadPool::new().unwrap();
+        let addresses = stream::iter(vec!["simon@example.com", "simon", "jane@example.org"]);
+        let results: Vec<(usize, Result<EmailAddress, Error>)> = futures::executor::block_on(
+            EmailAddress::validate_stream(addresses, &Options::default(), &pool, 2).collect(),
+        );
+        assert_eq!(
+            results,
+            EmailAddress::validate_bulk(
+                vec!["simon@example.com", "simon", "jane@example.org"],
+                &Options::default()
+            )
+        );
+        // The parses of a pool that is dropped cannot be spawned, so are run by the stream.
+        let mut pool = LocalPool::new();
+        let spawner = pool.spawner();
+        let results: Vec<(usize, Result<EmailAddress, Error>)> = pool.run_until(
+            EmailAddress::validate_stream(
+                stream::iter(vec!["simon@example.com"]),
+                &Options::default(),
+                spawner.clone(),
+                0,
+            )
+            .collect(),
+        );
+        assert_eq!(results.len(), 1);
+        drop(pool);
+        let results: Vec<(usize, Result<EmailAddress, Error>)> = futures::executor::block_on(
+            EmailAddress::validate_stream(
+                stream::iter(vec!["simon"]),
+                &Options::default(),
+                spawner,
+                1,
+            )
+            .collect(),
+        );
+        assert_eq!(results, vec![(0, Err(Error::MissingSeparator))]);
+    }
+
+    #[cfg(feature = "dns")]
+    #[test]
+    fn test_dns_check() {
+        let mx = |records: &[(u16, &str)]| {
+            records
+                .iter()
+                .map(|(preference, exchange)| (*preference, exchange.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            dns::classify(
+                mx(&[(20, "mx2.example.com."), (10, "mx1.example.com.")]),
+                true
+            ),
+            DnsCheck::Mx(vec![
+                "mx1.example.com".to_string(),
+                "mx2.example.com".to_string()
+            ])
+        );
+        assert_eq!(dns::classify(mx(&[(0, ".")]), true), DnsCheck::NullMx);
+        assert_eq!(dns::classify(mx(&[]), true), DnsCheck::ImplicitMx);
+        assert_eq!(dns::classify(mx(&[]), false), DnsCheck::NoRecords);
+        assert!(DnsCheck::Mx(vec!["mx.example.com".to_string()]).accepts_mail());
+        assert!(DnsCheck::ImplicitMx.accepts_mail());
+        assert!(DnsCheck::AddressLiteral.accepts_mail());
+        assert!(!DnsCheck::NullMx.accepts_mail());
+        assert!(!DnsCheck::NoRecords.accepts_mail());
+        assert_eq!(
+            Diagnostic::from(&DnsCheck::ImplicitMx),
+            Diagnostic::DnsNoMxRecord
+        );
+        assert_eq!(Diagnostic::from(&DnsCheck::NullMx), Diagnostic::DnsNoRecord);
+    }
+
+    #[cfg(feature = "smtp")]
+    #[test]
+    fn test_smtp_callout() {
+        use std::io::{BufRead, BufReader, Write};
+        assert_eq!(smtp::parse_reply_line("250 OK"), Some((250, true, "OK")));
+        assert_eq!(
+            smtp::parse_reply_line("250-mx.example.com"),
+            Some((250, false, "mx.example.com"))
+        );
+        assert_eq!(smtp::parse_reply_line("221"), Some((221, true, "")));
+        assert_eq!(smtp::parse_reply_line("25O OK"), None);
+        assert_eq!(smtp::parse_reply_line("250:OK"), None);
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            for _ in 0..5 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut stream = stream;
+                stream.write_all(b"220 mx.example.com ESMTP\r\n").unwrap();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 0 {
+                    let reply: &[u8] = match line.trim_end() {
+                        "EHLO legacy.example.net" => b"250-mx.example.com\r\n250 SIZE 1000000\r\n",
+                        command if command.starts_with("EHLO") => {
+                            b"250-mx.example.com\r\n250-SIZE 1000000\r\n250 SMTPUTF8\r\n"
+                        }
+                        "MAIL FROM:<>" | "MAIL FROM:<> SMTPUTF8" => b"250 2.1.0 OK\r\n",
+                        "RCPT TO:<jöran@[127.0.0.1]>" => b"250 2.1.5 OK\r\n",
+                        "RCPT TO:<simon@[127.0.0.1]>" => b"250 2.1.5 OK\r\n",
+                        "RCPT TO:<jane@[127.0.0.1]>" => b"451 4.7.1 Greylisted, try again\r\n",
+                        "QUIT" => b"221 2.0.0 Bye\r\n",
+                        _ => b"550 5.1.1 No such user\r\n",
+                    };
+                    stream.write_all(reply).unwrap();
+                    line.clear();
+                }
+            }
+        });
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let callout = Callout::new("verifier.example.net").with_port(port);
+        let verify = |address| {
+            let email = EmailAddress::from_str(address).unwrap();
+            runtime.block_on(callout.verify(&email))
+        };
+        assert_eq!(verify("simon@[127.0.0.1]"), CalloutResult::Accepted);
+        match verify("jane@[127.0.0.1]") {
+            CalloutResult::Greylisted(reply) => assert_eq!(reply.code(), 451),
+            result => panic!("expected greylisting, not {:?}", result),
+        }
+        match verify("nobody@[127.0.0.1]") {
+            CalloutResult::Rejected(reply) => {
+                assert_eq!(reply.code(), 550);
+                assert_eq!(reply.text(), "5.1.1 No such user");
+            }
+            result => panic!("expected rejection, not {:?}", result),
+        }
+        assert_eq!(verify("jöran@[127.0.0.1]"), CalloutResult::Accepted);
+        let legacy = Callout::new("legacy.example.net").with_port(port);
+        let email = EmailAddress::from_str("jöran@[127.0.0.1]").unwrap();
+        assert_eq!(
+            runtime.block_on(legacy.verify(&email)),
+            CalloutResult::Unknown("the server does not support SMTPUTF8".to_string())
+        );
+        server.join().unwrap();
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let long_line = format!("220 {}\r\n", "x".repeat(600));
+            let many_lines = "220-mx.example.com\r\n".repeat(100);
+            for greeting in [long_line, many_lines] {
+                let (mut stream, _) = listener.accept().unwrap();
+                // The client may close the connection before it is all written.
+                let _ = stream.write_all(greeting.as_bytes());
+            }
+        });
+        let callout = Callout::new("verifier.example.net").with_port(port);
+        let email = EmailAddress::from_str("simon@[127.0.0.1]").unwrap();
+        assert_eq!(
+            runtime.block_on(callout.verify(&email)),
+            CalloutResult::Unknown("a reply line is longer than 512 octets".to_string())
+        );
+        assert_eq!(
+            runtime.block_on(callout.verify(&email)),
+            CalloutResult::Unknown("a reply is longer than 32 lines".to_string())
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_verifier() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let verifier = Verifier::new()
+            .with_disposable_domains(
+                DomainList::default().with_domain("mailinator.com"),
+                Policy::Warn,
+            )
+            .with_role_local_parts(["Sales", "support"], Policy::Reject);
+
+        let report = runtime.block_on(verifier.verify("simon@example.com"));
+        assert_eq!(report.verdict(), Verdict::Valid);
+        assert!(report.is_valid());
+        assert_eq!(report.score(), 100);
+        assert!(report.reasons().is_empty());
+        assert_eq!(
+            report
+                .checks()
+                .iter()
+                .map(CheckReport::check)
+                .collect::<Vec<_>>(),
+            vec![Check::Syntax, Check::Disposable, Check::Role]
+        );
+        assert_eq!(report.email().unwrap().as_str(), "simon@example.com");
+
+        let report = runtime.block_on(verifier.verify("simon"));
+        assert_eq!(report.verdict(), Verdict::Invalid);
+        assert_eq!(report.checks().len(), 1);
+        assert_eq!(
+            report.status(Check::Syntax),
+            Some(&CheckStatus::Failed(Error::MissingSeparator.to_string()))
+        );
+        assert_eq!(report.email(), None);
+        assert_eq!(report.reasons(), &[Reason::InvalidSyntax]);
+        assert_eq!(report.score(), 0);
+
+        let report = runtime.block_on(verifier.verify("simon@sub.mailinator.com"));
+        assert_eq!(report.verdict(), Verdict::Risky);
+        assert!(matches!(
+            report.status(Check::Disposable),
+            Some(CheckStatus::Warning(_))
+        ));
+
+        let report = runtime.block_on(verifier.verify("SALES+leads@mailinator.com"));
+        assert_eq!(report.verdict(), Verdict::Invalid);
+        assert_eq!(report.reasons(), &[Reason::Disposable, Reason::RoleAccount]);
+        assert_eq!(report.score(), 20);
+        assert_eq!(
+            report
+                .reasons()
+                .iter()
+                .map(Reason::code)
+                .collect::<Vec<_>>(),
+            vec!["disposable", "role_account"]
+        );
+        assert!(matches!(
+            report.status(Check::Role),
+            Some(CheckStatus::Failed(_))
+        ));
+
+        let verifier = Verifier::new().with_role_accounts(Policy::Warn);
+        let report = runtime.block_on(verifier.verify("postmaster@example.com"));
+        assert_eq!(report.verdict(), Verdict::Risky);
+        assert_eq!(
+            report.to_string(),
+            "postmaster@example.com: risky\n  syntax: passed\n  role: warning: postmaster is a role account"
+        );
+    }
+
+    #[cfg(feature = "lettre")]
+    #[test]
+    fn test_lettre_conversions() {
+        use std::convert::TryFrom;
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        let address = lettre::Address::try_from(&email).unwrap();
+        assert_eq!(address.user(), "simon");
+        assert_eq!(address.domain(), "example.com");
+        assert_eq!(EmailAddress::try_from(address).unwrap(), email);
+
+        let mailbox = lettre::message::Mailbox::try_from(email.clone()).unwrap();
+        assert_eq!(mailbox.name, None);
+        assert_eq!(mailbox.to_string(), "simon@example.com");
+        assert_eq!(
+            email.to_mailbox("Simon Smith").unwrap().to_string(),
+            "Simon Smith <simon@example.com>"
+        );
+
+        let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+        assert!(lettre::Address::try_from(email).is_ok());
+        let email = EmailAddress::from_str("\"simon smith\"@example.com").unwrap();
+        let address = lettre::Address::try_from(&email).unwrap();
+        assert_eq!(address.user(), "\"simon smith\"");
+        assert_eq!(EmailAddress::try_from(&address).unwrap(), email);
+    }
+
+    #[cfg(feature = "mail-parser")]
+    #[test]
+    fn test_mail_parser_conversions() {
+        use std::convert::TryFrom;
+        let message = mail_parser::MessageParser::default()
+            .parse(b"From: Simon <simon@example.com>\r\nTo: jane\r\n\r\nHello\r\n".as_slice())
+            .unwrap();
+        let from = message.from().unwrap().first().unwrap();
+        let email = EmailAddress::try_from(from).unwrap();
+        assert_eq!(email.as_str(), "simon@example.com");
+        assert_eq!(
+            mail_parser::Addr::from(&email).address(),
+            Some("simon@example.com")
+        );
+        let to = message.to().unwrap().first().unwrap();
+        assert_eq!(EmailAddress::try_from(to), Err(Error::MissingSeparator));
+    }
+
+    #[cfg(feature = "mail-builder")]
+    #[test]
+    fn test_mail_builder_conversions() {
+        use mail_builder::headers::address::{Address, EmailAddress as BuilderEmailAddress};
+        use std::convert::TryFrom;
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        let address = BuilderEmailAddress::from(&email);
+        assert_eq!(address.email, "simon@example.com");
+        assert_eq!(EmailAddress::try_from(&address).unwrap(), email);
+        let message = mail_builder::MessageBuilder::new()
+            .from(Address::from(&email))
+            .to(Address::from(&email))
+            .subject("Hello")
+            .text_body("Hello")
+            .write_to_string()
+            .unwrap();
+        assert!(message.contains("From: <simon@example.com>"));
+    }
+
+    #[cfg(feature = "addr")]
+    #[test]
+    fn test_addr_conversions() {
+        use std::convert::TryFrom;
+        let email = EmailAddress::from_str("simon@mail.example.co.uk").unwrap();
+        let name = addr::domain::Name::try_from(&email).unwrap();
+        assert_eq!(name.root(), Some("example.co.uk"));
+        assert_eq!(name.suffix(), "co.uk");
+        assert_eq!(
+            DomainName::try_from(name).unwrap().as_ref(),
+            "mail.example.co.uk"
+        );
+        let address = addr::email::Address::try_from(&email).unwrap();
+        assert_eq!(address.user(), "simon");
+        assert_eq!(EmailAddress::try_from(address).unwrap(), email);
+
+        let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+        assert!(addr::domain::Name::try_from(&email).is_err());
+    }
+
+    #[test]
+    fn test_x509_profile() {
+        let options = Options::from(Profile::X509);
+        assert!(EmailAddress::parse_with("simon@example.com", &options).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with("\"simon\"@example.com", &options),
+            Err(Error::UnsupportedQuotedLocalPart)
+        );
+        assert_eq!(
+            EmailAddress::parse_with("simon@[192.168.2.1]", &options),
+            Err(Error::UnsupportedDomainLiteral)
+        );
+        assert!(EmailAddress::parse_with("用户@example.com", &options).is_err());
+        assert!(EmailAddress::parse_with("simon@exa_mple.com", &options).is_err());
+
+        let email = EmailAddress::from_str("simon@mail.example.com").unwrap();
+        assert!(email.matches_name_constraint("mail.EXAMPLE.com"));
+        assert!(email.matches_name_constraint(".com"));
+        assert!(!email.matches_name_constraint("ail.example.com"));
+        assert!(!email.matches_name_constraint(".ail.example.com"));
+        assert!(!email.matches_name_constraint("."));
+        assert!(!email.matches_name_constraint("jane@mail.example.com"));
+        let literal = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+        assert!(!literal.matches_name_constraint("[192.168.2.1]"));
+        let email = EmailAddress::from_str("x@öa").unwrap();
+        assert!(!email.matches_name_constraint(".a"));
+        let email = EmailAddress::from_str("x@mail.bücher.example").unwrap();
+        assert!(email.matches_name_constraint(".bücher.example"));
+        assert!(!email.matches_name_constraint(".cher.example"));
+    }
+
+    #[test]
+    fn test_openpgp_user_id() {
+        let user_id = |s| UserId::from_str(s).unwrap();
+        let parts = |user_id: &UserId| {
+            (
+                user_id.name().map(str::to_string),
+                user_id.comment().map(str::to_string),
+                user_id.email().to_string(),
+            )
+        };
+        for (input, name, comment, email) in [
+            (
+                "Simon Johnston (work) <simon@example.com>",
+                Some("Simon Johnston"),
+                Some("work"),
+                "simon@example.com",
+            ),
+            (
+                "Simon <simon@example.com>",
+                Some("Simon"),
+                None,
+                "simon@example.com",
+            ),
+            ("<simon@example.com>", None, None, "simon@example.com"),
+            ("  simon@example.com ", None, None, "simon@example.com"),
+            (
+                "\"Johnston, Simon\" (key (old)) <simon@example.com>",
+                Some("Johnston, Simon"),
+                Some("key (old)"),
+                "simon@example.com",
+            ),
+            (
+                "Simon (a) (b) <simon@example.com>",
+                Some("Simon (a)"),
+                Some("b"),
+                "simon@example.com",
+            ),
+            (
+                "Simon () <simon@example.com>",
+                Some("Simon"),
+                None,
+                "simon@example.com",
+            ),
+        ] {
+            assert_eq!(
+                parts(&user_id(input)),
+                (
+                    name.map(str::to_string),
+                    comment.map(str::to_string),
+                    email.to_string()
+                ),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            user_id("Simon Johnston (work) <simon@example.com>").to_string(),
+            "Simon Johnston (work) <simon@example.com>"
+        );
+        assert_eq!(
+            UserId::from_str("Simon Johnston"),
+            Err(Error::MissingSeparator)
+        );
+        assert!(UserId::from_str("Simon <simon@exa mple.com>").is_err());
+        assert_eq!(
+            EmailAddress::from(user_id("Simon <simon@example.com>")).as_str(),
+            "simon@example.com"
+        );
+    }
+
+    #[test]
+    fn test_git_ident() {
+        let ident =
+            GitIdent::parse("Simon Johnston <simon@example.com> 1700000000 -0530\n").unwrap();
+        assert_eq!(ident.name(), "Simon Johnston");
+        assert_eq!(ident.time(), Some((1_700_000_000, -330)));
+
+        let ident = GitIdent::parse("Simon Johnston <simon@example.com>").unwrap();
+        assert_eq!(ident.name(), "Simon Johnston");
+        assert_eq!(ident.email(), "simon@example.com");
+        assert_eq!(ident.address().unwrap().as_str(), "simon@example.com");
+        assert_eq!(ident.time(), None);
+        assert!(!ident.has_placeholder_domain());
+
+        let ident = GitIdent::parse(" <> 1700000000 +0000").unwrap();
+        assert_eq!(ident.name(), "");
+        assert_eq!(ident.email(), "");
+        assert!(ident.address().is_err());
+        assert_eq!(ident.time(), Some((1_700_000_000, 0)));
+
+        let ident = GitIdent::parse("Simon <simon at example dot com> soon").unwrap();
+        assert_eq!(ident.address(), Err(&Error::MissingSeparator));
+        assert_eq!(ident.time(), None);
+
+        assert_eq!(GitIdent::parse("Simon Johnston"), None);
+        assert_eq!(GitIdent::parse("Simon <simon@example.com"), None);
+    }
+
+    #[test]
+    fn test_message_id() {
+        for (input, left, right) in [
+            ("<1234@example.com>", "1234", "example.com"),
+            (" <a.b.c@[127.0.0.1]>\r\n", "a.b.c", "[127.0.0.1]"),
+            ("<x@[]>", "x", "[]"),
+            ("<用户@例子.广告>", "用户", "例子.广告"),
+            ("<a+b=c@localhost>", "a+b=c", "localhost"),
+        ] {
+            let id = MessageId::from_str(input).unwrap();
+            assert_eq!((id.id_left(), id.id_right()), (left, right), "{}", input);
+        }
+        for (input, error) in [
+            ("1234@example.com", Error::MissingAngleBrackets),
+            ("<1234@example.com", Error::MissingAngleBrackets),
+            ("<1234>", Error::MissingSeparator),
+            ("<@example.com>", Error::LocalPartEmpty),
+            ("<1234@>", Error::DomainEmpty),
+            (
+                "<12 34@example.com>",
+                Error::LocalPartInvalidCharacter(' ', 3),
+            ),
+            (
+                "<.1234@example.com>",
+                Error::LocalPartInvalidCharacter('.', 1),
+            ),
+            (
+                "<12..34@example.com>",
+                Error::LocalPartInvalidCharacter('.', 4),
+            ),
+            (
+                "<\"1234\"@example.com>",
+                Error::LocalPartInvalidCharacter('"', 1),
+            ),
+            (
+                "<1234@example.com.>",
+                Error::DomainInvalidCharacter('.', 17),
+            ),
+            ("<1234@exa@mple.com>", Error::DomainInvalidCharacter('@', 9)),
+            (
+                "<1234@[a\\b]>",
+                Error::DomainLiteralInvalidCharacter('\\', 8),
+            ),
+            ("<1234@[127.0.0.1>", Error::DomainInvalidCharacter('[', 6)),
+        ] {
+            assert_eq!(MessageId::from_str(input), Err(error), "{}", input);
+        }
+        let id = MessageId::from_str("<1234@example.com>").unwrap();
+        assert_eq!(id.as_str(), "1234@example.com");
+        let text = id.to_string();
+        assert_eq!(text, "<1234@example.com>");
+        assert_eq!(MessageId::from_str(&text), Ok(id));
+    }
+
+    #[test]
+    fn test_reverse_path() {
+        assert_eq!(ReversePath::from_str("<>"), Ok(ReversePath::Null));
+        assert!(ReversePath::Null.is_null());
+        assert_eq!(ReversePath::Null.mailbox(), None);
+        assert_eq!(ReversePath::Null.to_string(), "<>");
+
+        let path = ReversePath::from_str("<simon@example.com>").unwrap();
+        assert!(!path.is_null());
+        assert_eq!(path.mailbox().unwrap().as_str(), "simon@example.com");
+        let text = path.to_string();
+        assert_eq!(text, "<simon@example.com>");
+        assert_eq!(ReversePath::from_str(&text), Ok(path.clone()));
+
+        for (input, error) in [
+            ("", Error::MissingAngleBrackets),
+            ("<", Error::MissingAngleBrackets),
+            ("simon@example.com", Error::MissingAngleBrackets),
+            ("<simon@example.com", Error::MissingAngleBrackets),
+            ("<simon>", Error::MissingSeparator),
+            (
+                "<si mon@example.com>",
+                Error::LocalPartInvalidCharacter(' ', 3),
+            ),
+        ] {
+            assert_eq!(ReversePath::from_str(input), Err(error), "{:?}", input);
+        }
+
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        assert_eq!(ReversePath::from(email.clone()), path);
+        assert_eq!(ReversePath::from(None), ReversePath::Null);
+        assert_eq!(Option::<EmailAddress>::from(path), Some(email));
+        assert_eq!(Option::<EmailAddress>::from(ReversePath::Null), None);
+
+        let options = Options::default().with_obsolete();
+        assert_eq!(
+            ReversePath::parse_with("<\"a\\\r\\\nRSET\"@example.com>", &options),
+            Err(Error::QuotedStringInvalidCharacter('\r', 4))
+        );
+    }
+
+    #[test]
+    fn test_source_route() {
+        let path = Path::from_str("<@relay1.example,@relay2.example:simon@example.com>").unwrap();
+        assert_eq!(
+            path.route()
+                .iter()
+                .map(DomainName::as_str)
+                .collect::<Vec<_>>(),
+            ["relay1.example", "relay2.example"]
+        );
+        assert_eq!(path.mailbox().as_str(), "simon@example.com");
+        let text = path.to_string();
+        assert_eq!(text, "<@relay1.example,@relay2.example:simon@example.com>");
+        assert_eq!(Path::from_str(&text), Ok(path.clone()));
+        assert_eq!(
+            path.clone().without_route().to_string(),
+            "<simon@example.com>"
+        );
+        assert_eq!(
+            ReversePath::from_str(&text),
+            Ok(ReversePath::Mailbox(path.into()))
+        );
+
+        let path = Path::from_str("<simon@example.com>").unwrap();
+        assert!(path.route().is_empty());
+        assert_eq!(path.to_string(), "<simon@example.com>");
+
+        for (input, error) in [
+            (
+                "@relay.example:simon@example.com",
+                Error::MissingAngleBrackets,
+            ),
+            ("<>", Error::MissingSeparator),
+            ("<@:simon@example.com>", Error::DomainEmpty),
+            ("<@relay.example,:simon@example.com>", Error::DomainEmpty),
+            (
+                "<@relay.example,relay2:simon@example.com>",
+                Error::DomainInvalidCharacter('r', 16),
+            ),
+            (
+                "<@re lay.example:simon@example.com>",
+                Error::DomainInvalidCharacter(' ', 4),
+            ),
+            (
+                "<@relay.example:si mon@example.com>",
+                Error::LocalPartInvalidCharacter(' ', 18),
+            ),
+            (
+                "<<simon@example.com>>",
+                Error::LocalPartInvalidCharacter('<', 1),
+            ),
+        ] {
+            assert_eq!(Path::from_str(input), Err(error), "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_postmaster() {
+        for address in [
+            "postmaster@example.com",
+            "Postmaster@example.com",
+            "POSTMASTER@[127.0.0.1]",
+            "\"postmaster\"@example.com",
+        ] {
+            assert!(
+                EmailAddress::from_str(address).unwrap().is_postmaster(),
+                "{}",
+                address
+            );
+        }
+        for address in ["postmaster+x@example.com", "post.master@example.com"] {
+            assert!(!EmailAddress::from_str(address).unwrap().is_postmaster());
+        }
+
+        for profile in [Profile::Rfc5321, Profile::Rfc5322, Profile::Rfc6531] {
+            let options = Options::from(profile);
+            for path in ["<postmaster>", "<Postmaster>", "<POSTMASTER>"] {
+                assert_eq!(
+                    ForwardPath::parse_with(path, &options),
+                    Ok(ForwardPath::Postmaster)
+                );
+            }
+        }
+        assert_eq!(ForwardPath::Postmaster.to_string(), "<Postmaster>");
+        assert!(ForwardPath::Postmaster.is_postmaster());
+        assert_eq!(ForwardPath::Postmaster.mailbox(), None);
+
+        let path = ForwardPath::from_str("<@relay.example:Postmaster@example.com>").unwrap();
+        assert!(path.is_postmaster());
+        assert_eq!(path.to_string(), "<Postmaster@example.com>");
+        let path = ForwardPath::from_str("<simon@example.com>").unwrap();
+        assert!(!path.is_postmaster());
+        assert_eq!(
+            path.mailbox().map(EmailAddress::as_str),
+            Some("simon@example.com")
+        );
+
+        assert_eq!(
+            ForwardPath::from_str("postmaster"),
+            Err(Error::MissingAngleBrackets)
+        );
+        assert_eq!(
+            ForwardPath::from_str("<postmasters>"),
+            Err(Error::MissingSeparator)
+        );
+        assert_eq!(
+            EmailAddress::parse_with("postmaster", &Options::from(Profile::Rfc5321)),
+            Err(Error::MissingSeparator)
+        );
+    }
+
+    #[test]
+    fn test_reserved_domain() {
+        for address in [
+            "simon@example.com",
+            "simon@example.net",
+            "simon@EXAMPLE.org",
+            "simon@mail.example.com",
+            "simon@host.example",
+            "simon@nowhere.invalid",
+            "simon@localhost",
+            "simon@my.localhost",
+            "simon@test",
+            "simon@ci.test",
+        ] {
+            assert!(
+                EmailAddress::from_str(address)
+                    .unwrap()
+                    .is_reserved_domain(),
+                "{}",
+                address
+            );
+        }
+        for address in [
+            "simon@example.co.uk",
+            "simon@notexample.com",
+            "simon@example.com.au",
+            "simon@testing.com",
+            "simon@[127.0.0.1]",
+        ] {
+            assert!(
+                !EmailAddress::from_str(address)
+                    .unwrap()
+                    .is_reserved_domain(),
+                "{}",
+                address
+            );
+        }
+    }
+
+    #[test]
+    fn test_options_internal_domains() {
+        let options = Options::default().without_internal_domains();
+        for address in [
+            "simon@localhost",
+            "simon@LocalHost",
+            "simon@mailserver1",
+            "simon@my.localhost",
+            "simon@printer.local",
+            "simon@wiki.corp.internal",
+        ] {
+            assert!(EmailAddress::is_valid(address), "{}", address);
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::InternalDomain.into(),
+                "{}",
+                address
+            );
+        }
+        for address in [
+            "simon@example.com",
+            "simon@local.example.com",
+            "simon@notlocal.com",
+            "simon@[127.0.0.1]",
+        ] {
+            assert!(
+                EmailAddress::parse_with(address, &options).is_ok(),
+                "{}",
+                address
+            );
+        }
+        assert_eq!(
+            DomainName::parse_with("printer.local", &options),
+            Err(Error::InternalDomain)
+        );
+        let error = EmailAddress::parse_spanned("simon@localhost", &options).unwrap_err();
+        assert_eq!(error.span(), 6..15);
+        assert!(
+            EmailAddress::parse_with("simon@localhost", &options.with_internal_domains()).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_options_special_use_domains() {
+        let options = Options::default().without_special_use_domains();
+        for address in [
+            "simon@hidden.onion",
+            "simon@router.home.arpa",
+            "simon@Example.COM",
+            "simon@test",
+            "simon@host.alt",
+            "simon@1.168.192.in-addr.arpa",
+        ] {
+            assert!(EmailAddress::is_valid(address), "{}", address);
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::DeniedDomain.into(),
+                "{}",
+                address
+            );
+        }
+        for address in [
+            "simon@example.co.uk",
+            "simon@onion.com",
+            "simon@arpa",
+            "simon@[127.0.0.1]",
+        ] {
+            assert!(
+                EmailAddress::parse_with(address, &options).is_ok(),
+                "{}",
+                address
+            );
+        }
+        assert!(DomainList::special_use().contains("onion"));
+        assert!(
+            EmailAddress::parse_with("simon@hidden.onion", &options.without_denied_domains())
+                .is_ok()
+        );
+
+        let denied: DomainList = vec!["corp.example.net"].into_iter().collect();
+        let options = Options::default().with_denied_domains(denied);
+        assert_eq!(
+            EmailAddress::parse_with("simon@mail.corp.example.net", &options),
+            Error::DeniedDomain.into()
+        );
+        assert!(EmailAddress::parse_with("simon@hidden.onion", &options).is_ok());
+        assert_eq!(
+            DomainName::parse_with("Corp.Example.Net", &options),
+            Err(Error::DeniedDomain)
+        );
+    }
+
+    #[test]
+    fn test_ip_scope() {
+        for (address, scope) in [
+            ("simon@[8.8.8.8]", IpScope::Global),
+            ("simon@[10.1.2.3]", IpScope::Private),
+            ("simon@[172.16.0.1]", IpScope::Private),
+            ("simon@[172.32.0.1]", IpScope::Global),
+            ("simon@[192.168.2.1]", IpScope::Private),
+            ("simon@[100.64.0.1]", IpScope::Private),
+            ("simon@[127.0.0.1]", IpScope::Loopback),
+            ("simon@[169.254.1.1]", IpScope::LinkLocal),
+            ("simon@[192.0.2.1]", IpScope::Documentation),
+            ("simon@[198.51.100.1]", IpScope::Documentation),
+            ("simon@[203.0.113.1]", IpScope::Documentation),
+            ("simon@[0.0.0.0]", IpScope::Unspecified),
+            ("simon@[224.0.0.1]", IpScope::Multicast),
+            ("simon@[198.18.0.1]", IpScope::Reserved),
+            ("simon@[255.255.255.255]", IpScope::Reserved),
+            ("simon@[IPv6:2606:4700::1111]", IpScope::Global),
+            ("simon@[IPv6:::1]", IpScope::Loopback),
+            ("simon@[IPv6:::]", IpScope::Unspecified),
+            ("simon@[IPv6:fd00::1]", IpScope::Private),
+            ("simon@[IPv6:fe80::1]", IpScope::LinkLocal),
+            ("simon@[IPv6:2001:db8::1]", IpScope::Documentation),
+            ("simon@[IPv6:3fff::1]", IpScope::Documentation),
+            ("simon@[IPv6:3fff:fff:ffff::1]", IpScope::Documentation),
+            ("simon@[IPv6:3fff:1000::1]", IpScope::Global),
+            ("simon@[IPv6:3ff0::1]", IpScope::Global),
+            ("simon@[IPv6:3ffe:ffff::1]", IpScope::Global),
+            ("simon@[IPv6:2002:a00:1::1]", IpScope::Private),
+            ("simon@[IPv6:2002:7f00:1::1]", IpScope::Loopback),
+            ("simon@[IPv6:2002:808:808::1]", IpScope::Global),
+            (
+                "simon@[IPv6:2001:0:4136:e378:8000:63bf:f5ff:fffe]",
+                IpScope::Private,
+            ),
+            (
+                "simon@[IPv6:2001:0:4136:e378:8000:63bf:3fff:fdd2]",
+                IpScope::Documentation,
+            ),
+            (
+                "simon@[IPv6:2001:0:4136:e378:8000:63bf:f7f7:f7f7]",
+                IpScope::Global,
+            ),
+            ("simon@[IPv6:ff02::1]", IpScope::Multicast),
+            ("simon@[IPv6:::ffff:192.168.2.1]", IpScope::Private),
+        ] {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert_eq!(email.ip_scope(), Some(scope), "{}", address);
+        }
+        assert_eq!(
+            EmailAddress::from_str("simon@example.com")
+                .unwrap()
+                .ip_scope(),
+            None
+        );
+        assert_eq!(
+            EmailAddress::from_str("simon@[x400:c=gb]")
+                .unwrap()
+                .ip_scope(),
+            None
+        );
+
+        let options = Options::default().without_non_global_address_literals();
+        for address in [
+            "simon@[8.8.8.8]",
+            "simon@[IPv6:2606:4700::1111]",
+            "simon@[x400:c=gb]",
+            "simon@example.com",
+        ] {
+            assert!(
+                EmailAddress::parse_with(address, &options).is_ok(),
+                "{}",
+                address
+            );
+        }
+        for address in [
+            "simon@[127.0.0.1]",
+            "simon@[10.0.0.1]",
+            "simon@[IPv6:::1]",
+            "simon@[IPv6:::ffff:127.0.0.1]",
+        ] {
+            assert_eq!(
+                EmailAddress::parse_with(address, &options),
+                Error::NonGlobalAddressLiteral.into(),
+                "{}",
+                address
+            );
+        }
+        assert!(EmailAddress::parse_with(
+            "simon@[127.0.0.1]",
+            &options.with_non_global_address_literals()
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_xtext() {
+        for (address, xtext) in [
+            ("simon@example.com", "simon@example.com"),
+            ("simon+news@example.com", "simon+2Bnews@example.com"),
+            ("a=b@example.com", "a+3Db@example.com"),
+            ("\"si mon\"@example.com", "\"si+20mon\"@example.com"),
+            ("jöran@example.com", "j+C3+B6ran@example.com"),
+        ] {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert_eq!(email.to_xtext(), xtext);
+            assert_eq!(EmailAddress::from_xtext(xtext), Ok(email));
+        }
+        for (xtext, error) in [
+            ("simon+news@example.com", Error::InvalidXtext(5)),
+            ("simon+2bnews@example.com", Error::InvalidXtext(5)),
+            ("simon+2", Error::InvalidXtext(5)),
+            ("a=b@example.com", Error::InvalidXtext(1)),
+            ("si mon@example.com", Error::InvalidXtext(2)),
+            ("simon+0Aexample.com", Error::MissingSeparator),
+            ("j+F6ran@example.com", Error::InvalidUtf8(1)),
+        ] {
+            assert_eq!(EmailAddress::from_xtext(xtext), Err(error), "{:?}", xtext);
+        }
+    }
+
+    #[test]
+    fn test_smtp_commands() {
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        assert!(!email.requires_smtputf8());
+        assert_eq!(
+            email.to_mail_from_command().as_deref(),
+            Some("MAIL FROM:<simon@example.com>")
+        );
+        assert_eq!(
+            email.to_rcpt_to_command().as_deref(),
+            Some("RCPT TO:<simon@example.com>")
+        );
+
+        let email = EmailAddress::from_str("\"si mon\"@example.com").unwrap();
+        assert_eq!(
+            email.to_rcpt_to_command().as_deref(),
+            Some("RCPT TO:<\"si mon\"@example.com>")
+        );
+
+        let options = Options::default().with_obsolete();
+        for address in [
+            "\"a\\\r\\\nRSET\"@example.com",
+            "\"a\\\u{0}b\"@example.com",
+            "a@[a\\\rb]",
+            "john.\"doe\"@example.com",
+        ] {
+            let email = EmailAddress::parse_with(address, &options).unwrap();
+            assert!(!email.is_smtp_mailbox(), "{:?}", address);
+            assert_eq!(email.to_mail_from_command(), None, "{:?}", address);
+            assert_eq!(email.to_rcpt_to_command(), None, "{:?}", address);
+        }
+
+        for address in ["jöran@example.com", "simon@bücher.example"] {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert!(email.requires_smtputf8());
+            assert_eq!(
+                email.to_mail_from_command(),
+                Some(format!("MAIL FROM:<{}> SMTPUTF8", address))
+            );
+            assert_eq!(
+                email.to_rcpt_to_command(),
+                Some(format!("RCPT TO:<{}>", address))
+            );
+        }
+    }
+
+    #[test]
+    fn test_envelope() {
+        let simon = EmailAddress::from_str("simon@example.com").unwrap();
+        let jane = EmailAddress::from_str("jane@example.org").unwrap();
+        let joran = EmailAddress::from_str("jöran@example.org").unwrap();
+
+        assert_eq!(
+            Envelope::new(simon.clone(), vec![]),
+            Err(EnvelopeError::NoRecipients)
+        );
+
+        let envelope = Envelope::new(simon.clone(), vec![jane.clone()]).unwrap();
+        assert_eq!(
+            envelope.reverse_path(),
+            &ReversePath::Mailbox(simon.clone())
+        );
+        assert_eq!(envelope.recipients(), std::slice::from_ref(&jane));
+        assert!(!envelope.requires_smtputf8());
+        assert_eq!(envelope.check_smtputf8(false), Ok(()));
+        assert_eq!(
+            envelope.to_commands().unwrap(),
+            [
+                "MAIL FROM:<simon@example.com>",
+                "RCPT TO:<jane@example.org>"
+            ]
+        );
+
+        let envelope = envelope.with_recipient(joran.clone());
+        assert!(envelope.requires_smtputf8());
+        assert_eq!(envelope.check_smtputf8(true), Ok(()));
+        assert_eq!(
+            envelope.check_smtputf8(false),
+            Err(EnvelopeError::Smtputf8Required(joran.clone()))
+        );
+        assert_eq!(
+            envelope.to_commands().unwrap(),
+            [
+                "MAIL FROM:<simon@example.com> SMTPUTF8",
+                "RCPT TO:<jane@example.org>",
+                "RCPT TO:<jöran@example.org>"
+            ]
+        );
+
+        let envelope = Envelope::new(ReversePath::Null, vec![jane]).unwrap();
+        assert_eq!(envelope.to_commands().unwrap()[0], "MAIL FROM:<>");
+        let envelope = Envelope::new(joran.clone(), vec![simon]).unwrap();
+        assert_eq!(
+            envelope.check_smtputf8(false),
+            Err(EnvelopeError::Smtputf8Required(joran))
+        );
+        assert_eq!(
+            EnvelopeError::NoRecipients.to_string(),
+            "The envelope has no recipients."
+        );
+
+        let injected = EmailAddress::parse_with(
+            "\"a\\\r\\\nRSET\"@example.com",
+            &Options::default().with_obsolete(),
+        )
+        .unwrap();
+        let envelope = Envelope::new(ReversePath::Null, vec![injected.clone()]).unwrap();
+        assert_eq!(
+            envelope.to_commands(),
+            Err(EnvelopeError::NotSmtpMailbox(injected.clone()))
+        );
+        let envelope = Envelope::new(
+            injected.clone(),
+            vec![EmailAddress::from_str("jane@example.org").unwrap()],
+        )
+        .unwrap();
+        assert_eq!(
+            envelope.to_commands(),
+            Err(EnvelopeError::NotSmtpMailbox(injected))
+        );
+    }
+
+    #[test]
+    fn test_address_list() {
+        let list =
+            EmailAddressList::from_str(" simon@example.com,\"a,b\"@example.org , c@[127.0.0.1]")
+                .unwrap();
+        assert_eq!(
+            list.iter().map(EmailAddress::as_str).collect::<Vec<_>>(),
+            vec!["simon@example.com", "\"a,b\"@example.org", "c@[127.0.0.1]"]
+        );
+        assert_eq!(
+            list.to_string(),
+            "simon@example.com, \"a,b\"@example.org, c@[127.0.0.1]"
+        );
+        assert_eq!(EmailAddressList::from_str(&list.to_string()), Ok(list));
+
+        let options = Options::default().with_cfws();
+        assert_eq!(
+            EmailAddressList::parse_with("jane(x,y)@example.net, simon@example.com", &options)
+                .map(|list| list.len()),
+            Ok(2)
+        );
+
+        assert_eq!(EmailAddressList::from_str(" "), Ok(EmailAddressList::new()));
+        assert_eq!(
+            EmailAddressList::from_str("simon@example.com, j ane@example.org"),
+            Err(Error::LocalPartInvalidCharacter(' ', 20))
+        );
+        assert!(EmailAddressList::from_str("simon@example.com,,jane@example.org").is_err());
+
+        let mut list: EmailAddressList = ["a@example.com", "b@example.com", "a@example.com"]
+            .iter()
+            .map(|s| EmailAddress::from_str(s).unwrap())
+            .collect();
+        list.dedup();
+        assert_eq!(list.to_string(), "a@example.com, b@example.com");
+    }
+
+    #[test]
+    fn test_corpus() {
+        let examples = corpus::examples();
+        for example in &examples {
+            assert_eq!(
+                EmailAddress::from_str(example.address()).map(|_| ()),
+                example.result(),
+                "{:?}",
+                example
+            );
+            assert_eq!(
+                EmailAddress::is_valid(example.address()),
+                example.is_valid(),
+                "{:?}",
+                example
+            );
+        }
+        for kind in [
+            corpus::Kind::ValidAscii,
+            corpus::Kind::ValidUtf8,
+            corpus::Kind::QuotedLocalPart,
+            corpus::Kind::DomainLiteral,
+            corpus::Kind::Invalid,
+        ] {
+            assert!(corpus::examples_of(kind).next().is_some(), "{:?}", kind);
+        }
+        assert!(corpus::examples_of(corpus::Kind::ValidAscii)
+            .all(|example| example.address().is_ascii()));
+        assert_eq!(
+            corpus::examples_of(corpus::Kind::Invalid).count(),
+            examples
+                .iter()
+                .filter(|example| !example.is_valid())
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let errors = [
+            Error::LocalPartInvalidCharacter(' ', 0),
+            Error::QuotedStringInvalidCharacter(' ', 0),
+            Error::DomainInvalidCharacter(' ', 0),
+            Error::DomainLiteralInvalidCharacter(' ', 0),
+            Error::MissingSeparator,
+            Error::LocalPartEmpty,
+            Error::LocalPartTooLong,
+            Error::DomainEmpty,
+            Error::DomainTooLong,
+            Error::SubDomainTooLong,
+            Error::AddressTooLong,
+            Error::DomainTooFew,
+            Error::DomainInvalidSeparator,
+            Error::UnbalancedQuotes,
+            Error::InvalidComment,
+            Error::InvalidIPAddress,
+            Error::UnsupportedQuotedLocalPart,
+            Error::UnsupportedDomainLiteral,
+            Error::InvalidUtf8(0),
+            Error::MissingAngleBrackets,
+            Error::InvalidXtext(0),
+            Error::InternalDomain,
+            Error::DeniedDomain,
+            Error::NonGlobalAddressLiteral,
+            Error::DomainTooMany,
+            Error::TooManyTagSeparators,
+            Error::PercentHackRouting,
+            Error::BangPathRouting,
+        ];
+        assert_eq!(
+            Error::LocalPartTooLong.to_string(),
+            "Local part is too long. Length limit: 64 octets by default."
+        );
+        assert_eq!(
+            Error::AddressTooLong.to_string(),
+            "Address is too long. Length limit: 254 octets by default."
+        );
+        for (i, error) in errors.iter().enumerate() {
+            assert_eq!(usize::from(error.code()), i + 1, "{:?}", error);
+            assert!(Diagnostic::from(error).is_error(), "{:?}", error);
+        }
+
+        let code = ErrorCode::from(&Error::QuotedStringInvalidCharacter('ö', 12));
+        assert_eq!(
+            code,
+            ErrorCode {
+                code: 2,
+                character: 0xF6,
+                position: 12
+            }
+        );
+        assert_eq!(
+            Error::InvalidUtf8(4).error_code(),
+            ErrorCode {
+                code: 19,
+                character: 0,
+                position: 4
+            }
+        );
+    }
+
+    #[cfg(feature = "validator")]
+    #[test]
+    fn test_validator() {
+        use validator::ValidateEmail;
+
+        assert!(validate_email_address("simon@example.com").is_ok());
+        assert!(validate_email_address(&"\"john..doe\"@example.org".to_string()).is_ok());
+        let error = validate_email_address("simon@").unwrap_err();
+        assert_eq!(error.code, "email");
+        assert_eq!(error.message.as_deref(), Some("Domain is empty."));
+        assert_eq!(
+            error.params["error"],
+            serde_json::json!(Error::DomainEmpty.code())
+        );
+        assert_eq!(error.params["value"], serde_json::json!("simon@"));
+
+        let email = EmailAddress::from_str("\"john..doe\"@example.org").unwrap();
+        assert!(email.validate_email());
+        assert!(!email.as_str().validate_email());
+    }
+
+    #[cfg(feature = "garde")]
+    #[test]
+    fn test_garde() {
+        use garde::Validate;
+
+        #[derive(Validate)]
+        struct SignUp {
+            #[garde(custom(garde_email_address))]
+            email: String,
+            #[garde(dive)]
+            backup: Option<EmailAddress>,
+        }
+
+        assert!(garde_email_address("simon@example.com", &()).is_ok());
+        assert!(garde_email_address(&"\"john..doe\"@example.org".to_string(), &()).is_ok());
+        let error = garde_email_address("simon@", &()).unwrap_err();
+        assert_eq!(error.message(), Error::DomainEmpty.to_string());
+
+        let sign_up = SignUp {
+            email: "simon@example.com".to_string(),
+            backup: Some(EmailAddress::from_str("\"john..doe\"@example.org").unwrap()),
+        };
+        assert!(sign_up.validate().is_ok());
+        let sign_up = SignUp {
+            email: "simon@".to_string(),
+            backup: None,
+        };
+        let report = sign_up.validate().unwrap_err();
+        let errors: Vec<_> = report
+            .iter()
+            .map(|(path, error)| (path.to_string(), error.message().to_string()))
+            .collect();
+        assert_eq!(
+            errors,
+            vec![("email".to_string(), Error::DomainEmpty.to_string())]
+        );
+    }
+
+    #[cfg(feature = "web")]
+    #[test]
+    fn test_web_fields() {
+        use axum::response::IntoResponse;
+
+        let field: EmailField = serde_json::from_str("\"simon@example.com\"").unwrap();
+        assert!(field.is_valid());
+        assert_eq!(
+            field.into_result("email").unwrap().as_str(),
+            "simon@example.com"
+        );
+
+        let field: EmailField = serde_json::from_str("\"simon@\"").unwrap();
+        assert_eq!(field.input(), "simon@");
+        let errors = field.into_result("email").unwrap_err();
+        assert_eq!(errors.errors()[0].field(), "email");
+        assert_eq!(errors.errors()[0].error(), &Error::DomainEmpty);
+        assert_eq!(errors.errors()[0].span(), 6..6);
+        assert_eq!(
+            serde_json::to_value(&errors).unwrap(),
+            serde_json::json!({
+                "errors": [{
+                    "field": "email",
+                    "code": Error::DomainEmpty.code(),
+                    "message": "Domain is empty.",
+                    "error": {"error": "domain-empty"},
+                    "span": {"start": 6, "end": 6},
+                }]
+            })
+        );
+        assert_eq!(errors.to_string(), "email: Domain is empty.");
+        assert_eq!(
+            errors.into_response().status(),
+            axum::http::StatusCode::UNPROCESSABLE_ENTITY
+        );
+
+        let mut errors = FieldErrors::new();
+        let valid = EmailField::parse_with("simon@example.com", &Options::default());
+        assert!(errors.check("email", valid).is_some());
+        assert!(errors.clone().into_result().is_ok());
+        let invalid = EmailField::parse_with("simon", &Options::default());
+        assert!(errors.check("backup", invalid).is_none());
+        assert_eq!(errors.errors().len(), 1);
+        assert!(errors.into_result().is_err());
+    }
+
+    #[cfg(feature = "pyo3")]
+    #[test]
+    fn test_python_module() {
+        use pyo3::prelude::*;
+        use pyo3::types::{PyDict, PyModule};
+        use std::ffi::CString;
+
+        let code = CString::new(
+            r#"
+email = email_address.EmailAddress("Simon@Example.COM")
+assert email.local_part == "Simon"
+assert email.domain == "Example.COM"
+assert str(email.normalize()) == "simon@example.com"
+assert str(email.normalize(False)) == "Simon@example.com"
+assert repr(email) == 'EmailAddress("Simon@Example.COM")'
+assert email == email_address.EmailAddress("Simon@Example.COM")
+assert hash(email) == hash(email_address.EmailAddress("Simon@Example.COM"))
+
+assert email_address.is_valid("simon@example.com")
+assert not email_address.is_valid("simon@")
+assert email_address.normalize('"Simon"@Example.COM', False) == "Simon@example.com"
+assert email_address.error_code("simon@example.com") is None
+assert email_address.error_code("simon@") == 8
+
+try:
+    email_address.EmailAddress("simon@exa mple.com")
+    assert False
+except ValueError as e:
+    assert isinstance(e, email_address.EmailAddressError)
+    assert e.args[1:] == (3, 9)
+
+try:
+    email_address.normalize("jöran@exa mple.com")
+    assert False
+except email_address.EmailAddressError as e:
+    assert e.args[1:] == (3, 9)
+"#,
+        )
+        .unwrap();
+
+        Python::initialize();
+        Python::attach(|py| {
+            let module = PyModule::new(py, "email_address").unwrap();
+            pyo3_support::email_address(&module).unwrap();
+            let globals = PyDict::new(py);
+            globals.set_item("email_address", module).unwrap();
+            py.run(&code, Some(&globals), None)
+                .map_err(|error| error.display(py))
+                .unwrap();
+        });
+    }
+
+    #[cfg(feature = "i18n")]
+    #[test]
+    fn test_localized_errors() {
+        let errors = [
+            Error::DomainInvalidCharacter('_', 9),
+            Error::InvalidUtf8(4),
+            Error::MissingSeparator,
+            Error::NonGlobalAddressLiteral,
+        ];
+        for language in [
+            Language::German,
+            Language::French,
+            Language::Spanish,
+            Language::Japanese,
+        ] {
+            for error in &errors {
+                let message = error.localized(language).to_string();
+                assert_ne!(message, error.to_string(), "{:?} {:?}", language, error);
+            }
+        }
+        assert_eq!(
+            errors[0].localized(Language::French).to_string(),
+            "Caractère '_' invalide dans le domaine à la position 9."
+        );
+        assert_eq!(
+            errors[1].localized(Language::Japanese).to_string(),
+            "位置 4 の UTF-8 が無効です。"
+        );
+        assert_eq!(
+            errors[2].localized(Language::Spanish).to_string(),
+            "Falta el separador '@'."
+        );
+        assert_eq!(Language::from_tag("fr_CA"), Some(Language::French));
+        assert_eq!(Language::from_tag("en-GB"), Some(Language::English));
+        assert_eq!(Language::from_tag(""), None);
+    }
+
+    #[cfg(feature = "differential")]
+    #[test]
+    fn test_differential() {
+        let differential = Differential::new().with_html5();
+        assert_eq!(differential.names(), vec!["email_address", "html5"]);
+        assert!(differential
+            .run(vec!["simon@example.com", "simon", "simon@例子.广告"])
+            .iter()
+            .all(|disagreement| disagreement.input() == "simon@例子.广告"));
+
+        let disagreements = differential.run_corpus();
+        assert!(!disagreements.is_empty());
+        assert!(disagreements
+            .iter()
+            .all(
+                |disagreement| disagreement.accepted_by() == vec!["email_address"]
+                    || disagreement.accepted_by() == vec!["html5"]
+            ));
+        let quoted = disagreements
+            .iter()
+            .find(|disagreement| disagreement.input() == "\"john..doe\"@example.org")
+            .unwrap();
+        assert_eq!(
+            quoted.to_string(),
+            "\"\\\"john..doe\\\"@example.org\": email_address=valid html5=invalid"
+        );
+
+        assert!(Differential::new().with_regex("broken", "(").is_err());
+        let same = Differential::new().with_validator("same", EmailAddress::is_valid);
+        assert!(same.run_corpus().is_empty());
+    }
+
+    #[test]
+    fn test_dedupe() {
+        let addresses: Vec<EmailAddress> = [
+            "Simon.Doe+news@gmail.com",
+            "simon@Example.com",
+            "simondoe@googlemail.com",
+            "simon@example.COM",
+            "simon+work@example.com",
+            "Simon@example.com",
+        ]
+        .iter()
+        .map(|s| EmailAddress::from_str(s).unwrap())
+        .collect();
+        let unique = |equivalence| {
+            EmailAddress::dedupe(addresses.clone(), equivalence)
+                .unique()
+                .iter()
+                .map(EmailAddress::as_str)
+                .map(String::from)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            unique(Equivalence::CaseInsensitiveDomain),
+            vec![
+                "Simon.Doe+news@gmail.com",
+                "simon@Example.com",
+                "simondoe@googlemail.com",
+                "simon+work@example.com",
+                "Simon@example.com",
+            ]
+        );
+        assert_eq!(
+            unique(Equivalence::ProviderCanonical),
+            vec![
+                "Simon.Doe+news@gmail.com",
+                "simon@Example.com",
+                "simon+work@example.com",
+                "Simon@example.com",
+            ]
+        );
+        assert_eq!(
+            unique(Equivalence::IgnoreTags),
+            vec![
+                "Simon.Doe+news@gmail.com",
+                "simon@Example.com",
+                "simondoe@googlemail.com",
+                "Simon@example.com",
+            ]
+        );
+
+        let deduplicated = EmailAddress::dedupe(addresses.clone(), Equivalence::IgnoreTags);
+        assert_eq!(
+            deduplicated
+                .duplicates()
+                .iter()
+                .map(|(email, index)| (email.as_str(), *index))
+                .collect::<Vec<_>>(),
+            vec![("simon@example.COM", 1), ("simon+work@example.com", 1)]
+        );
+        assert_eq!(
+            deduplicated.representative(&addresses[4]),
+            Some(&addresses[1])
+        );
+        assert_eq!(
+            deduplicated.representative(&addresses[5]),
+            Some(&addresses[5])
+        );
+        assert_eq!(
+            deduplicated.representative(&EmailAddress::from_str("jane@example.org").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_email_key() {
+        let key = |address, equivalence| {
+            EmailKey::new(EmailAddress::from_str(address).unwrap(), equivalence)
+        };
+
+        let a = key("Simon@Example.COM", Equivalence::CaseInsensitiveDomain);
+        let b = key("Simon@example.com", Equivalence::CaseInsensitiveDomain);
+        let c = key("simon@example.com", Equivalence::CaseInsensitiveDomain);
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(a.email().as_str(), "Simon@Example.COM");
+        assert_eq!(a.to_string(), "Simon@example.com");
+        assert_eq!(EmailAddress::from(a.clone()).as_str(), "Simon@Example.COM");
+
+        let mut keys = vec![
+            key("simon.doe+x@gmail.com", Equivalence::ProviderCanonical),
+            key("jane@example.org", Equivalence::ProviderCanonical),
+            key("SimonDoe@googlemail.com", Equivalence::ProviderCanonical),
+        ];
+        keys.sort();
+        keys.dedup();
+        assert_eq!(
+            keys.iter().map(EmailKey::to_string).collect::<Vec<_>>(),
+            vec!["jane@example.org", "simondoe@gmail.com"]
+        );
+
+        let set: std::collections::HashSet<EmailKey> = vec![a, b, c].into_iter().collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_address_lines() {
+        let input = b"\xEF\xBB\xBFsimon@example.com\r\n\n  \nsimon\r\nj\xF6ran@example.com\njane@example.org";
+        let results: Vec<(usize, Result<EmailAddress, Error>)> = AddressLines::new(&input[..])
+            .collect::<std::io::Result<_>>()
+            .unwrap();
+        assert_eq!(
+            results,
+            vec![
+                (1, EmailAddress::from_str("simon@example.com")),
+                (3, Err(Error::MissingSeparator)),
+                (4, Err(Error::MissingSeparator)),
+                (5, Err(Error::InvalidUtf8(1))),
+                (6, EmailAddress::from_str("jane@example.org")),
+            ]
+        );
+        let mut lines = AddressLines::new(&b"simon@localhost\n"[..])
+            .with_options(Options::default().with_required_tld());
+        assert_eq!(lines.next().unwrap().unwrap().1, Err(Error::DomainTooFew));
+        assert!(lines.next().is_none());
+        assert_eq!(lines.line_number(), 1);
+    }
+
+    #[test]
+    fn test_borrowed_email_address() {
+        let input = String::from("<simon@example.com>");
+        let email = BorrowedEmailAddress::parse(&input).unwrap();
+        assert!(email.is_borrowed());
+        assert!(std::ptr::eq(email.as_str(), &input[1..input.len() - 1]));
+        assert_eq!(email.local_part(), "simon");
+        assert_eq!(email.domain(), "example.com");
+        assert_eq!(
+            EmailAddress::from(email.clone()),
+            EmailAddress::from_str(&input).unwrap()
+        );
+        assert_eq!(
+            allocations(|| BorrowedEmailAddress::parse(&input).is_ok()),
+            0
+        );
+        let email = BorrowedEmailAddress::parse_with(
+            "simon(comment)@example.com",
+            &Options::default().with_cfws(),
+        )
+        .unwrap();
+        assert!(!email.is_borrowed());
+        assert_eq!(email.as_str(), "simon@example.com");
+        assert_eq!(
+            BorrowedEmailAddress::parse("simon"),
+            Err(Error::MissingSeparator)
+        );
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn test_serde() {
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        let json = serde_json::to_string(&email).unwrap();
+        assert_eq!(json, r#"{"local":"simon","domain":"example.com"}"#);
+        assert_eq!(serde_json::from_str::<EmailAddress>(&json).unwrap(), email);
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn test_serde_lowercase() {
+        #[derive(Serialize, Deserialize)]
+        struct Contact {
+            #[serde(with = "crate::serde_lowercase")]
+            email: EmailAddress,
+        }
+
+        let contact = Contact {
+            email: EmailAddress::from_str("\"Simon J\"@Example.COM").unwrap(),
+        };
+        let json = serde_json::to_string(&contact).unwrap();
+        assert_eq!(
+            json,
+            r#"{"email":{"local":"\"Simon J\"","domain":"example.com"}}"#
+        );
+
+        let json = r#"{"email":{"local":"Simon","domain":"Example.COM"}}"#;
+        let contact: Contact = serde_json::from_str(json).unwrap();
+        assert_eq!(contact.email.as_str(), "simon@example.com");
+    }
+
+    #[cfg(feature = "serde_support")]
+    #[test]
+    fn test_serde_errors() {
+        let error =
+            EmailAddress::parse_spanned("sim on@example.com", &Options::default()).unwrap_err();
+        let json = serde_json::to_string(&error).unwrap();
+        assert_eq!(
+            json,
+            r#"{"error":{"error":"local-part-invalid-character","details":[" ",3]},"span":{"start":3,"end":4}}"#
+        );
+        assert_eq!(serde_json::from_str::<ParseError>(&json).unwrap(), error);
+
+        for error in [
+            Error::LocalPartEmpty,
+            Error::InvalidIPAddress,
+            Error::InvalidUtf8(7),
+        ] {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(serde_json::from_str::<Error>(&json).unwrap(), error);
+        }
+        assert_eq!(
+            serde_json::to_string(&Error::InvalidIPAddress).unwrap(),
+            r#"{"error":"invalid-ip-address"}"#
+        );
+
+        let diagnostic = Diagnostic::from(&Error::LocalPartEmpty);
+        let json = serde_json::to_string(&(diagnostic, diagnostic.category())).unwrap();
+        assert_eq!(json, r#"["no-local-part","error"]"#);
+        assert_eq!(
+            serde_json::to_string(&Warning::QuotedLocalPart).unwrap(),
+            r#""quoted-local-part""#
+        );
+    }
+
+    #[test]
+    fn test_is_subdomain_of() {
+        let subdomain_of = |address, domain| {
+            EmailAddress::from_str(address)
+                .unwrap()
+                .is_subdomain_of(domain)
+        };
+        assert!(subdomain_of("simon@example.com", "example.com"));
+        assert!(subdomain_of("simon@example.com", "EXAMPLE.com."));
+        assert!(subdomain_of("simon@a.b.Example.com", "example.com"));
+        assert!(subdomain_of("simon@a.b.example.com", "com"));
+        assert!(!subdomain_of("simon@notexample.com", "example.com"));
+        assert!(!subdomain_of("simon@example.com", "mail.example.com"));
+        assert!(!subdomain_of("simon@example.com", ""));
+        assert!(!subdomain_of("simon@example.com", "."));
+        assert!(subdomain_of("simon@[192.168.2.1]", "[192.168.2.1]"));
+        assert!(!subdomain_of("simon@[192.168.2.1]", "2.1]"));
+    }
+
+    #[test]
+    fn test_sort_key() {
+        let key = |address| EmailAddress::from_str(address).unwrap().sort_key();
+        assert_eq!(
+            key("Simon@Mail.Example.COM"),
+            (
+                vec!["com".to_string(), "example".to_string(), "mail".to_string()],
+                "Simon".to_string()
+            )
+        );
+        assert_eq!(
+            key("simon@[IPv6:::1]"),
+            (vec!["[ipv6:::1]".to_string()], "simon".to_string())
+        );
+        assert!(key("zed@example.com") < key("alice@a.example.com"));
+        assert!(key("alice@a.example.com") < key("alice@example-a.com"));
+        assert!(key("alice@example.com") < key("bob@example.com"));
+    }
+
+    #[test]
+    fn test_canonical_bytes() {
+        let bytes = |address| EmailAddress::from_str(address).unwrap().canonical_bytes();
+        assert_eq!(
+            bytes("Simon@Example.COM"),
+            b"\x01\0\0\0\x05simonexample.com"
+        );
+        assert_eq!(bytes("simon@example.com"), bytes("\"SIMON\"@EXAMPLE.com"));
+        assert_eq!(
+            bytes("\"Simon Doe\"@example.com"),
+            b"\x01\0\0\0\x0B\"Simon Doe\"example.com"
+        );
+        assert_eq!(
+            bytes("\"Simon Doe\"@example.com"),
+            bytes("\"Simon\\ Doe\"@Example.com")
+        );
+        assert_ne!(bytes("a@bc.com"), bytes("\"a@b\"@c.com"));
+        assert_eq!(
+            bytes("Jöran@Exämple.com"),
+            "\x01\0\0\0\x06jöranexämple.com".as_bytes()
+        );
+        assert_eq!(bytes("a@[IPv6:ABCD::1]"), b"\x01\0\0\0\x01a[ipv6:abcd::1]");
+        assert_eq!(bytes("a@[IPv6:ABCD::1]"), bytes("a@[ipv6:abcd::1]"));
+    }
+
+    #[cfg(feature = "hashing")]
+    #[test]
+    fn test_hashed_sha256() {
+        let email = EmailAddress::from_str("\"Test\"@example.COM").unwrap();
+        assert_eq!(
+            email.hashed_sha256(),
+            "87317699c3db6e1489f1329d21c3450e477b7cdcbdf9dd79f2cf852eb003a6cb"
+        );
+        let email = EmailAddress::from_str("john.doe+ads@googlemail.com").unwrap();
+        assert_eq!(
+            email.hashed_sha256_with(ProviderRule::built_in()),
+            EmailAddress::from_str("johndoe@gmail.com")
+                .unwrap()
+                .hashed_sha256()
+        );
+        assert_ne!(
+            email.hashed_sha256(),
+            email.hashed_sha256_with(ProviderRule::built_in())
+        );
+    }
+
+    #[test]
+    fn test_tld() {
+        let tld = |address| {
+            EmailAddress::from_str(address)
+                .unwrap()
+                .tld()
+                .map(str::to_string)
+        };
+        assert_eq!(tld("simon@example.com").as_deref(), Some("com"));
+        assert_eq!(tld("simon@mail.Example.ORG").as_deref(), Some("ORG"));
+        assert_eq!(tld("simon@example.xn--p1ai").as_deref(), Some("xn--p1ai"));
+        assert_eq!(tld("simon@localhost"), None);
+        assert_eq!(tld("simon@[192.168.2.1]"), None);
+        assert_eq!(tld("simon@[IPv6:::1]"), None);
+    }
+
+    #[test]
+    fn test_domain_name() {
+        let domain = DomainName::from_str("Mail.Example.com").unwrap();
+        assert_eq!(domain.as_str(), "Mail.Example.com");
+        assert_eq!(domain.to_string(), "Mail.Example.com");
+        assert_eq!(domain.label_count(), 3);
+        assert_eq!(
+            domain.labels().rev().collect::<Vec<_>>(),
+            vec!["com", "Example", "Mail"]
+        );
+        assert_eq!(domain.label_lengths().collect::<Vec<_>>(), vec![4, 7, 3]);
+        assert_eq!(domain.tld(), Some("com"));
+        assert_eq!(DomainName::from_str("localhost").unwrap().tld(), None);
+        assert_eq!(
+            DomainName::from_str("example..com"),
+            Err(Error::DomainInvalidCharacter('.', 8))
+        );
+        assert_eq!(
+            DomainName::from_str("[192.168.2.1]"),
+            Err(Error::UnsupportedDomainLiteral)
+        );
+        assert!(DomainName::from_str(&format!("{}.com", "a".repeat(64))).is_err());
+        let options = Options::default().with_ldh_domain();
+        assert!(DomainName::parse_with("my-host.example", &options).is_ok());
+        assert!(DomainName::parse_with("my_host.example", &options).is_err());
+        let email = EmailAddress::from_str("simon@mail.example.com").unwrap();
+        assert_eq!(
+            email.domain_name(),
+            Some(DomainName::from_str("mail.example.com").unwrap())
+        );
+        let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+        assert_eq!(email.domain_name(), None);
+    }
+
+    #[test]
+    fn test_domain() {
+        let domain = Domain::from_str("mail.example.com").unwrap();
+        assert!(!domain.is_literal());
+        assert_eq!(domain.as_str(), "mail.example.com");
+        assert_eq!(
+            domain,
+            Domain::from(DomainName::from_str("mail.example.com").unwrap())
+        );
+        let domain = Domain::from_str("[IPv6:::1]").unwrap();
+        assert!(domain.is_literal());
+        assert_eq!(domain.as_name(), None);
+        assert_eq!(domain.to_string(), "[IPv6:::1]");
+        assert_eq!(
+            Domain::parse_with(
+                "[192.168.2.1]",
+                &Options::default().without_domain_literal()
+            ),
+            Err(Error::UnsupportedDomainLiteral)
+        );
+        assert_eq!(Domain::from_str(""), Err(Error::DomainEmpty));
+        let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+        assert_eq!(
+            email.to_domain(),
+            Domain::Literal("[192.168.2.1]".to_string())
+        );
+        assert_eq!(String::from(email.to_domain()), "[192.168.2.1]");
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_09() {
-        is_valid("admin@mailserver1", Some("local domain name with no TLD, although ICANN highly discourages dotless email addresses"));
+    fn test_domain_part() {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+        let part = |address| {
+            let email = EmailAddress::from_str(address).unwrap();
+            assert_eq!(email.domain_part(), email.to_domain().part());
+            format!("{:?}", email.domain_part())
+        };
+        assert_eq!(part("simon@example.com"), "Name(\"example.com\")");
+        assert_eq!(
+            EmailAddress::from_str("simon@[192.168.002.001]")
+                .unwrap()
+                .domain_part(),
+            DomainPart::Ipv4(Ipv4Addr::new(192, 168, 2, 1))
+        );
+        assert_eq!(
+            EmailAddress::from_str("simon@[IPv6:2001:db8::1]")
+                .unwrap()
+                .domain_part(),
+            DomainPart::Ipv6("2001:db8::1".parse::<Ipv6Addr>().unwrap())
+        );
+        assert_eq!(
+            part("simon@[ipv6:::ffff:192.168.2.1]"),
+            "Ipv6(::ffff:192.168.2.1)"
+        );
+        assert_eq!(
+            part("simon@[x400:c=gb;p=example]"),
+            "GeneralLiteral { tag: \"x400\", content: \"c=gb;p=example\" }"
+        );
+        assert_eq!(
+            part("simon@[IPv6:nonsense]"),
+            "GeneralLiteral { tag: \"IPv6\", content: \"nonsense\" }"
+        );
+        assert_eq!(
+            part("simon@[192.168.2.256]"),
+            "GeneralLiteral { tag: \"\", content: \"192.168.2.256\" }"
+        );
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_10() {
-        is_valid(
-            "example@s.example",
-            Some("see the List of Internet top-level domains"),
+    fn test_local_part() {
+        let kind = |local| LocalPart::from_str(local).unwrap().kind();
+        assert_eq!(kind("simon"), LocalPartKind::DotAtom);
+        assert_eq!(kind("simon.johnston+tag"), LocalPartKind::DotAtom);
+        assert_eq!(kind("\"simon johnston\""), LocalPartKind::QuotedString);
+        assert_eq!(kind("\"simon\\\"johnston\""), LocalPartKind::QuotedString);
+        let options = Options::from(Profile::Rfc5322);
+        let local = LocalPart::parse_with("\"simon johnston\".sj", &options).unwrap();
+        assert_eq!(local.kind(), LocalPartKind::Obsolete);
+        let local = LocalPart::parse_with("sj.\"simon\"", &options).unwrap();
+        assert_eq!(local.kind(), LocalPartKind::Obsolete);
+        assert_eq!(
+            LocalPart::from_str("simon johnston"),
+            Err(Error::LocalPartInvalidCharacter(' ', 5))
+        );
+        assert_eq!(
+            LocalPart::from_str(&"a".repeat(65)),
+            Err(Error::LocalPartTooLong)
         );
+        assert_eq!(LocalPart::from_str("simon").unwrap().to_string(), "simon");
+        let email = EmailAddress::from_str("\"simon johnston\"@example.com").unwrap();
+        assert_eq!(email.to_local_part().kind(), LocalPartKind::QuotedString);
+        assert_eq!(email.to_local_part().as_str(), "\"simon johnston\"");
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_11() {
-        is_valid("\" \"@example.org", Some("space between the quotes"));
+    fn test_is_quoted() {
+        let options = Options::from(Profile::Rfc5322);
+        let email = |address| EmailAddress::parse_with(address, &options).unwrap();
+        assert_eq!(
+            email("simon.johnston@example.com").local_part_kind(),
+            LocalPartKind::DotAtom
+        );
+        assert!(!email("simon.johnston@example.com").is_quoted());
+        assert_eq!(
+            email("\"simon@johnston\"@example.com").local_part_kind(),
+            LocalPartKind::QuotedString
+        );
+        assert!(email("\"simon@johnston\"@example.com").is_quoted());
+        assert_eq!(
+            email("\"simon\".johnston@example.com").local_part_kind(),
+            LocalPartKind::Obsolete
+        );
+        assert!(email("\"simon\".johnston@example.com").is_quoted());
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_12() {
-        is_valid("\"john..doe\"@example.org", Some("quoted double dot"));
+    fn test_local_part_unescaped() {
+        let options = Options::from(Profile::Rfc5322);
+        let unescaped = |address| {
+            EmailAddress::parse_with(address, &options)
+                .unwrap()
+                .local_part_unescaped()
+                .into_owned()
+        };
+        assert!(matches!(
+            EmailAddress::from_str("simon@example.com")
+                .unwrap()
+                .local_part_unescaped(),
+            Cow::Borrowed("simon")
+        ));
+        assert_eq!(unescaped(r#""Joe.\\Blow"@example.com"#), r"Joe.\Blow");
+        assert_eq!(unescaped(r#""Abc@def"@example.com"#), "Abc@def");
+        assert_eq!(unescaped(r#""Fred\ Bloggs"@example.com"#), "Fred Bloggs");
+        assert_eq!(unescaped(r#""\"quoted\""@example.com"#), r#""quoted""#);
+        assert_eq!(
+            unescaped(r#""simon smith".sj@example.com"#),
+            "simon smith.sj"
+        );
+        assert_eq!(unescaped("\"simon\r\n smith\"@example.com"), "simon smith");
+        assert_eq!(
+            LocalPart::from_str(r#""a\"b""#).unwrap().unescaped(),
+            r#"a"b"#
+        );
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_13() {
-        is_valid(
-            "mailhost!username@example.org",
-            Some("bangified host route used for uucp mailers"),
+    fn test_minimally_quoted() {
+        let options = Options::from(Profile::Rfc5322);
+        let minimal = |address| {
+            let minimal = EmailAddress::parse_with(address, &options)
+                .unwrap()
+                .minimally_quoted();
+            assert!(EmailAddress::is_valid(minimal.as_str()));
+            minimal.to_string()
+        };
+        assert_eq!(minimal("john.doe@x.com"), "john.doe@x.com");
+        assert_eq!(minimal("\"john.doe\"@x.com"), "john.doe@x.com");
+        assert_eq!(minimal(r#""j\o\h\n"@x.com"#), "john@x.com");
+        assert_eq!(minimal("\"john\".doe@x.com"), "john.doe@x.com");
+        assert_eq!(minimal("\"john doe\"@x.com"), "\"john doe\"@x.com");
+        assert_eq!(minimal(r#""john\ doe"@x.com"#), "\"john doe\"@x.com");
+        assert_eq!(minimal("\"john doe\".jd@x.com"), "\"john doe.jd\"@x.com");
+        assert_eq!(minimal(r#""john\\doe"@x.com"#), r#""john\\doe"@x.com"#);
+        assert_eq!(minimal(r#""john\"doe"@x.com"#), r#""john\"doe"@x.com"#);
+        assert_eq!(minimal("\"john..doe\"@x.com"), "\"john..doe\"@x.com");
+        assert_eq!(minimal("\".john\"@x.com"), "\".john\"@x.com");
+        for address in [
+            "\"a\\\u{0}b\"@x.com",
+            "\"a\\\r\\\nb\"@x.com",
+            "\"a\\\u{7f}b\".c@x.com",
+        ] {
+            let email = EmailAddress::parse_with(address, &options).unwrap();
+            let minimal = email.minimally_quoted();
+            assert_eq!(
+                EmailAddress::parse_with(minimal.as_str(), &options),
+                Ok(minimal.clone()),
+                "{:?}",
+                address
+            );
+            assert_eq!(minimal.local_part_unescaped(), email.local_part_unescaped());
+        }
+        assert_eq!(minimal("\"a\tb\"@x.com"), "\"a\tb\"@x.com");
+        assert_eq!(
+            EmailAddress::from_str("\"jöhn\"@x.com")
+                .unwrap()
+                .minimally_quoted()
+                .to_string(),
+            "jöhn@x.com"
+        );
+        assert_eq!(
+            LocalPart::from_str("\"john\"")
+                .unwrap()
+                .minimally_quoted()
+                .as_str(),
+            "john"
         );
     }
 
+    #[cfg(feature = "normalize")]
     #[test]
-    fn test_good_examples_from_wikipedia_14() {
-        is_valid(
-            "user%example.com@example.org",
-            Some("% escaped mail route to user@example.com via example.org"),
+    fn test_normalized() {
+        let normalized = |address, case| {
+            EmailAddress::from_str(address)
+                .unwrap()
+                .normalized(case)
+                .to_string()
+        };
+        use LocalPartCase::{Lowercase, Preserve};
+        assert_eq!(
+            normalized("Simon@Example.COM", Preserve),
+            "Simon@example.com"
+        );
+        assert_eq!(
+            normalized("Simon@Example.COM", Lowercase),
+            "simon@example.com"
+        );
+        assert_eq!(
+            normalized("\"Simon\"@example.com", Preserve),
+            "Simon@example.com"
+        );
+        assert_eq!(
+            normalized("\"Simon Johnston\"@example.com", Lowercase),
+            "\"simon johnston\"@example.com"
+        );
+        assert_eq!(
+            normalized("jose\u{301}@Bu\u{308}cher.example", Preserve),
+            "jos\u{e9}@b\u{fc}cher.example"
+        );
+        assert_eq!(
+            normalized("\"jose\\\u{301}\"@example.com", Preserve),
+            "jos\u{e9}@example.com"
+        );
+        assert_eq!(
+            normalized("S\u{130}MON@example.com", Lowercase),
+            "si\u{307}mon@example.com"
         );
+        assert_eq!(normalized("simon@[IPv6:::1]", Preserve), "simon@[ipv6:::1]");
+        let a = EmailAddress::from_str("\"José\"@EXAMPLE.com").unwrap();
+        let b = EmailAddress::from_str("jose\u{301}@example.com").unwrap();
+        assert_eq!(a.normalized(Lowercase), b.normalized(Lowercase));
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_15() {
-        is_valid("jsmith@[192.168.2.1]", None);
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_16() {
-        is_valid("jsmith@[IPv6:2001:db8::1]", None);
+    unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            std::alloc::System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+            std::alloc::System.dealloc(ptr, layout)
+        }
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_17() {
-        is_valid("user+mailbox/department=shipping@example.com", None);
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations<F: FnOnce() -> bool>(f: F) -> usize {
+        let before = ALLOCATIONS.with(|count| count.get());
+        assert!(std::hint::black_box(f()));
+        ALLOCATIONS.with(|count| count.get()) - before
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_18() {
-        is_valid("!#$%&'*+-/=?^_`.{|}~@example.com", None);
+    fn test_is_valid_does_not_allocate() {
+        #[cfg(feature = "compact_str")]
+        assert_eq!(
+            allocations(|| EmailAddress::from_str("simon@example.com").is_ok()),
+            0
+        );
+        for address in [
+            "simon@example.com",
+            "<simon@example.com>",
+            "\"simon johnston\"@example.com",
+            "simon@[IPv6:2001:db8::1]",
+            "sïmon@exämple.com",
+        ] {
+            assert_eq!(
+                allocations(|| EmailAddress::is_valid(address)),
+                0,
+                "{}",
+                address
+            );
+        }
+        for address in ["simon", "simon@exa mple.com", &"a".repeat(300)] {
+            assert_eq!(
+                allocations(|| !EmailAddress::is_valid(address)),
+                0,
+                "{}",
+                address
+            );
+        }
+        assert!(allocations(|| EmailAddress::from_str(&"a".repeat(30)).is_err()) > 0);
+        assert!(
+            allocations(|| EmailAddress::from_str("simon.johnston@mail.example.com").is_ok()) > 0
+        );
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_19() {
-        // '@' is allowed in a quoted local part. Sorry.
-        is_valid("\"Abc@def\"@example.com", None);
+    fn test_ascii_characters() {
+        let atext = "!#$%&'*+-/=?^_`{|}~";
+        for c in (1u8..128).map(char::from) {
+            let expected = c.is_ascii_alphanumeric() || atext.contains(c);
+            assert_eq!(
+                EmailAddress::is_valid(&format!("a{}b@example.com", c)),
+                expected || c == '.',
+                "{:?} in local part",
+                c
+            );
+            assert_eq!(
+                EmailAddress::is_valid(&format!("ab@exa{}mple.com", c)),
+                expected || c == '.',
+                "{:?} in domain",
+                c
+            );
+            let options = Options::default().with_ldh_domain();
+            assert_eq!(
+                EmailAddress::parse_with(&format!("ab@exa{}mple.com", c), &options).is_ok(),
+                c.is_ascii_alphanumeric() || c == '-' || c == '.',
+                "{:?} in LDH domain",
+                c
+            );
+        }
     }
 
-    #[test]
-    fn test_good_examples_from_wikipedia_20() {
-        is_valid("\"Joe.\\\\Blow\"@example.com", None);
+    ///
+    /// Addresses that the `Rfc5322` profile accepts, or rejects, differently from is_email, with
+    /// the reason for each.
+    ///
+    const ISEMAIL_DIVERGENCES: &[(&str, &str)] = &[
+        (
+            "test@-iana.org",
+            "RFC 5322 allows a leading hyphen in an atom; only `Options::with_ldh_domain` applies \
+             the host name rule of RFC 1123",
+        ),
+        (
+            "test@iana-.com",
+            "RFC 5322 allows a trailing hyphen in an atom",
+        ),
+        (
+            "test@iana.org-",
+            "RFC 5322 allows a trailing hyphen in an atom",
+        ),
+        (
+            "\"\"@iana.org",
+            "an empty quoted string leaves no mailbox name, and is rejected as `LocalPartEmpty`",
+        ),
+    ];
+
+    ///
+    /// Returns the address, category, and diagnosis of each test in an is_email test file.
+    ///
+    fn isemail_tests(xml: &str) -> Vec<(String, String, String)> {
+        fn element<'a>(test: &'a str, name: &str) -> &'a str {
+            let start = test.find(&format!("<{}>", name)).unwrap() + name.len() + 2;
+            let end = test.find(&format!("</{}>", name)).unwrap();
+            &test[start..end]
+        }
+        fn unescape(text: &str) -> String {
+            let mut unescaped = String::new();
+            let mut rest = text;
+            while let Some(start) = rest.find('&') {
+                unescaped.push_str(&rest[..start]);
+                let end = start + rest[start..].find(';').unwrap();
+                unescaped.push(match &rest[start + 1..end] {
+                    "amp" => '&',
+                    "lt" => '<',
+                    "gt" => '>',
+                    "quot" => '"',
+                    "apos" => '\'',
+                    entity => {
+                        let code = u32::from_str_radix(entity.trim_start_matches("#x"), 16);
+                        char::from_u32(code.unwrap()).unwrap()
+                    }
+                });
+                rest = &rest[end + 1..];
+            }
+            unescaped.push_str(rest);
+            unescaped
+                .chars()
+                .map(|c| match c {
+                    '\u{2400}'..='\u{241F}' => char::from_u32(c as u32 - 0x2400).unwrap(),
+                    '\u{2421}' => '\x7F',
+                    _ => c,
+                })
+                .collect()
+        }
+        xml.split("<test ")
+            .skip(1)
+            .map(|test| {
+                (
+                    unescape(element(test, "address")),
+                    element(test, "category").to_string(),
+                    element(test, "diagnosis").to_string(),
+                )
+            })
+            .collect()
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_21() {
-        is_valid("用户@例子.广告", Some("Chinese"));
+    fn test_isemail_corpus() {
+        let options = Options::from(Profile::Rfc5322);
+        let mut unexpected = Vec::new();
+        for (address, category, diagnosis) in isemail_tests(include_str!("../tests/isemail.xml")) {
+            let expected = category != "ISEMAIL_ERR";
+            let accepted = EmailAddress::parse_with(&address, &options).is_ok();
+            let divergent = ISEMAIL_DIVERGENCES.iter().any(|(a, _)| *a == address);
+            if (accepted == expected) == divergent {
+                unexpected.push(format!("{:?} {} {}", address, diagnosis, accepted));
+            }
+        }
+        assert!(unexpected.is_empty(), "{:#?}", unexpected);
     }
 
+    #[cfg(feature = "macros")]
     #[test]
-    fn test_good_examples_from_wikipedia_22() {
-        is_valid("अजय@डाटा.भारत", Some("Hindi"));
+    fn test_email_macro() {
+        assert_eq!(
+            email!("simon@example.com"),
+            EmailAddress::from_str("simon@example.com").unwrap()
+        );
+        assert_eq!(
+            email!("<\"simon\"@[IPv6:::1]>"),
+            EmailAddress::from_str("<\"simon\"@[IPv6:::1]>").unwrap()
+        );
+        assert_eq!(email!("jöran@example.com").local_part(), "jöran");
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_23() {
-        is_valid("квіточка@пошта.укр", Some("Ukranian"));
+    fn test_is_valid_ascii() {
+        const _: () = assert!(EmailAddress::is_valid_ascii("simon@example.com"));
+        for address in [
+            "simon@example.com",
+            "user.name+tag+sorting@example.com",
+            "!#$%&'*+/=?^_`{|}~-@localhost",
+            "a@b",
+            "simon@123.example",
+            "x-y@a-b.c-d",
+        ] {
+            assert!(EmailAddress::is_valid_ascii(address), "{:?}", address);
+            assert!(EmailAddress::is_valid(address), "{:?}", address);
+        }
+        for address in [
+            "",
+            "@example.com",
+            "simon@",
+            "simon",
+            ".simon@example.com",
+            "simon.@example.com",
+            "si..mon@example.com",
+            "simon@example..com",
+            "simon@example.com.",
+            "simon@-example.com",
+            "simon@example-.com",
+            "simon@exa_mple.com",
+            "simon@example@com",
+            "\"simon\"@example.com",
+            "simon@[127.0.0.1]",
+            "jöran@example.com",
+            "simon@bücher.example",
+        ] {
+            assert!(!EmailAddress::is_valid_ascii(address), "{:?}", address);
+        }
+        let local = "a".repeat(64);
+        let label = "b".repeat(63);
+        assert!(EmailAddress::is_valid_ascii(&format!(
+            "{}@{}",
+            local, label
+        )));
+        assert!(!EmailAddress::is_valid_ascii(&format!(
+            "a{}@{}",
+            local, label
+        )));
+        assert!(!EmailAddress::is_valid_ascii(&format!(
+            "{}@b{}",
+            local, label
+        )));
+        let domain = [label.as_str(); 4].join(".");
+        assert!(!EmailAddress::is_valid_ascii(&format!("a@{}", domain)));
+        assert!(!EmailAddress::is_valid(&format!("a@{}", domain)));
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_24() {
-        is_valid("θσερ@εχαμπλε.ψομ", Some("Greek"));
+    fn test_validate_bulk() {
+        let addresses = vec![
+            "simon@example.com".to_string(),
+            "simon".to_string(),
+            "simon@".to_string(),
+            "<simon@example.org>".to_string(),
+        ];
+        let results = EmailAddress::validate_bulk(&addresses, &Options::default());
+        assert_eq!(
+            results,
+            vec![
+                (0, EmailAddress::from_str("simon@example.com")),
+                (1, Err(Error::MissingSeparator)),
+                (2, Err(Error::DomainEmpty)),
+                (3, EmailAddress::from_str("simon@example.org")),
+            ]
+        );
+        assert!(EmailAddress::validate_bulk(Vec::<&str>::new(), &Options::default()).is_empty());
     }
 
+    #[cfg(feature = "rayon")]
     #[test]
-    fn test_good_examples_from_wikipedia_25() {
-        is_valid("Dörte@Sörensen.example.com", Some("German"));
+    fn test_par_validate_bulk() {
+        let addresses: Vec<String> = (0..10_000)
+            .map(|i| {
+                if i % 3 == 0 {
+                    format!("user{}", i)
+                } else {
+                    format!("user{}@example.com", i)
+                }
+            })
+            .collect();
+        let options = Options::default();
+        assert_eq!(
+            EmailAddress::par_validate_bulk(&addresses, &options),
+            EmailAddress::validate_bulk(&addresses, &options)
+        );
     }
 
     #[test]
-    fn test_good_examples_from_wikipedia_26() {
-        is_valid("коля@пример.рф", Some("Russian"));
+    fn test_interner() {
+        let mut interner = Interner::default();
+        assert!(interner.is_empty());
+        let addresses: Vec<InternedAddress> = (0..1000)
+            .map(|i| {
+                interner
+                    .parse(&format!("user{}@example{}.com", i, i % 3))
+                    .unwrap()
+            })
+            .collect();
+        assert_eq!(interner.len(), 3);
+        assert!(Arc::ptr_eq(
+            addresses[0].shared_domain(),
+            addresses[999].shared_domain()
+        ));
+        assert!(!Arc::ptr_eq(
+            addresses[0].shared_domain(),
+            addresses[1].shared_domain()
+        ));
+        assert_eq!(addresses[1].local_part(), "user1");
+        assert_eq!(addresses[1].domain(), "example1.com");
+        assert_eq!(addresses[1].to_string(), "user1@example1.com");
+        assert_eq!(
+            EmailAddress::from(addresses[1].clone()),
+            EmailAddress::from_str("user1@example1.com").unwrap()
+        );
+        interner.parse("user@EXAMPLE0.com").unwrap();
+        assert_eq!(interner.len(), 4);
+        assert_eq!(interner.parse("user"), Err(Error::MissingSeparator));
+        let mut interner = Interner::default().with_options(Options::default().without_utf8());
+        assert!(interner.parse("jöhn@example.com").is_err());
+        interner.clear();
+        assert!(interner.is_empty());
     }
 
-    // ------------------------------------------------------------------------------------------------
-
-    fn expect(address: &str, error: Error, test_case: Option<&str>) {
-        if let Some(test_case) = test_case {
-            println!(">> test case: {}", test_case);
-            println!("     <{}>, expecting {:?}", address, error);
-        } else {
-            println!(">> <{}>, expecting {:?}", address, error);
+    #[test]
+    fn test_tag() {
+        let email = |address| EmailAddress::from_str(address).unwrap();
+        assert_eq!(email("simon+news@example.com").tag(), Some("news"));
+        assert_eq!(
+            email("simon+news+daily@example.com").tag(),
+            Some("news+daily")
+        );
+        assert_eq!(email("simon+@example.com").tag(), Some(""));
+        assert_eq!(email("simon@example.com").tag(), None);
+        assert_eq!(email("+news@example.com").tag(), None);
+        assert_eq!(email("\"simon+news\"@example.com").tag(), None);
+        assert_eq!(email("a.+tag@example.com").tag(), None);
+        assert_eq!(email("a.b+.tag@example.com").tag(), Some(".tag"));
+        for address in ["a.+tag@example.com", "a.-tag@example.com"] {
+            let without = email(address).without_tag_with(&['+', '-']);
+            assert_eq!(without, email(address));
+            assert!(EmailAddress::is_valid(without.as_str()));
         }
-        assert_eq!(EmailAddress::from_str(address), error.into());
+        assert_eq!(
+            Equivalence::IgnoreTags.key(&email("a.+tag@example.com")),
+            email("a.+tag@example.com")
+        );
+        assert_eq!(
+            email("simon+news+daily@example.com").without_tag(),
+            email("simon@example.com")
+        );
+        assert_eq!(
+            email("+news@example.com").without_tag(),
+            email("+news@example.com")
+        );
+        assert!(email("simon+news@Example.com").matches_ignoring_tag(&email("simon@example.com")));
+        assert!(email("simon+a@example.com").matches_ignoring_tag(&email("simon+b@example.com")));
+        assert!(!email("simon+a@example.com").matches_ignoring_tag(&email("simone@example.com")));
+        assert!(!email("simon@example.com").matches_ignoring_tag(&email("simon@example.org")));
+        let separators = &['-', '='];
+        assert_eq!(
+            email("simon-news@example.com").tag_with(separators),
+            Some("news")
+        );
+        assert_eq!(
+            email("simon=a-b@example.com").tag_with(separators),
+            Some("a-b")
+        );
+        assert_eq!(email("simon+news@example.com").tag_with(separators), None);
+        assert_eq!(email("-news@example.com").tag_with(separators), None);
+        assert_eq!(email("simon-news@example.com").tag_with(&[]), None);
+        assert_eq!(
+            email("simon=news@example.com").without_tag_with(separators),
+            email("simon@example.com")
+        );
+        assert!(email("simon-a@example.com")
+            .matches_ignoring_tag_with(&email("simon=b@example.com"), separators));
+        assert!(!email("simon-a@example.com").matches_ignoring_tag(&email("simon-b@example.com")));
     }
 
     #[test]
-    fn test_bad_examples_from_wikipedia_00() {
-        expect(
-            "Abc.example.com",
-            Error::MissingSeparator,
-            Some("no @ character"),
+    fn test_batv() {
+        let batv = |address| EmailAddress::from_str(address).unwrap().batv();
+        let parsed = batv("PRVS=9999ABCDEF=simon+news@example.com").unwrap();
+        assert_eq!(parsed.key_number(), 9);
+        assert_eq!(parsed.expiry_day(), 999);
+        assert_eq!(parsed.hash(), "abcdef");
+        assert_eq!(
+            parsed.into_address(),
+            EmailAddress::from_str("simon+news@example.com").unwrap()
         );
+        assert_eq!(batv("simon@example.com"), None);
+        assert_eq!(batv("prvs=0123a1b2c3@example.com"), None);
+        assert_eq!(batv("prvs=0123a1b2c3=@example.com"), None);
+        assert_eq!(batv("prvs=0123a1b2=simon@example.com"), None);
+        assert_eq!(batv("prvs=x123a1b2c3=simon@example.com"), None);
+        assert_eq!(batv("prvs=012xa1b2c3=simon@example.com"), None);
+        assert_eq!(batv("prvs=0123a1b2cg=simon@example.com"), None);
+        assert_eq!(batv("btv1=0123a1b2c3=simon@example.com"), None);
     }
 
+    #[cfg(feature = "batv")]
     #[test]
-    fn test_bad_examples_from_wikipedia_01() {
-        expect(
-            "A@b@c@example.com",
-            Error::InvalidCharacter,
-            Some("only one @ is allowed outside quotation marks"),
+    fn test_batv_sign() {
+        let email = EmailAddress::from_str("simon@example.com").unwrap();
+        let signed = Batv::sign(&email, 1, 20_999, b"secret").unwrap();
+        assert!(signed.to_string().starts_with("prvs=1999"));
+        let batv = signed.batv().unwrap();
+        assert!(batv.verify(b"secret", 20_999));
+        assert!(batv.verify(b"secret", 20_990));
+        assert!(batv.verify(b"secret", 20_999 - Batv::MAX_LIFETIME));
+        assert!(!batv.verify(b"secret", 20_998 - Batv::MAX_LIFETIME));
+        assert!(!batv.verify(b"secret", 21_000));
+        assert!(!batv.verify(b"wrong", 20_990));
+        let tampered = EmailAddress::from_str(&signed.to_string().replace("simon", "simone"))
+            .unwrap()
+            .batv()
+            .unwrap();
+        assert!(!tampered.verify(b"secret", 20_990));
+        let long = EmailAddress::from_str(&format!("{}@example.com", "a".repeat(60))).unwrap();
+        assert_eq!(
+            Batv::sign(&long, 0, 0, b"secret"),
+            Err(Error::LocalPartTooLong)
         );
     }
 
     #[test]
-    fn test_bad_examples_from_wikipedia_02() {
-        expect("a\"b(c)d,e:f;g<h>i[j\\k]l@example.com",
-            Error::InvalidCharacter,
-        Some("none of the special characters in this local-part are allowed outside quotation marks")
+    fn test_canonicalize() {
+        let canonical = |address| {
+            EmailAddress::from_str(address)
+                .unwrap()
+                .canonicalize(ProviderRule::built_in())
+                .to_string()
+        };
+        assert_eq!(
+            canonical("S.J.ohnston+a+b@GMail.com"),
+            "sjohnston@gmail.com"
+        );
+        assert_eq!(canonical("sjohnston@googlemail.com"), "sjohnston@gmail.com");
+        assert_eq!(
+            canonical("S.Johnston+news@outlook.com"),
+            "s.johnston@outlook.com"
+        );
+        assert_eq!(canonical("sjohnston-news@yahoo.com"), "sjohnston@yahoo.com");
+        assert_eq!(
+            canonical("sjohnston+news@yahoo.com"),
+            "sjohnston+news@yahoo.com"
+        );
+        assert_eq!(
+            canonical("S.Johnston+news@example.com"),
+            "S.Johnston+news@example.com"
+        );
+        assert_eq!(canonical("+news@gmail.com"), "+news@gmail.com");
+        for address in ["a.+x@outlook.com", "a.-x@yahoo.com"] {
+            assert_eq!(canonical(address), address);
+        }
+        assert_eq!(canonical("a.b.+x@gmail.com"), "ab@gmail.com");
+        assert_eq!(canonical("\"s.j+x\"@gmail.com"), "\"s.j+x\"@gmail.com");
+        let rules = vec![ProviderRule::new(vec!["example.com"])
+            .with_tag_separator('+')
+            .with_tag_separator('=')
+            .without_lowercase()];
+        let email = EmailAddress::from_str("S.J=news@Example.com").unwrap();
+        assert_eq!(email.canonicalize(&rules).to_string(), "S.J@example.com");
+
+        struct Catchall;
+        impl ProviderRules for Catchall {
+            fn canonicalize(&self, _: &str, domain: &str) -> Option<(String, String)> {
+                Some(("postmaster".to_string(), domain.to_string()))
+            }
+        }
+        assert_eq!(
+            email.canonicalize(&Catchall).to_string(),
+            "postmaster@Example.com"
         );
+
+        struct Broken;
+        impl ProviderRules for Broken {
+            fn canonicalize(&self, _: &str, domain: &str) -> Option<(String, String)> {
+                Some(("a.".to_string(), domain.to_string()))
+            }
+        }
+        assert_eq!(email.canonicalize(&Broken), email);
     }
 
     #[test]
-    fn test_bad_examples_from_wikipedia_03() {
-        expect(
-            "just\"not\"right@example.com",
-            Error::InvalidCharacter,
-            Some(
-                "quoted strings must be dot separated or the only element making up the local-part",
-            ),
-        );
+    fn test_domain_list() {
+        let mut list = DomainList::from_str("# comment\n\n example.com \nEXAMPLE.org.\n").unwrap();
+        assert_eq!(list.len(), 2);
+        assert!(list.contains("example.com"));
+        assert!(list.contains("example.org"));
+        assert!(list.contains("mail.Example.Com."));
+        assert!(!list.contains("com"));
+        assert!(!list.contains("myexample.com"));
+        assert!(list.insert("example.net"));
+        assert!(!list.insert("Example.net"));
+        assert!(list.remove("example.com"));
+        assert!(!list.contains("mail.example.com"));
+        assert!(list.matches(&EmailAddress::from_str("simon@example.net").unwrap()));
+        let list: DomainList = vec!["example.com"].into_iter().collect();
+        assert_eq!(list, DomainList::default().with_domain("example.com"));
     }
 
     #[test]
-    fn test_bad_examples_from_wikipedia_04() {
-        expect("this is\"not\\allowed@example.com",
-            Error::InvalidCharacter,
-        Some("spaces, quotes, and backslashes may only exist when within quoted strings and preceded by a backslash")
+    fn test_domain_suffix_set() {
+        let mut set =
+            DomainSuffixSet::from_str("# comment\n\n example.com \nEXAMPLE.org.\n").unwrap();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains("example.com"));
+        assert!(set.contains("example.org"));
+        assert!(set.contains("mail.Example.Com."));
+        assert!(!set.contains("com"));
+        assert!(!set.contains("myexample.com"));
+        assert!(set.insert("example.net"));
+        assert!(!set.insert("Example.net"));
+        assert!(set.insert("mail.example.com"));
+        assert_eq!(set.len(), 4);
+        assert!(set.matches(&EmailAddress::from_str("simon@example.net").unwrap()));
+        assert!(!set.matches(&EmailAddress::from_str("simon@[192.168.2.1]").unwrap()));
+        let list = DomainList::default().with_domain("example.com");
+        assert_eq!(
+            DomainSuffixSet::from(&list),
+            DomainSuffixSet::default().with_domain("example.com")
         );
+        let set: DomainSuffixSet = (0..5000).map(|i| format!("d{}.example", i)).collect();
+        assert_eq!(set.len(), 5000);
+        assert!(set.contains("mail.d4999.example"));
+        assert!(!set.contains("d5000.example"));
     }
 
+    #[cfg(feature = "disposable")]
     #[test]
-    fn test_bad_examples_from_wikipedia_05() {
-        // ()
-        expect("this\\ still\"not\\allowed@example.com",
-            Error::InvalidCharacter,
-        Some("even if escaped (preceded by a backslash), spaces, quotes, and backslashes must still be contained by quotes")
-        );
+    fn test_is_disposable() {
+        let disposable = |address| EmailAddress::from_str(address).unwrap().is_disposable();
+        assert!(disposable("simon@mailinator.com"));
+        assert!(disposable("simon@YopMail.com"));
+        assert!(disposable("simon@sub.guerrillamail.com"));
+        assert!(!disposable("simon@gmail.com"));
+        assert!(!disposable("simon@[192.168.2.1]"));
+        assert!(!DomainList::disposable().is_empty());
     }
 
+    #[cfg(feature = "free_provider")]
     #[test]
-    fn test_bad_examples_from_wikipedia_06() {
-        expect(
-            "1234567890123456789012345678901234567890123456789012345678901234+x@example.com",
-            Error::LocalPartTooLong,
-            Some("local part is longer than 64 characters"),
-        );
+    fn test_is_free_provider() {
+        let free = |address| EmailAddress::from_str(address).unwrap().is_free_provider();
+        assert!(free("simon@gmail.com"));
+        assert!(free("simon@Yahoo.co.uk"));
+        assert!(free("simon@proton.me"));
+        assert!(!free("simon@example.com"));
+        assert!(!free("simon@mailinator.com"));
+        assert!(!free("simon@[192.168.2.1]"));
     }
 
+    #[cfg(feature = "psl")]
     #[test]
-    fn test_bad_example_01() {
-        expect(
-            "foo@example.v1234567890123456789012345678901234567890123456789012345678901234v.com",
-            Error::SubDomainTooLong,
-            Some("domain part is longer than 64 characters"),
-        );
+    fn test_public_suffix() {
+        let email = |address| EmailAddress::from_str(address).unwrap();
+        let simon = email("simon@example.com");
+        assert_eq!(simon.public_suffix(), Some("com"));
+        assert_eq!(simon.registrable_domain(), Some("example.com"));
+        let simon = email("simon@Mail.Corp.CO.UK");
+        assert_eq!(simon.public_suffix(), Some("CO.UK"));
+        assert_eq!(simon.registrable_domain(), Some("Corp.CO.UK"));
+        let simon = email("simon@simon.github.io");
+        assert_eq!(simon.public_suffix(), Some("github.io"));
+        assert_eq!(simon.registrable_domain(), Some("simon.github.io"));
+        let simon = email("simon@co.uk");
+        assert_eq!(simon.public_suffix(), Some("co.uk"));
+        assert_eq!(simon.registrable_domain(), None);
+        let simon = email("simon@example.unlisted");
+        assert_eq!(simon.public_suffix(), Some("unlisted"));
+        assert_eq!(simon.registrable_domain(), Some("example.unlisted"));
+        let simon = email("simon@[192.168.2.1]");
+        assert_eq!(simon.public_suffix(), None);
+        assert_eq!(simon.registrable_domain(), None);
     }
 
+    #[cfg(feature = "psl")]
     #[test]
-    fn test_bad_example_02() {
-        expect(
-            "@example.com",
-            Error::LocalPartEmpty,
-            Some("local-part is empty"),
-        );
+    fn test_aligned_with() {
+        let email = |address| EmailAddress::from_str(address).unwrap();
+        let aligned = |a, b, mode| email(a).aligned_with(&email(b), mode);
+        use AlignmentMode::{Relaxed, Strict};
+        assert!(aligned("a@example.com", "b@EXAMPLE.com", Strict));
+        assert!(aligned("a@example.com", "b@example.com", Relaxed));
+        assert!(!aligned("a@example.com", "b@mail.example.com", Strict));
+        assert!(aligned("a@example.com", "b@mail.example.com", Relaxed));
+        assert!(aligned("a@news.example.com", "b@mail.example.com", Relaxed));
+        assert!(!aligned("a@example.com", "b@example.org", Relaxed));
+        assert!(aligned("a@corp.co.uk", "b@mail.corp.co.uk", Relaxed));
+        assert!(!aligned("a@one.co.uk", "b@two.co.uk", Relaxed));
+        assert!(!aligned("a@one.github.io", "b@two.github.io", Relaxed));
+        assert!(aligned("a@co.uk", "b@co.uk", Relaxed));
+        assert!(!aligned("a@[192.168.2.1]", "b@[192.168.2.1]", Strict));
     }
 
     #[test]
-    fn test_bad_example_03() {
-        expect(
-            "\"\"@example.com",
-            Error::LocalPartEmpty,
-            Some("local-part is empty"),
+    fn test_suggester() {
+        let suggester = Suggester::default().with_domain("Example-Corp.com");
+        assert_eq!(
+            suggester.suggest_domain("example-crop.com"),
+            Some("example-corp.com")
         );
+        assert_eq!(suggester.suggest_domain("gmial.com"), Some("gmail.com"));
+        assert_eq!(suggester.suggest_domain("example-corp.com"), None);
+
+        let suggester = Suggester::default()
+            .without_provider_domains()
+            .with_domains(vec!["acme.io", "acme.com"]);
+        assert_eq!(suggester.suggest_domain("gmial.com"), None);
+        assert_eq!(suggester.suggest_domain("acme.cm"), Some("acme.com"));
+        assert_eq!(suggester.suggest_domain("ac.io"), None);
+        let suggester = suggester.with_max_distance(2);
+        assert_eq!(suggester.suggest_domain("ac.io"), Some("acme.io"));
+        assert_eq!(suggester.suggest_domain("acme.iom"), Some("acme.io"));
+        assert_eq!(suggester.suggest_domain("example.com"), None);
+    }
+
+    fn span(address: &str, options: &Options) -> (Error, Range<usize>) {
+        let error = EmailAddress::parse_spanned(address, options).unwrap_err();
+        (error.error().clone(), error.span())
     }
 
     #[test]
-    fn test_bad_example_04() {
-        expect("simon@example.com.", Error::InvalidCharacter, Some("rooted DNS syntax"));
+    fn test_parse_spanned() {
+        let options = Options::default();
+        assert_eq!(span("simon", &options), (Error::MissingSeparator, 0..5));
+        assert_eq!(span("<simon>", &options), (Error::MissingSeparator, 1..6));
+        assert_eq!(
+            span("@example.com", &options),
+            (Error::LocalPartEmpty, 0..0)
+        );
+        assert_eq!(
+            span("si,mon@example.com", &options),
+            (Error::LocalPartInvalidCharacter(',', 2), 2..3)
+        );
+        assert_eq!(
+            span("<si,mon@example.com>", &options),
+            (Error::LocalPartInvalidCharacter(',', 3), 3..4)
+        );
+        assert_eq!(
+            span("simon.@example.com", &options),
+            (Error::LocalPartInvalidCharacter('.', 5), 5..6)
+        );
+        assert_eq!(
+            span("\"simon@example.com", &options),
+            (Error::UnbalancedQuotes, 0..6)
+        );
+        assert_eq!(span("simon@", &options), (Error::DomainEmpty, 6..6));
+        assert_eq!(
+            span("simon@example..com", &options),
+            (Error::DomainInvalidCharacter('.', 14), 14..15)
+        );
+        assert_eq!(
+            span(&format!("simon@{}.com", "a".repeat(64)), &options),
+            (Error::SubDomainTooLong, 6..74)
+        );
+        assert_eq!(
+            span("simon@[1.2.3]", &Options::from(Profile::Rfc5321)),
+            (Error::InvalidIPAddress, 7..12)
+        );
+        assert_eq!(
+            span("simon@(example.com", &Options::from(Profile::Rfc5322)),
+            (Error::InvalidComment, 6..18)
+        );
     }
 
+    #[cfg(feature = "idna")]
     #[test]
-    fn test_bad_example_05() {
-        expect("simon@", Error::DomainEmpty, Some("domain is empty"));
+    fn test_a_label_lengths() {
+        let options = Options::default().with_a_label_lengths();
+        // 64 octets of UTF-8, but only 38 as an A-label.
+        let shrinks = format!("simon@{}.com", "ö".repeat(32));
+        assert!(EmailAddress::parse_with(&shrinks, &options).is_ok());
+        // 62 octets of UTF-8, but 67 as an A-label.
+        let grows = format!("simon@{}é.com", "a".repeat(60));
+        assert!(EmailAddress::from_str(&grows).is_ok());
+        assert_eq!(
+            EmailAddress::parse_with(&grows, &options),
+            Error::SubDomainTooLong.into()
+        );
     }
 
     // --------------------------------------------------------------------------------------------
@@ -0,0 +1,111 @@
+/*!
+Validation of address lists read one address per line, such as lists exported from a mailing
+list manager or CRM, without loading the whole list into memory.
+*/
+
+use crate::{EmailAddress, Error, Options};
+use std::io::BufRead;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An iterator over the addresses in a `BufRead`, one per line, yielding the line number,
+/// counted from 1, of each address with the result of parsing it. Lines may end with either LF
+/// or CRLF, a UTF-8 byte order mark at the start of the input is ignored, as are empty lines.
+/// An error reading the input is returned, after which the iterator ends.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let input = "\u{FEFF}simon@example.com\r\n\r\nsimon\r\njane@example.org\n";
+/// let invalid: Vec<usize> = AddressLines::new(input.as_bytes())
+///     .map(Result::unwrap)
+///     .filter(|(_, result)| result.is_err())
+///     .map(|(line_number, _)| line_number)
+///     .collect();
+///
+/// assert_eq!(invalid, vec![3]);
+/// ```
+///
+#[derive(Debug)]
+pub struct AddressLines<R> {
+    reader: R,
+    options: Options,
+    line_number: usize,
+    buffer: Vec<u8>,
+    done: bool,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<R: BufRead> Iterator for AddressLines<R> {
+    type Item = std::io::Result<(usize, Result<EmailAddress, Error>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buffer.clear();
+            match self.reader.read_until(LF, &mut self.buffer) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line_number += 1;
+                    let mut line = self.buffer.as_slice();
+                    if self.line_number == 1 {
+                        line = line.strip_prefix(BOM).unwrap_or(line);
+                    }
+                    line = line.strip_suffix(&[LF]).unwrap_or(line);
+                    line = line.strip_suffix(&[CR]).unwrap_or(line);
+                    if !line.is_empty() {
+                        let result = EmailAddress::parse_bytes_with(line, &self.options);
+                        return Some(Ok((self.line_number, result)));
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<R: BufRead> AddressLines<R> {
+    ///
+    /// Read addresses from `reader`, parsing them with the same rules as `FromStr::from_str`.
+    ///
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            options: Options::default(),
+            line_number: 0,
+            buffer: Vec::new(),
+            done: false,
+        }
+    }
+
+    ///
+    /// Parse addresses using the rules selected by `options`, rather than the defaults.
+    ///
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Returns the number of lines read so far.
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const LF: u8 = b'\n';
+
+const CR: u8 = b'\r';
+
+const BOM: &[u8] = b"\xEF\xBB\xBF";
@@ -0,0 +1,256 @@
+/*!
+A list of addresses, such as the value of a `To` or `Cc` header, RFC 5322, §3.4, with the
+addresses separated by commas.
+
+```ebnf
+address-list   = (address *("," address)) / obs-addr-list
+```
+
+Only the `addr-spec` form of each address is supported here, not display names or groups;
+commas within a quoted local part, a comment, or a domain literal do not separate addresses.
+*/
+
+use crate::{EmailAddress, Error, Options, DQUOTE, ESC, LBRACKET, LPAREN, RBRACKET, RPAREN};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An ordered list of addresses, parsed from, and displayed as, a comma-separated list.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let mut list = EmailAddressList::from_str(
+///     "simon@example.com, \"jane, doe\"@example.org,simon@example.com",
+/// )
+/// .unwrap();
+/// assert_eq!(list.len(), 3);
+///
+/// list.dedup();
+/// assert_eq!(list.to_string(), "simon@example.com, \"jane, doe\"@example.org");
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct EmailAddressList(Vec<EmailAddress>);
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for EmailAddressList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, email) in self.0.iter().enumerate() {
+            let separator = if i == 0 { "" } else { DISPLAY_SEPARATOR };
+            write!(f, "{}{}", separator, email)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for EmailAddressList {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl FromIterator<EmailAddress> for EmailAddressList {
+    fn from_iter<I: IntoIterator<Item = EmailAddress>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<EmailAddress> for EmailAddressList {
+    fn extend<I: IntoIterator<Item = EmailAddress>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl IntoIterator for EmailAddressList {
+    type Item = EmailAddress;
+    type IntoIter = std::vec::IntoIter<EmailAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a EmailAddressList {
+    type Item = &'a EmailAddress;
+    type IntoIter = std::slice::Iter<'a, EmailAddress>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl From<Vec<EmailAddress>> for EmailAddressList {
+    fn from(addresses: Vec<EmailAddress>) -> Self {
+        Self(addresses)
+    }
+}
+
+impl From<EmailAddressList> for Vec<EmailAddress> {
+    fn from(list: EmailAddressList) -> Self {
+        list.0
+    }
+}
+
+impl AsRef<[EmailAddress]> for EmailAddressList {
+    fn as_ref(&self) -> &[EmailAddress] {
+        &self.0
+    }
+}
+
+impl EmailAddressList {
+    /// Create an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Parse a comma-separated list, parsing each address using the rules selected by
+    /// `options`; whitespace around each address is ignored, and an empty, or blank, string is
+    /// the empty list. The positions in any error are those of the characters of `list`.
+    ///
+    pub fn parse_with(list: &str, options: &Options) -> Result<Self, Error> {
+        if list.trim().is_empty() {
+            return Ok(Self::new());
+        }
+        split(list)
+            .map(|(start, address)| {
+                let trimmed = address.trim_start();
+                let start = start + address.len() - trimmed.len();
+                EmailAddress::parse_with(trimmed.trim_end(), options).map_err(|e| e.offset(start))
+            })
+            .collect()
+    }
+
+    /// Returns the number of addresses in the list.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list has no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the addresses, in order.
+    pub fn iter(&self) -> std::slice::Iter<'_, EmailAddress> {
+        self.0.iter()
+    }
+
+    /// Returns the addresses as a slice.
+    pub fn as_slice(&self) -> &[EmailAddress] {
+        &self.0
+    }
+
+    /// Add `email` to the end of the list.
+    pub fn push(&mut self, email: EmailAddress) {
+        self.0.push(email)
+    }
+
+    /// Returns `true` if the list has an address equal to `email`.
+    pub fn contains(&self, email: &EmailAddress) -> bool {
+        self.0.contains(email)
+    }
+
+    ///
+    /// Remove every address equal to one earlier in the list, keeping the first of each, and
+    /// the order of those that remain.
+    ///
+    pub fn dedup(&mut self) {
+        self.dedup_by_key(EmailAddress::clone)
+    }
+
+    ///
+    /// Remove every address with the same key as one earlier in the list, keeping the first of
+    /// each, and the order of those that remain.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    /// use std::str::FromStr;
+    ///
+    /// let mut list =
+    ///     EmailAddressList::from_str("simon@example.com, Simon@Example.COM, simon+news@example.com")
+    ///         .unwrap();
+    ///
+    /// list.dedup_by_key(|email| email.to_lowercase());
+    /// assert_eq!(list.len(), 2);
+    ///
+    /// list.dedup_by_key(|email| email.without_tag().to_lowercase());
+    /// assert_eq!(list.to_string(), "simon@example.com");
+    /// ```
+    ///
+    pub fn dedup_by_key<K, F>(&mut self, mut key: F)
+    where
+        K: Eq + Hash,
+        F: FnMut(&EmailAddress) -> K,
+    {
+        let mut seen = HashSet::new();
+        self.0.retain(|email| seen.insert(key(email)));
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the items of a comma-separated list with the offset of each, where a comma is not a
+/// separator within a quoted string, a comment, or a domain literal, or when escaped.
+///
+fn split(list: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut items = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    let mut literal = false;
+    let mut comments = 0_usize;
+    let mut escaped = false;
+    for (index, c) in list.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == ESC {
+            escaped = quoted || comments > 0;
+        } else if quoted {
+            quoted = c != DQUOTE;
+        } else if comments > 0 {
+            match c {
+                LPAREN => comments += 1,
+                RPAREN => comments -= 1,
+                _ => {}
+            }
+        } else if literal {
+            literal = c != RBRACKET;
+        } else {
+            match c {
+                DQUOTE => quoted = true,
+                LPAREN => comments += 1,
+                LBRACKET => literal = true,
+                SEPARATOR => {
+                    items.push((start, &list[start..index]));
+                    start = index + SEPARATOR.len_utf8();
+                }
+                _ => {}
+            }
+        }
+    }
+    items.push((start, &list[start..]));
+    items.into_iter()
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const SEPARATOR: char = ',';
+
+const DISPLAY_SEPARATOR: &str = ", ";
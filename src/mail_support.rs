@@ -0,0 +1,69 @@
+/*!
+Conversions between `EmailAddress` and the address types of the `mail-parser` and `mail-builder`
+crates, so that the addresses of a parsed message can be validated here before being used to
+build another; requires the `mail-parser` or `mail-builder` features.
+
+Conversions to the other crates borrow the address; conversions from them parse the address
+with the same rules as `FromStr::from_str`, and so may fail. Display names are not kept.
+*/
+
+use crate::{EmailAddress, Error};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+#[cfg(feature = "mail-builder")]
+use mail_builder::headers::address::{
+    Address as BuilderAddress, EmailAddress as BuilderEmailAddress,
+};
+#[cfg(feature = "mail-parser")]
+use mail_parser::Addr;
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+#[cfg(feature = "mail-parser")]
+impl<'a> From<&'a EmailAddress> for Addr<'a> {
+    fn from(email: &'a EmailAddress) -> Self {
+        Addr::new(None, email.as_str())
+    }
+}
+
+#[cfg(feature = "mail-parser")]
+impl TryFrom<&Addr<'_>> for EmailAddress {
+    type Error = Error;
+
+    ///
+    /// Parse the address of `addr`; an `Addr` without an address, as `mail-parser` returns for
+    /// a group with no members, is parsed as the empty string.
+    ///
+    fn try_from(addr: &Addr<'_>) -> Result<Self, Self::Error> {
+        EmailAddress::from_str(addr.address().unwrap_or_default())
+    }
+}
+
+#[cfg(feature = "mail-builder")]
+impl<'a> From<&'a EmailAddress> for BuilderEmailAddress<'a> {
+    fn from(email: &'a EmailAddress) -> Self {
+        BuilderEmailAddress {
+            name: None,
+            email: email.as_str().into(),
+        }
+    }
+}
+
+#[cfg(feature = "mail-builder")]
+impl<'a> From<&'a EmailAddress> for BuilderAddress<'a> {
+    fn from(email: &'a EmailAddress) -> Self {
+        BuilderAddress::Address(email.into())
+    }
+}
+
+#[cfg(feature = "mail-builder")]
+impl TryFrom<&BuilderEmailAddress<'_>> for EmailAddress {
+    type Error = Error;
+
+    fn try_from(address: &BuilderEmailAddress<'_>) -> Result<Self, Self::Error> {
+        EmailAddress::from_str(&address.email)
+    }
+}
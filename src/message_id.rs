@@ -0,0 +1,138 @@
+/*!
+Validation of message identifiers, the `msg-id` of the `Message-ID`, `In-Reply-To`, and
+`References` header fields, from RFC 5322, §3.6.4:
+
+```ebnf
+msg-id          =   [CFWS] "<" id-left "@" id-right ">" [CFWS]
+
+id-left         =   dot-atom-text / obs-id-left
+
+id-right        =   dot-atom-text / no-fold-literal / obs-id-right
+
+no-fold-literal =   "[" *dtext "]"
+```
+
+The grammar shares `dot-atom-text` and `dtext` with `addr-spec`, as extended by RFC 6532 to allow
+UTF-8. The obsolete forms, and comments around the angle brackets, are not supported; white space
+around them is ignored.
+*/
+
+use crate::parser::{invalid_dot_atom_text, invalid_dtext};
+use crate::{Error, AT, GT, LBRACKET, LT, RBRACKET};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A valid message identifier, without its angle brackets.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let id = MessageId::from_str("<20240101.1234@mail.example.com>").unwrap();
+/// assert_eq!(id.id_left(), "20240101.1234");
+/// assert_eq!(id.id_right(), "mail.example.com");
+/// assert_eq!(id.to_string(), "<20240101.1234@mail.example.com>");
+///
+/// assert_eq!(
+///     MessageId::from_str("20240101.1234@mail.example.com"),
+///     Err(Error::MissingAngleBrackets)
+/// );
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct MessageId {
+    id: String,
+    at: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for MessageId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}{}", LT, self.id, GT)
+    }
+}
+
+impl FromStr for MessageId {
+    type Err = Error;
+
+    ///
+    /// Parse a `msg-id`; the positions in any error are those of the characters of `s`.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim_start_matches(is_wsp);
+        let start = s.len() - trimmed.len() + LT.len_utf8();
+        let id = trimmed
+            .trim_end_matches(is_wsp)
+            .strip_prefix(LT)
+            .and_then(|id| id.strip_suffix(GT))
+            .ok_or(Error::MissingAngleBrackets)?;
+        let at = id.find(AT).ok_or(Error::MissingSeparator)?;
+        let (left, right) = (&id[..at], &id[at + 1..]);
+
+        if left.is_empty() {
+            return Err(Error::LocalPartEmpty);
+        }
+        if let Some((c, i)) = invalid_dot_atom_text(left) {
+            return Err(Error::LocalPartInvalidCharacter(c, start + i));
+        }
+
+        let start = start + at + 1;
+        if right.is_empty() {
+            return Err(Error::DomainEmpty);
+        }
+        let literal = right
+            .strip_prefix(LBRACKET)
+            .and_then(|literal| literal.strip_suffix(RBRACKET));
+        if let Some(literal) = literal {
+            if let Some((c, i)) = invalid_dtext(literal) {
+                return Err(Error::DomainLiteralInvalidCharacter(c, start + 1 + i));
+            }
+        } else if let Some((c, i)) = invalid_dot_atom_text(right) {
+            return Err(Error::DomainInvalidCharacter(c, start + i));
+        }
+
+        Ok(Self {
+            id: id.to_string(),
+            at,
+        })
+    }
+}
+
+impl AsRef<str> for MessageId {
+    fn as_ref(&self) -> &str {
+        &self.id
+    }
+}
+
+impl MessageId {
+    /// Returns the identifier, without its angle brackets.
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the part of the identifier before the '@'.
+    pub fn id_left(&self) -> &str {
+        &self.id[..self.at]
+    }
+
+    /// Returns the part of the identifier after the '@', a domain or a `no-fold-literal`.
+    pub fn id_right(&self) -> &str {
+        &self.id[self.at + 1..]
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_wsp(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\r' | '\n')
+}
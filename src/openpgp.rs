@@ -0,0 +1,169 @@
+/*!
+Parsing of OpenPGP User IDs, RFC 9580, §5.11, which by convention take the form
+`Name (Comment) <email@host>`, with both the name and the comment optional.
+
+The convention is only loosely followed, so parsing is lenient about everything except the
+address. A User ID that is only an address, with or without angle brackets, is accepted; the
+name may be a quoted string, as in RFC 5322; and the comment is the last parenthesized text
+before the address. The address is validated with the given `Options`.
+*/
+
+use crate::{EmailAddress, Error, Options, DQUOTE, ESC, GT, LPAREN, LT, RPAREN};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An OpenPGP User ID, split into its optional name and comment, and its address.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let user_id = UserId::from_str("Simon Johnston (work) <simon@example.com>").unwrap();
+/// assert_eq!(user_id.name(), Some("Simon Johnston"));
+/// assert_eq!(user_id.comment(), Some("work"));
+/// assert_eq!(user_id.email().domain(), "example.com");
+///
+/// let user_id = UserId::from_str("simon@example.com").unwrap();
+/// assert_eq!(user_id.name(), None);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UserId {
+    name: Option<String>,
+    comment: Option<String>,
+    email: EmailAddress,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for UserId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(name) = &self.name {
+            write!(f, "{} ", name)?;
+        }
+        if let Some(comment) = &self.comment {
+            write!(f, "({}) ", comment)?;
+        }
+        write!(f, "<{}>", self.email)
+    }
+}
+
+impl FromStr for UserId {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl From<UserId> for EmailAddress {
+    fn from(user_id: UserId) -> Self {
+        user_id.email
+    }
+}
+
+impl UserId {
+    ///
+    /// Parse `user_id`, validating its address with the rules selected by `options`.
+    ///
+    pub fn parse_with(user_id: &str, options: &Options) -> Result<Self, Error> {
+        let user_id = user_id.trim();
+        let (rest, address) = match user_id.strip_suffix(GT) {
+            Some(rest) => match rest.rfind(LT) {
+                Some(start) => (&rest[..start], &rest[start + 1..]),
+                None => ("", user_id),
+            },
+            None => ("", user_id),
+        };
+        let email = EmailAddress::parse_with(address, &options.for_unbracketed())?;
+
+        let mut name = rest.trim_end();
+        let mut comment = None;
+        if let Some(inner) = name.strip_suffix(RPAREN) {
+            if let Some(start) = comment_start(inner) {
+                comment = Some(inner[start + 1..].trim().to_string());
+                name = inner[..start].trim_end();
+            }
+        }
+        let name = unquote(name.trim());
+        Ok(Self {
+            name: if name.is_empty() { None } else { Some(name) },
+            comment: comment.filter(|comment| !comment.is_empty()),
+            email,
+        })
+    }
+
+    ///
+    /// Create a User ID from its parts.
+    ///
+    pub fn new(name: Option<&str>, comment: Option<&str>, email: EmailAddress) -> Self {
+        Self {
+            name: name.map(str::to_string),
+            comment: comment.map(str::to_string),
+            email,
+        }
+    }
+
+    /// Returns the name, without any quotes around it, if there is one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the comment, without the parentheses around it, if there is one.
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Returns the address.
+    pub fn email(&self) -> &EmailAddress {
+        &self.email
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns the offset of the '(' that opens the comment that `s` ends within, allowing for
+/// nested parentheses.
+///
+fn comment_start(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in s.char_indices().rev() {
+        match c {
+            RPAREN => depth += 1,
+            LPAREN if depth == 0 => return Some(i),
+            LPAREN => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn unquote(name: &str) -> String {
+    match name
+        .strip_prefix(DQUOTE)
+        .and_then(|name| name.strip_suffix(DQUOTE))
+    {
+        Some(quoted) => {
+            let mut unquoted = String::with_capacity(quoted.len());
+            let mut chars = quoted.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    ESC => unquoted.extend(chars.next()),
+                    c => unquoted.push(c),
+                }
+            }
+            unquoted
+        }
+        None => name.to_string(),
+    }
+}
@@ -0,0 +1,1000 @@
+/*!
+The private implementation of the `addr-spec` grammar; the productions are described in the crate
+documentation. The grammar is parsed by hand, left to right, with the choice of which optional
+productions are allowed (comments, obsolete forms, domain literals, ...) taken from `Options`.
+
+The `local-part` and `domain` returned from a successful parse have any comments and folding white
+space removed, they borrow from the input string unless something had to be removed.
+*/
+
+use crate::{
+    Error, Options, ParseError, Warning, ADDRESS_MAX_LENGTH, AT, CR, DOMAIN_MAX_LENGTH, DOT,
+    DQUOTE, ESC, HTAB, LBRACKET, LF, LOCAL_PART_MAX_LENGTH, LPAREN, RBRACKET, RPAREN, SP,
+    SUB_DOMAIN_MAX_LENGTH, UTF8_START,
+};
+use std::borrow::Cow;
+use std::net::Ipv6Addr;
+use std::ops::Range;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The result of a successful parse of a complete address, with the span of the input each
+/// component was parsed from.
+///
+#[derive(Debug)]
+pub(crate) struct Parsed<'a> {
+    pub(crate) local: Cow<'a, str>,
+    pub(crate) local_span: Range<usize>,
+    pub(crate) domain: Cow<'a, str>,
+    pub(crate) domain_span: Range<usize>,
+}
+
+///
+/// Receives the errors and warnings found while parsing. By default the first error stops the
+/// parse and warnings are ignored; a collector may instead record every error, recovering and
+/// continuing after each, and may record warnings.
+///
+#[derive(Debug, Default)]
+pub(crate) struct Collector {
+    errors: Option<Vec<ParseError>>,
+    warnings: Option<Vec<Warning>>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Parse the address found in the `range` of `input`; the spans of any errors are offsets into
+/// `input`.
+///
+pub(crate) fn parse_address<'a>(
+    input: &'a str,
+    range: Range<usize>,
+    options: &Options,
+    collector: &mut Collector,
+) -> Result<Parsed<'a>, ParseError> {
+    let address = &input[range.clone()];
+    let separator = if options.check_syntax {
+        find_separator(address, options)
+    } else {
+        address.rfind(AT)
+    };
+    let at = match separator {
+        Some(at) => range.start + at,
+        None => return Err(collector.abort(Error::MissingSeparator, range)),
+    };
+    let local_span = range.start..at;
+    let domain_span = at + 1..range.end;
+    if !options.check_syntax {
+        if local_span.is_empty() {
+            collector.fail(Error::LocalPartEmpty, local_span.clone())?;
+        }
+        if domain_span.is_empty() {
+            collector.fail(Error::DomainEmpty, domain_span.clone())?;
+        }
+        return Ok(Parsed {
+            local: Cow::Borrowed(&input[local_span.clone()]),
+            local_span,
+            domain: Cow::Borrowed(&input[domain_span.clone()]),
+            domain_span,
+        });
+    }
+    let local = Parser::new(input, local_span.clone(), options, collector).local_part()?;
+    let domain = Parser::new(input, domain_span.clone(), options, collector).domain()?;
+    Ok(Parsed {
+        local,
+        local_span,
+        domain,
+        domain_span,
+    })
+}
+
+pub(crate) fn parse_local_part<'a>(
+    part: &'a str,
+    options: &Options,
+) -> Result<Cow<'a, str>, ParseError> {
+    if !options.check_syntax {
+        return non_empty(part, Error::LocalPartEmpty);
+    }
+    Parser::new(part, 0..part.len(), options, &mut Collector::default()).local_part()
+}
+
+pub(crate) fn parse_domain<'a>(
+    part: &'a str,
+    options: &Options,
+) -> Result<Cow<'a, str>, ParseError> {
+    if !options.check_syntax {
+        return non_empty(part, Error::DomainEmpty);
+    }
+    Parser::new(part, 0..part.len(), options, &mut Collector::default()).domain()
+}
+
+///
+/// Returns `true` if `address` is an ASCII `dot-atom` local part and a domain of
+/// letter-digit-hyphen labels, separated by '@', within the default length limits.
+///
+pub(crate) const fn is_ascii_address(address: &[u8]) -> bool {
+    let length = address.len();
+    if length > ADDRESS_MAX_LENGTH {
+        return false;
+    }
+    let mut index = 0;
+    let mut start = 0;
+    while index < length && address[index] != b'@' {
+        let b = address[index];
+        if b == b'.' {
+            if index == start {
+                return false;
+            }
+            start = index + 1;
+        } else if !b.is_ascii() || !ATEXT[b as usize] {
+            return false;
+        }
+        index += 1;
+    }
+    if index == length || index == start || index > LOCAL_PART_MAX_LENGTH {
+        return false;
+    }
+    index += 1;
+    if length - index > DOMAIN_MAX_LENGTH {
+        return false;
+    }
+    start = index;
+    while index < length {
+        let b = address[index];
+        if b == b'.' {
+            if !is_ascii_ldh_label(address, start, index) {
+                return false;
+            }
+            start = index + 1;
+        } else if !b.is_ascii() || !LDH[b as usize] {
+            return false;
+        }
+        index += 1;
+    }
+    is_ascii_ldh_label(address, start, length)
+}
+
+///
+/// Returns `true` if `s` is a `dot-atom`, allowing UTF-8 in atoms.
+///
+pub(crate) fn is_dot_atom(s: &str) -> bool {
+    s.split(DOT)
+        .all(|atom| !atom.is_empty() && atom.chars().all(|c| is_ascii_atext(c) || is_uchar(c)))
+}
+
+///
+/// Returns the first character, and its offset, that prevents the non-empty `s` from being a
+/// `dot-atom-text`, allowing UTF-8 in atoms; a dot is returned if it is at either end, or
+/// follows another dot.
+///
+pub(crate) fn invalid_dot_atom_text(s: &str) -> Option<(char, usize)> {
+    let mut previous = DOT;
+    for (i, c) in s.char_indices() {
+        let valid = if c == DOT {
+            previous != DOT && i + 1 < s.len()
+        } else {
+            is_ascii_atext(c) || is_uchar(c)
+        };
+        if !valid {
+            return Some((c, i));
+        }
+        previous = c;
+    }
+    None
+}
+
+///
+/// Returns the first character, and its offset, in `s` that is not `dtext`, allowing UTF-8.
+///
+pub(crate) fn invalid_dtext(s: &str) -> Option<(char, usize)> {
+    s.char_indices()
+        .find(|(_, c)| !(is_dcontent(*c) || is_uchar(*c)))
+        .map(|(i, c)| (c, i))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+struct Parser<'a, 'o> {
+    input: &'a str,
+    options: &'o Options,
+    collector: &'o mut Collector,
+    context: Context,
+    start: usize,
+    pos: usize,
+    end: usize,
+}
+
+///
+/// The production being parsed, which determines the error reported for an invalid character.
+///
+#[derive(Clone, Copy, Debug)]
+enum Context {
+    LocalPart,
+    QuotedString,
+    Domain,
+    DomainLiteral,
+}
+
+///
+/// Accumulates the spans of the input that make up the semantic value of a component, only
+/// allocating a new string when those spans are not contiguous.
+///
+struct Text<'a> {
+    input: &'a str,
+    span: Range<usize>,
+    owned: Option<String>,
+}
+
+impl Collector {
+    pub(crate) fn with_errors(mut self) -> Self {
+        self.errors = Some(Vec::new());
+        self
+    }
+
+    pub(crate) fn with_warnings(mut self) -> Self {
+        self.warnings = Some(Vec::new());
+        self
+    }
+
+    ///
+    /// Report an error; if all errors are being collected this returns `Ok` so that the caller
+    /// can recover and continue, otherwise it returns the error.
+    ///
+    pub(crate) fn fail(&mut self, error: Error, span: Range<usize>) -> Result<(), ParseError> {
+        let error = ParseError::new(error, span);
+        match &mut self.errors {
+            Some(errors) => {
+                errors.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    ///
+    /// Report an error that the caller cannot recover from, returning it.
+    ///
+    pub(crate) fn abort(&mut self, error: Error, span: Range<usize>) -> ParseError {
+        let error = ParseError::new(error, span);
+        if let Some(errors) = &mut self.errors {
+            errors.push(error.clone());
+        }
+        error
+    }
+
+    pub(crate) fn warn(&mut self, warning: Warning) {
+        if let Some(warnings) = &mut self.warnings {
+            if !warnings.contains(&warning) {
+                warnings.push(warning);
+            }
+        }
+    }
+
+    ///
+    /// If any errors were collected, return the first.
+    ///
+    pub(crate) fn finish(&self) -> Result<(), ParseError> {
+        match self.errors.as_ref().and_then(|errors| errors.first()) {
+            Some(error) => Err(error.clone()),
+            None => Ok(()),
+        }
+    }
+
+    pub(crate) fn into_errors(self) -> Vec<ParseError> {
+        self.errors.unwrap_or_default()
+    }
+
+    pub(crate) fn into_warnings(self) -> Vec<Warning> {
+        self.warnings.unwrap_or_default()
+    }
+}
+
+impl<'a> Text<'a> {
+    fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            span: 0..0,
+            owned: None,
+        }
+    }
+
+    fn push(&mut self, range: Range<usize>) {
+        if range.is_empty() {
+            return;
+        }
+        match &mut self.owned {
+            Some(owned) => owned.push_str(&self.input[range]),
+            None if self.span.is_empty() => self.span = range,
+            None if self.span.end == range.start => self.span.end = range.end,
+            None => {
+                let mut owned = self.input[self.span.clone()].to_string();
+                owned.push_str(&self.input[range]);
+                self.owned = Some(owned);
+            }
+        }
+    }
+
+    fn finish(self) -> Cow<'a, str> {
+        match self.owned {
+            Some(owned) => Cow::Owned(owned),
+            None => Cow::Borrowed(&self.input[self.span]),
+        }
+    }
+}
+
+impl<'a, 'o> Parser<'a, 'o> {
+    fn new(
+        input: &'a str,
+        range: Range<usize>,
+        options: &'o Options,
+        collector: &'o mut Collector,
+    ) -> Self {
+        Self {
+            input,
+            options,
+            collector,
+            context: Context::LocalPart,
+            start: range.start,
+            pos: range.start,
+            end: range.end,
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+    // Where an error is reported with `fail` the parser recovers, usually by skipping the
+    // offending character, so that parsing can continue when all errors are being collected.
+    // --------------------------------------------------------------------------------------------
+
+    fn local_part(mut self) -> Result<Cow<'a, str>, ParseError> {
+        self.context = Context::LocalPart;
+        let mut text = Text::new(self.input);
+        let obsolete = self.options.allow_obsolete;
+        let mut words = 0;
+        let mut quoted = false;
+        let mut empty_quotes = false;
+        self.cfws()?;
+        loop {
+            match self.peek() {
+                Some(DQUOTE) if words == 0 || obsolete => {
+                    if !self.options.allow_quoted_local_part {
+                        self.fail(Error::UnsupportedQuotedLocalPart)?;
+                    }
+                    empty_quotes = self.quoted_string(&mut text)?;
+                    quoted = true;
+                    self.warn(Warning::QuotedLocalPart);
+                }
+                Some(c) if self.is_atext(c) => self.atom(&mut text),
+                None if words == 0 => {
+                    self.fail_at(Error::LocalPartEmpty, self.start..self.end)?;
+                    break;
+                }
+                None => {
+                    self.invalid()?;
+                    break;
+                }
+                Some(_) => {
+                    self.invalid()?;
+                    self.bump();
+                    continue;
+                }
+            }
+            words += 1;
+            if words > 1 && quoted {
+                self.warn(Warning::ObsoleteSyntax);
+            }
+            let folded = self.cfws()?;
+            match self.peek() {
+                None => break,
+                Some(DOT) if obsolete || !(folded || quoted) => {
+                    self.dot(&mut text);
+                    let after = self.pos;
+                    let folded_after = self.cfws()?;
+                    if folded_after && !obsolete {
+                        self.invalid_at(after)?;
+                    } else if folded || folded_after {
+                        self.warn(Warning::ObsoleteSyntax);
+                    }
+                }
+                Some(_) => {
+                    self.invalid()?;
+                    self.bump();
+                }
+            }
+        }
+        if words == 1 && empty_quotes {
+            self.fail_at(Error::LocalPartEmpty, self.start..self.end)?;
+        }
+        Ok(text.finish())
+    }
+
+    fn atom(&mut self, text: &mut Text<'a>) {
+        let start = self.pos;
+        loop {
+            self.skip_ascii(&ATEXT);
+            match self.peek() {
+                Some(c) if self.is_atext(c) => self.bump(),
+                _ => break,
+            }
+        }
+        text.push(start..self.pos);
+    }
+
+    fn dot(&mut self, text: &mut Text<'a>) {
+        let start = self.pos;
+        self.bump();
+        text.push(start..self.pos);
+    }
+
+    ///
+    /// Parse a `quoted-string`, returning `true` if it has no content.
+    ///
+    fn quoted_string(&mut self, text: &mut Text<'a>) -> Result<bool, ParseError> {
+        let start = self.pos;
+        let mut run = start;
+        self.context = Context::QuotedString;
+        self.bump();
+        loop {
+            match self.peek() {
+                None => {
+                    self.fail_at(Error::UnbalancedQuotes, start..self.pos)?;
+                    self.context = Context::LocalPart;
+                    return Ok(false);
+                }
+                Some(DQUOTE) => {
+                    self.bump();
+                    text.push(run..self.pos);
+                    self.context = Context::LocalPart;
+                    return Ok(self.pos - start == 2);
+                }
+                Some(ESC) => self.quoted_pair(false)?,
+                Some(CR) if self.options.allow_cfws => {
+                    text.push(run..self.pos);
+                    self.fold()?;
+                    run = self.pos;
+                }
+                Some(c) if is_wsp(c) || self.is_qtext(c) => self.accept(c),
+                Some(_) => {
+                    self.invalid()?;
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    fn quoted_pair(&mut self, comment: bool) -> Result<(), ParseError> {
+        let start = self.pos;
+        self.bump();
+        match self.peek() {
+            Some(c) if self.is_quoted_pair(c) => {
+                if !(is_vchar(c) || is_wsp(c) || is_uchar(c)) {
+                    self.warn(Warning::ObsoleteSyntax);
+                }
+                self.bump();
+                Ok(())
+            }
+            _ if comment => self.fail_at(Error::InvalidComment, start..self.next()),
+            _ => self.invalid(),
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    fn domain(mut self) -> Result<Cow<'a, str>, ParseError> {
+        self.context = Context::Domain;
+        let mut text = Text::new(self.input);
+        self.cfws()?;
+        let literal = match self.peek() {
+            None => {
+                self.fail_at(Error::DomainEmpty, self.start..self.end)?;
+                return Ok(text.finish());
+            }
+            Some(LBRACKET) => {
+                if !self.options.allow_domain_literal {
+                    self.fail(Error::UnsupportedDomainLiteral)?;
+                }
+                self.domain_literal(&mut text)?;
+                self.warn(Warning::DomainLiteral);
+                true
+            }
+            Some(_) => {
+                self.sub_domains(&mut text)?;
+                false
+            }
+        };
+        loop {
+            self.cfws()?;
+            if self.peek().is_none() {
+                break;
+            }
+            self.invalid()?;
+            self.bump();
+            if !literal {
+                self.sub_domains(&mut text)?;
+            }
+        }
+        Ok(text.finish())
+    }
+
+    fn sub_domains(&mut self, text: &mut Text<'a>) -> Result<(), ParseError> {
+        let obsolete = self.options.allow_obsolete;
+        let mut labels = 0;
+        loop {
+            let start = self.pos;
+            let table = if self.options.ldh_domain {
+                &LDH
+            } else {
+                &ATEXT
+            };
+            loop {
+                self.skip_ascii(table);
+                match self.peek() {
+                    Some(c) if self.is_sub_domain_char(c) => self.bump(),
+                    _ => break,
+                }
+            }
+            let label = &self.input[start..self.pos];
+            if label.is_empty() {
+                self.invalid()?;
+            } else if self.options.ldh_domain && label.starts_with('-') {
+                self.invalid_at(start)?;
+            } else if self.options.ldh_domain && label.ends_with('-') {
+                self.invalid_at(self.pos - 1)?;
+            } else if !is_ldh_label(label) {
+                self.warn(Warning::NonLdhDomain);
+            }
+            labels += 1;
+            text.push(start..self.pos);
+            let folded = self.cfws()?;
+            match self.peek() {
+                Some(DOT) if obsolete || !folded => {
+                    self.dot(text);
+                    let after = self.pos;
+                    let folded_after = self.cfws()?;
+                    if folded_after && !obsolete {
+                        self.invalid_at(after)?;
+                    } else if folded || folded_after {
+                        self.warn(Warning::ObsoleteSyntax);
+                    }
+                }
+                _ => {
+                    if labels == 1 {
+                        self.warn(Warning::DotlessDomain);
+                    } else if label.chars().all(|c| c.is_ascii_digit()) {
+                        self.warn(Warning::NumericTld);
+                    }
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn domain_literal(&mut self, text: &mut Text<'a>) -> Result<(), ParseError> {
+        let start = self.pos;
+        let mut run = start;
+        self.context = Context::DomainLiteral;
+        self.bump();
+        let content = self.pos;
+        loop {
+            match self.peek() {
+                None => {
+                    self.invalid_at(start)?;
+                    self.context = Context::Domain;
+                    return Ok(());
+                }
+                Some(RBRACKET) => break,
+                Some(c) if is_wsp(c) && self.options.allow_cfws => {
+                    text.push(run..self.pos);
+                    self.bump();
+                    run = self.pos;
+                }
+                Some(CR) if self.options.allow_cfws => {
+                    text.push(run..self.pos);
+                    self.fold()?;
+                    run = self.pos;
+                }
+                Some(ESC) if self.options.allow_obsolete => self.quoted_pair(false)?,
+                Some(c) if self.is_dtext(c) => self.accept(c),
+                Some(_) => {
+                    self.invalid()?;
+                    self.bump();
+                }
+            }
+        }
+        let content = content..self.pos;
+        self.bump();
+        text.push(run..self.pos);
+        self.context = Context::Domain;
+        if self.options.strict_domain_literal && !is_address_literal(&self.input[content.clone()]) {
+            self.fail_at(Error::InvalidIPAddress, content)?;
+        }
+        Ok(())
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    ///
+    /// Skip any `CFWS` at the current position, if allowed, returning `true` if anything was
+    /// skipped.
+    ///
+    fn cfws(&mut self) -> Result<bool, ParseError> {
+        if !self.options.allow_cfws {
+            return Ok(false);
+        }
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(c) if is_wsp(c) => {
+                    self.bump();
+                    self.warn(Warning::FoldingWhiteSpace);
+                }
+                Some(CR) => {
+                    self.fold()?;
+                    self.warn(Warning::FoldingWhiteSpace);
+                }
+                Some(LPAREN) => {
+                    self.comment()?;
+                    self.warn(Warning::Comment);
+                }
+                _ => return Ok(self.pos > start),
+            }
+        }
+    }
+
+    ///
+    /// A line break within folding white space must be CRLF, followed by at least one `WSP`.
+    ///
+    fn fold(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        self.bump();
+        if self.peek() != Some(LF) {
+            return self.invalid_at(start);
+        }
+        self.bump();
+        match self.peek() {
+            Some(c) if is_wsp(c) => Ok(()),
+            _ => self.invalid_at(start),
+        }
+    }
+
+    fn comment(&mut self) -> Result<(), ParseError> {
+        let start = self.pos;
+        let mut depth = 0;
+        loop {
+            match self.peek() {
+                None => return self.fail_at(Error::InvalidComment, start..self.pos),
+                Some(LPAREN) => {
+                    self.bump();
+                    depth += 1;
+                }
+                Some(RPAREN) => {
+                    self.bump();
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(());
+                    }
+                }
+                Some(ESC) => self.quoted_pair(true)?,
+                Some(CR) => self.fold()?,
+                Some(c) if is_wsp(c) || self.is_ctext(c) => self.accept(c),
+                Some(_) => {
+                    self.fail(Error::InvalidComment)?;
+                    self.bump();
+                }
+            }
+        }
+    }
+
+    // --------------------------------------------------------------------------------------------
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.input[self.pos..self.end].chars().next()
+    }
+
+    #[inline]
+    fn bump(&mut self) {
+        if let Some(c) = self.peek() {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    ///
+    /// Skip the ASCII characters marked in `table`, stopping at the first other character; this
+    /// is the fast path for the runs of plain characters that make up most addresses.
+    ///
+    #[inline]
+    fn skip_ascii(&mut self, table: &[bool; 128]) {
+        self.pos += self.input.as_bytes()[self.pos..self.end]
+            .iter()
+            .position(|b| !table.get(usize::from(*b)).copied().unwrap_or(false))
+            .unwrap_or(self.end - self.pos);
+    }
+
+    ///
+    /// Consume a character that has been checked against the grammar, noting any that are only
+    /// allowed by the obsolete syntax.
+    ///
+    fn accept(&mut self, c: char) {
+        if is_obs_no_ws_ctl(c) {
+            self.warn(Warning::ObsoleteSyntax);
+        }
+        self.bump();
+    }
+
+    ///
+    /// The offset following the current character.
+    ///
+    #[inline]
+    fn next(&self) -> usize {
+        self.pos + self.peek().map(char::len_utf8).unwrap_or_default()
+    }
+
+    ///
+    /// Report an error at the current character.
+    ///
+    #[inline]
+    fn fail(&mut self, error: Error) -> Result<(), ParseError> {
+        self.fail_at(error, self.pos..self.next())
+    }
+
+    #[inline]
+    fn fail_at(&mut self, error: Error, span: Range<usize>) -> Result<(), ParseError> {
+        self.collector.fail(error, span)
+    }
+
+    ///
+    /// Report the current character as invalid or, if the input ended early, the last character.
+    ///
+    fn invalid(&mut self) -> Result<(), ParseError> {
+        let at = match self.peek() {
+            Some(_) => self.pos,
+            None => self.input[..self.pos]
+                .char_indices()
+                .next_back()
+                .map(|(at, _)| at)
+                .unwrap_or(self.pos),
+        };
+        self.invalid_at(at)
+    }
+
+    fn invalid_at(&mut self, at: usize) -> Result<(), ParseError> {
+        let c = self.input[at..].chars().next().unwrap_or_default();
+        let error = match self.context {
+            Context::LocalPart => Error::LocalPartInvalidCharacter(c, at),
+            Context::QuotedString => Error::QuotedStringInvalidCharacter(c, at),
+            Context::Domain => Error::DomainInvalidCharacter(c, at),
+            Context::DomainLiteral => Error::DomainLiteralInvalidCharacter(c, at),
+        };
+        self.fail_at(error, at..at + c.len_utf8())
+    }
+
+    #[inline]
+    fn warn(&mut self, warning: Warning) {
+        self.collector.warn(warning)
+    }
+
+    #[inline]
+    fn is_utf8(&self, c: char) -> bool {
+        self.options.allow_utf8 && is_uchar(c)
+    }
+
+    #[inline]
+    fn is_obsolete(&self, c: char) -> bool {
+        self.options.allow_obsolete && is_obs_no_ws_ctl(c)
+    }
+
+    fn is_atext(&self, c: char) -> bool {
+        is_ascii_atext(c) || self.is_utf8(c)
+    }
+
+    fn is_qtext(&self, c: char) -> bool {
+        c == '\x21'
+            || ('\x23'..='\x5B').contains(&c)
+            || ('\x5D'..='\x7E').contains(&c)
+            || self.is_utf8(c)
+            || self.is_obsolete(c)
+    }
+
+    fn is_quoted_pair(&self, c: char) -> bool {
+        is_vchar(c)
+            || is_wsp(c)
+            || self.is_utf8(c)
+            || (self.options.allow_obsolete && (c == '\0' || c == CR || c == LF))
+            || self.is_obsolete(c)
+    }
+
+    fn is_ctext(&self, c: char) -> bool {
+        ('\x21'..='\x27').contains(&c)
+            || ('\x2A'..='\x5B').contains(&c)
+            || ('\x5D'..='\x7E').contains(&c)
+            || self.is_utf8(c)
+            || self.is_obsolete(c)
+    }
+
+    fn is_dtext(&self, c: char) -> bool {
+        ('\x21'..='\x5A').contains(&c) || ('\x5E'..='\x7E').contains(&c) || self.is_obsolete(c)
+    }
+
+    fn is_sub_domain_char(&self, c: char) -> bool {
+        if self.options.ldh_domain {
+            c.is_ascii_alphanumeric() || c == '-' || self.is_utf8(c)
+        } else {
+            self.is_atext(c)
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn non_empty(part: &str, error: Error) -> Result<Cow<'_, str>, ParseError> {
+    if part.is_empty() {
+        Err(ParseError::new(error, 0..0))
+    } else {
+        Ok(Cow::Borrowed(part))
+    }
+}
+
+///
+/// Find the separator between `local-part` and `domain`; this is the last '@' that is not within
+/// a quoted string, comment, or domain literal. If there is no such character the last '@' is
+/// used so that the error is reported in the component where it occurs.
+///
+fn find_separator(address: &str, options: &Options) -> Option<usize> {
+    let bytes = address.as_bytes();
+    if memchr::memchr3(b'"', b'[', b'(', bytes).is_none() {
+        return memchr::memrchr(b'@', bytes);
+    }
+    let mut separator = None;
+    let mut escaped = false;
+    let mut quoted = false;
+    let mut literal = false;
+    let mut comments = 0;
+    for (i, c) in address.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            ESC if quoted || comments > 0 => escaped = true,
+            DQUOTE if comments == 0 && !literal => quoted = !quoted,
+            LPAREN if options.allow_cfws && !quoted && !literal => comments += 1,
+            RPAREN if comments > 0 => comments -= 1,
+            LBRACKET if !quoted && comments == 0 => literal = true,
+            RBRACKET if literal => literal = false,
+            AT if !quoted && !literal && comments == 0 => separator = Some(i),
+            _ => {}
+        }
+    }
+    separator.or_else(|| address.rfind(AT))
+}
+
+const fn ascii_table(ldh: bool) -> [bool; 128] {
+    let mut table = [false; 128];
+    let mut i = 0;
+    while i < table.len() {
+        let c = i as u8 as char;
+        table[i] = if ldh {
+            c.is_ascii_alphanumeric() || c == '-'
+        } else {
+            is_ascii_atext(c)
+        };
+        i += 1;
+    }
+    table
+}
+
+///
+/// The content of an RFC 5321 `address-literal`; an IPv4 address, an IPv6 address following the
+/// tag "IPv6:", or a general address literal following some other standardized tag.
+///
+fn is_address_literal(content: &str) -> bool {
+    match content.find(':') {
+        None => is_ipv4_address(content),
+        Some(colon) => {
+            let (tag, address) = (&content[..colon], &content[colon + 1..]);
+            if tag.eq_ignore_ascii_case("IPv6") {
+                address.parse::<Ipv6Addr>().is_ok()
+            } else {
+                is_ldh_str(tag) && !address.is_empty() && address.chars().all(is_dcontent)
+            }
+        }
+    }
+}
+
+fn is_ipv4_address(s: &str) -> bool {
+    let mut count = 0;
+    for snum in s.split(DOT) {
+        count += 1;
+        if snum.is_empty()
+            || snum.len() > 3
+            || !snum.chars().all(|c| c.is_ascii_digit())
+            || snum.parse::<u16>().map(|n| n > 255).unwrap_or(true)
+        {
+            return false;
+        }
+    }
+    count == 4
+}
+
+///
+/// Returns `true` if the bytes of `address` from `start` to `end`, which are all letters, digits,
+/// or hyphens, are a label of a host name.
+///
+const fn is_ascii_ldh_label(address: &[u8], start: usize, end: usize) -> bool {
+    end > start
+        && end - start <= SUB_DOMAIN_MAX_LENGTH
+        && address[start] != b'-'
+        && address[end - 1] != b'-'
+}
+
+fn is_ldh_label(s: &str) -> bool {
+    s.chars().all(|c| c.is_alphanumeric() || c == '-') && !s.starts_with('-') && !s.ends_with('-')
+}
+
+fn is_ldh_str(s: &str) -> bool {
+    !s.is_empty() && !s.ends_with('-') && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn is_dcontent(c: char) -> bool {
+    ('\x21'..='\x5A').contains(&c) || ('\x5E'..='\x7E').contains(&c)
+}
+
+const fn is_ascii_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric()
+        || c == '!'
+        || c == '#'
+        || c == '$'
+        || c == '%'
+        || c == '&'
+        || c == '\''
+        || c == '*'
+        || c == '+'
+        || c == '-'
+        || c == '/'
+        || c == '='
+        || c == '?'
+        || c == '^'
+        || c == '_'
+        || c == '`'
+        || c == '{'
+        || c == '|'
+        || c == '}'
+        || c == '~'
+}
+
+fn is_uchar(c: char) -> bool {
+    c >= UTF8_START
+}
+
+fn is_vchar(c: char) -> bool {
+    ('\x21'..='\x7E').contains(&c)
+}
+
+fn is_wsp(c: char) -> bool {
+    c == SP || c == HTAB
+}
+
+fn is_obs_no_ws_ctl(c: char) -> bool {
+    ('\x01'..='\x08').contains(&c)
+        || c == '\x0B'
+        || c == '\x0C'
+        || ('\x0E'..='\x1F').contains(&c)
+        || c == '\x7F'
+}
+
+// ------------------------------------------------------------------------------------------------
+
+/// The ASCII characters of `atext`.
+const ATEXT: [bool; 128] = ascii_table(false);
+
+/// The ASCII characters of a letter-digit-hyphen label.
+const LDH: [bool; 128] = ascii_table(true);
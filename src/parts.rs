@@ -0,0 +1,645 @@
+/*!
+The components of an address as types of their own, validated by the same rules as the
+corresponding part of an `EmailAddress`, so that code dealing with only one part, such as
+host name checks, may use them independently of a full address.
+*/
+
+use crate::parser::is_dot_atom;
+use crate::{
+    parse_domain, parse_local_part, Error, Options, CR, DOT, DQUOTE, ESC, HTAB, LBRACKET, LF,
+    RBRACKET, SP,
+};
+use std::borrow::Cow;
+use std::fmt::{Debug, Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A domain name, the `dot-atom` form of the `domain` of an address; a sequence of labels
+/// separated by dots, and never a domain literal.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let domain = DomainName::from_str("mail.example.com").unwrap();
+///
+/// assert_eq!(domain.label_count(), 3);
+/// assert_eq!(domain.labels().collect::<Vec<_>>(), vec!["mail", "example", "com"]);
+/// assert_eq!(domain.label_lengths().max(), Some(7));
+/// assert!(DomainName::from_str("[192.168.2.1]").is_err());
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DomainName(String);
+
+///
+/// A `domain`, either a domain name or a domain literal; such as the domain of an address, or
+/// the argument of an SMTP `HELO` or `EHLO` command.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let domain = Domain::from_str("mail.example.com").unwrap();
+/// assert_eq!(domain.as_name().map(DomainName::label_count), Some(3));
+///
+/// let domain = Domain::from_str("[192.168.2.1]").unwrap();
+/// assert!(domain.is_literal());
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Domain {
+    /// A domain name, such as `example.com`.
+    Name(DomainName),
+    /// A domain literal, including its brackets; such as `[192.168.2.1]`.
+    Literal(String),
+}
+
+///
+/// The kind of a `domain`, with the address of a domain literal; returned by
+/// `EmailAddress::domain_part` and `Domain::part`.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::net::Ipv4Addr;
+/// use std::str::FromStr;
+///
+/// let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+/// assert_eq!(email.domain_part(), DomainPart::Ipv4(Ipv4Addr::new(192, 168, 2, 1)));
+///
+/// let email = EmailAddress::from_str("simon@example.com").unwrap();
+/// assert_eq!(email.domain_part(), DomainPart::Name("example.com"));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DomainPart<'a> {
+    /// A domain name, such as `example.com`.
+    Name(&'a str),
+    /// A domain literal holding an IPv4 address, such as `[192.168.2.1]`.
+    Ipv4(Ipv4Addr),
+    /// A domain literal holding an IPv6 address, such as `[IPv6:2001:db8::1]`.
+    Ipv6(Ipv6Addr),
+    ///
+    /// Any other domain literal; the standardized tag and content of a general address literal,
+    /// such as `[x400:c=gb;a=ac;p=example]`. If the literal has no tag, as may be accepted
+    /// without `Options::with_strict_domain_literal`, the tag is empty and the content is the
+    /// whole literal; if the tag is "IPv6" but the content is not an IPv6 address, it is
+    /// returned here too.
+    ///
+    GeneralLiteral {
+        /// The standardized tag, before the first ':'.
+        tag: &'a str,
+        /// The content, after the first ':'.
+        content: &'a str,
+    },
+}
+
+///
+/// The scope, or special purpose, of the IP address in an address literal, as assigned by the
+/// IANA IPv4 and IPv6 Special-Purpose Address Registries; returned by `DomainPart::ip_scope`.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let email = EmailAddress::from_str("simon@[192.168.2.1]").unwrap();
+/// assert_eq!(email.domain_part().ip_scope(), Some(IpScope::Private));
+///
+/// let email = EmailAddress::from_str("simon@[IPv6:2001:db8::1]").unwrap();
+/// assert_eq!(email.domain_part().ip_scope(), Some(IpScope::Documentation));
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IpScope {
+    /// A globally routable address.
+    Global,
+    /// A private network address; `10/8`, `172.16/12`, `192.168/16`, `100.64/10`, or `fc00::/7`.
+    Private,
+    /// A loopback address; `127/8` or `::1`.
+    Loopback,
+    /// A link-local address; `169.254/16` or `fe80::/10`.
+    LinkLocal,
+    ///
+    /// An address reserved for documentation; `192.0.2/24`, `198.51.100/24`, `203.0.113/24`,
+    /// `2001:db8::/32`, or `3fff::/20`.
+    ///
+    Documentation,
+    /// The unspecified address, or "this network"; `0/8` or `::`.
+    Unspecified,
+    /// A multicast address; `224/4` or `ff00::/8`.
+    Multicast,
+    /// Any other address that is not globally routable, such as `198.18/15` or `240/4`.
+    Reserved,
+}
+
+///
+/// A `local-part`, the mailbox name that precedes the '@' of an address.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let local = LocalPart::from_str("simon.johnston").unwrap();
+/// assert_eq!(local.kind(), LocalPartKind::DotAtom);
+///
+/// let local = LocalPart::from_str("\"simon johnston\"").unwrap();
+/// assert_eq!(local.kind(), LocalPartKind::QuotedString);
+///
+/// assert!(LocalPart::from_str("simon johnston").is_err());
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LocalPart(String);
+
+///
+/// The syntactic form of a `local-part`.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LocalPartKind {
+    /// The `dot-atom` form, atoms separated by dots; such as `simon.johnston`.
+    DotAtom,
+    /// The `quoted-string` form; such as `"simon johnston"`.
+    QuotedString,
+    ///
+    /// The obsolete form, atoms and quoted strings separated by dots; such as
+    /// `"simon johnston".sj`. Only accepted with `Options::with_obsolete`.
+    ///
+    Obsolete,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The kind of `domain`, a `domain` that has already been parsed.
+///
+pub(crate) fn domain_part(domain: &str) -> DomainPart<'_> {
+    let content = match domain
+        .strip_prefix(LBRACKET)
+        .and_then(|literal| literal.strip_suffix(RBRACKET))
+    {
+        Some(content) => content,
+        None => return DomainPart::Name(domain),
+    };
+    if let Some(address) = ipv4_address(content) {
+        return DomainPart::Ipv4(address);
+    }
+    match content.split_once(':') {
+        Some((tag, address)) => match address.parse::<Ipv6Addr>() {
+            Ok(address) if tag.eq_ignore_ascii_case("IPv6") => DomainPart::Ipv6(address),
+            _ => DomainPart::GeneralLiteral {
+                tag,
+                content: address,
+            },
+        },
+        None => DomainPart::GeneralLiteral { tag: "", content },
+    }
+}
+
+///
+/// The form of `local`, a `local-part` that has already been parsed.
+///
+pub(crate) fn local_part_kind(local: &str) -> LocalPartKind {
+    if !local.contains(DQUOTE) {
+        return LocalPartKind::DotAtom;
+    }
+    let mut chars = local.char_indices().skip(1);
+    if local.starts_with(DQUOTE) {
+        while let Some((i, c)) = chars.next() {
+            match c {
+                ESC => {
+                    let _ = chars.next();
+                }
+                DQUOTE => {
+                    if i + 1 == local.len() {
+                        return LocalPartKind::QuotedString;
+                    }
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    LocalPartKind::Obsolete
+}
+
+///
+/// The logical content of `local`, a `local-part` that has already been parsed; the quotes
+/// around quoted strings are removed, quoted pairs are replaced by the character they quote, and
+/// folding whitespace within quoted strings is unfolded.
+///
+pub(crate) fn unescape_local_part(local: &str) -> Cow<'_, str> {
+    if !local.contains(DQUOTE) {
+        return Cow::Borrowed(local);
+    }
+    let mut unescaped = String::with_capacity(local.len());
+    let mut quoted = false;
+    let mut chars = local.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            DQUOTE => quoted = !quoted,
+            ESC if quoted => unescaped.extend(chars.next()),
+            CR | LF if quoted => {}
+            _ => unescaped.push(c),
+        }
+    }
+    Cow::Owned(unescaped)
+}
+
+///
+/// `local`, a `local-part` that has already been parsed, in lowercase; the content of quoted
+/// strings, including quoted pairs, is only lowercased if `quoted` is `true`.
+///
+pub(crate) fn lowercase_local_part(local: &str, quoted: bool) -> Cow<'_, str> {
+    if quoted || !local.contains(DQUOTE) {
+        return if local.chars().any(char::is_uppercase) {
+            Cow::Owned(local.to_lowercase())
+        } else {
+            Cow::Borrowed(local)
+        };
+    }
+    let mut lowercased = String::with_capacity(local.len());
+    let mut in_quotes = false;
+    let mut chars = local.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            DQUOTE => {
+                in_quotes = !in_quotes;
+                lowercased.push(c);
+            }
+            ESC if in_quotes => {
+                lowercased.push(c);
+                lowercased.extend(chars.next());
+            }
+            c if in_quotes => lowercased.push(c),
+            c => lowercased.extend(c.to_lowercase()),
+        }
+    }
+    Cow::Owned(lowercased)
+}
+
+///
+/// The shortest form of `local`, a `local-part` that has already been parsed; a `dot-atom` if
+/// the content of `local` is one, otherwise a single quoted string in which only the characters
+/// that are neither `qtext` nor white space are quoted.
+///
+pub(crate) fn minimally_quote(local: &str) -> Cow<'_, str> {
+    quote_content(unescape_local_part(local))
+}
+
+///
+/// The shortest `local-part` with the given `content`, as returned by `unescape_local_part`.
+///
+pub(crate) fn quote_content(content: Cow<'_, str>) -> Cow<'_, str> {
+    if is_dot_atom(&content) {
+        return content;
+    }
+    let mut quoted = String::with_capacity(content.len() + 2);
+    quoted.push(DQUOTE);
+    for c in content.chars() {
+        // Characters from the obsolete quoted pairs, such as CR, LF, and NUL, stay quoted.
+        if c.is_ascii()
+            && !(c == SP || c == HTAB || (c.is_ascii_graphic() && c != DQUOTE && c != ESC))
+        {
+            quoted.push(ESC);
+        }
+        quoted.push(c);
+    }
+    quoted.push(DQUOTE);
+    Cow::Owned(quoted)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for DomainName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl FromStr for DomainName {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl AsRef<str> for DomainName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<DomainName> for String {
+    fn from(domain: DomainName) -> Self {
+        domain.0
+    }
+}
+
+impl Display for Domain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(self.as_str())
+    }
+}
+
+impl Debug for Domain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Domain")
+            .field("domain", &self.as_str())
+            .field("literal", &self.is_literal())
+            .finish()
+    }
+}
+
+impl FromStr for Domain {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl AsRef<str> for Domain {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<DomainName> for Domain {
+    fn from(domain: DomainName) -> Self {
+        Self::Name(domain)
+    }
+}
+
+impl From<Domain> for String {
+    fn from(domain: Domain) -> Self {
+        match domain {
+            Domain::Name(name) => name.into(),
+            Domain::Literal(literal) => literal,
+        }
+    }
+}
+
+impl DomainPart<'_> {
+    ///
+    /// Returns the scope of the IP address of an address literal, or `None` if this is a domain
+    /// name or a general address literal. IPv4-mapped IPv6 addresses have the scope of the IPv4
+    /// address they map, and 6to4 and Teredo addresses the scope of the IPv4 address they wrap,
+    /// the client's for Teredo.
+    ///
+    pub fn ip_scope(&self) -> Option<IpScope> {
+        match self {
+            DomainPart::Ipv4(address) => Some(ipv4_scope(address)),
+            DomainPart::Ipv6(address) => Some(ipv6_scope(address)),
+            _ => None,
+        }
+    }
+}
+
+impl IpScope {
+    /// Returns `true` if the address is globally routable.
+    pub fn is_global(&self) -> bool {
+        matches!(self, IpScope::Global)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Domain {
+    ///
+    /// Parse a domain with the rules of `options`.
+    ///
+    pub fn parse_with(domain: &str, options: &Options) -> Result<Self, Error> {
+        let domain = parse_domain(domain, options)?;
+        Ok(Self::new_unchecked(domain.into_owned()))
+    }
+
+    pub(crate) fn new_unchecked(domain: String) -> Self {
+        if domain.starts_with(LBRACKET) {
+            Self::Literal(domain)
+        } else {
+            Self::Name(DomainName::new_unchecked(domain))
+        }
+    }
+
+    /// Returns the domain as a string slice.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Name(name) => name.as_str(),
+            Self::Literal(literal) => literal,
+        }
+    }
+
+    /// Returns the kind of the domain, with the address of a domain literal.
+    pub fn part(&self) -> DomainPart<'_> {
+        domain_part(self.as_str())
+    }
+
+    /// Returns `true` if the domain is a domain literal.
+    pub fn is_literal(&self) -> bool {
+        matches!(self, Self::Literal(_))
+    }
+
+    /// Returns the domain name, or `None` if the domain is a domain literal.
+    pub fn as_name(&self) -> Option<&DomainName> {
+        match self {
+            Self::Name(name) => Some(name),
+            Self::Literal(_) => None,
+        }
+    }
+}
+
+impl Display for LocalPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.pad(&self.0)
+    }
+}
+
+impl Debug for LocalPart {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalPart")
+            .field("local", &self.0)
+            .field("kind", &self.kind())
+            .finish()
+    }
+}
+
+impl FromStr for LocalPart {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl AsRef<str> for LocalPart {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<LocalPart> for String {
+    fn from(local: LocalPart) -> Self {
+        local.0
+    }
+}
+
+impl LocalPart {
+    ///
+    /// Parse a `local-part` with the rules of `options`.
+    ///
+    pub fn parse_with(local: &str, options: &Options) -> Result<Self, Error> {
+        let local = parse_local_part(local, options)?;
+        Ok(Self(local.into_owned()))
+    }
+
+    pub(crate) fn new_unchecked(local: String) -> Self {
+        Self(local)
+    }
+
+    /// Returns the local part as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the syntactic form of the local part.
+    pub fn kind(&self) -> LocalPartKind {
+        local_part_kind(&self.0)
+    }
+
+    ///
+    /// Returns the logical content of the local part, without quotes or quoted pairs; see
+    /// `EmailAddress::local_part_unescaped`.
+    ///
+    pub fn unescaped(&self) -> Cow<'_, str> {
+        unescape_local_part(&self.0)
+    }
+
+    ///
+    /// Returns the shortest equivalent local part; see `EmailAddress::minimally_quoted`.
+    ///
+    pub fn minimally_quoted(&self) -> Self {
+        Self(minimally_quote(&self.0).into_owned())
+    }
+}
+
+impl DomainName {
+    ///
+    /// Parse a domain name with the rules of `options`, except that domain literals are never
+    /// allowed. To check host names, use options `with_ldh_domain`.
+    ///
+    pub fn parse_with(domain: &str, options: &Options) -> Result<Self, Error> {
+        let options = options.clone().without_domain_literal();
+        let domain = parse_domain(domain, &options)?;
+        Ok(Self(domain.into_owned()))
+    }
+
+    pub(crate) fn new_unchecked(domain: String) -> Self {
+        Self(domain)
+    }
+
+    /// Returns the domain name as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    ///
+    /// Returns the labels of the domain name, from the left; the top-level domain is the last.
+    ///
+    pub fn labels(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.0.split(DOT)
+    }
+
+    /// Returns the number of labels in the domain name.
+    pub fn label_count(&self) -> usize {
+        self.labels().count()
+    }
+
+    ///
+    /// Returns the length of each label, in octets, in the same order as `labels`.
+    ///
+    pub fn label_lengths(&self) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        self.labels().map(str::len)
+    }
+
+    ///
+    /// Returns the top-level domain, the last label, or `None` if there is only one label.
+    ///
+    pub fn tld(&self) -> Option<&str> {
+        self.0.rsplit_once(DOT).map(|(_, tld)| tld)
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An IPv4 address in dotted-decimal form, allowing the leading zeros that RFC 5321 allows.
+///
+fn ipv4_address(s: &str) -> Option<Ipv4Addr> {
+    let mut octets = [0u8; 4];
+    let mut parts = s.split(DOT);
+    for octet in octets.iter_mut() {
+        let part = parts.next()?;
+        if part.is_empty() || part.len() > 3 || !part.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        *octet = part.parse().ok()?;
+    }
+    match parts.next() {
+        None => Some(Ipv4Addr::from(octets)),
+        Some(_) => None,
+    }
+}
+
+fn ipv4_scope(address: &Ipv4Addr) -> IpScope {
+    let [a, b, c, _] = address.octets();
+    match (a, b, c) {
+        (0, _, _) => IpScope::Unspecified,
+        (10, _, _) | (172, 16..=31, _) | (192, 168, _) | (100, 64..=127, _) => IpScope::Private,
+        (127, _, _) => IpScope::Loopback,
+        (169, 254, _) => IpScope::LinkLocal,
+        (192, 0, 2) | (198, 51, 100) | (203, 0, 113) => IpScope::Documentation,
+        (224..=239, _, _) => IpScope::Multicast,
+        (192, 0, 0) | (198, 18..=19, _) | (240..=255, _, _) => IpScope::Reserved,
+        _ => IpScope::Global,
+    }
+}
+
+fn wrapped_ipv4(high: u16, low: u16) -> Ipv4Addr {
+    Ipv4Addr::from((u32::from(high) << 16) | u32::from(low))
+}
+
+fn ipv6_scope(address: &Ipv6Addr) -> IpScope {
+    if let Some(mapped) = address.to_ipv4_mapped() {
+        return ipv4_scope(&mapped);
+    }
+    let segments = address.segments();
+    match segments {
+        [0, 0, 0, 0, 0, 0, 0, 0] => IpScope::Unspecified,
+        [0, 0, 0, 0, 0, 0, 0, 1] => IpScope::Loopback,
+        [0x2001, 0x0db8, ..] => IpScope::Documentation,
+        [0x3fff, second, ..] if second & 0xf000 == 0 => IpScope::Documentation,
+        // 6to4, RFC 3056, carries an IPv4 address in the 32 bits after its prefix.
+        [0x2002, high, low, ..] => ipv4_scope(&wrapped_ipv4(high, low)),
+        // Teredo, RFC 4380, carries the client's IPv4 address, with every bit inverted, in the
+        // last 32 bits.
+        [0x2001, 0, _, _, _, _, high, low] => ipv4_scope(&wrapped_ipv4(!high, !low)),
+        [first, ..] if first & 0xfe00 == 0xfc00 => IpScope::Private,
+        [first, ..] if first & 0xffc0 == 0xfe80 => IpScope::LinkLocal,
+        [first, ..] if first & 0xff00 == 0xff00 => IpScope::Multicast,
+        [first, ..] if first & 0xe000 == 0x2000 => IpScope::Global,
+        _ => IpScope::Reserved,
+    }
+}
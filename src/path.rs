@@ -0,0 +1,355 @@
+/*!
+The paths of the SMTP `MAIL` and `RCPT` commands, RFC 5321, §4.1.2 and §4.1.1.3:
+
+```ebnf
+Reverse-path   = Path / "<>"
+
+Forward-path   = Path / "<Postmaster>"
+
+Path           = "<" [ A-d-l ":" ] Mailbox ">"
+
+A-d-l          = At-domain *( "," At-domain )
+
+At-domain      = "@" Domain
+```
+
+The null reverse-path, `<>`, is used by bounces and other automatic replies, so that they are
+never themselves replied to, RFC 5321, §4.5.5. The bare `<Postmaster>` forward-path, without a
+domain, must be accepted by every server for its own postmaster, RFC 5321, §4.5.1.
+
+The A-d-l, or source route, is a list of the hosts the message was to be relayed through; it is
+obsolete, but may still be found in `Return-Path` headers. RFC 5321, Appendix C, asks that it be
+accepted and ignored, and so it is kept by `Path`, but discarded by `ReversePath`.
+
+The mailbox of a parsed path is always parsed without the obsolete syntax of RFC 5322, §4.4,
+whatever the options, so that it can be written back into a command; a path made from an
+`EmailAddress` parsed with `Options::with_obsolete` should first be checked with
+`EmailAddress::is_smtp_mailbox`, as its address may contain a CR or LF.
+*/
+
+use crate::{DomainName, EmailAddress, Error, Options, AT, GT, LT, POSTMASTER};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An RFC 5321 `Path`; a mailbox in angle brackets, with an optional source route.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let path = Path::from_str("<@relay1.example,@relay2.example:simon@example.com>").unwrap();
+/// assert_eq!(path.route().len(), 2);
+/// assert_eq!(path.route()[0].as_str(), "relay1.example");
+/// assert_eq!(path.mailbox().as_str(), "simon@example.com");
+/// assert_eq!(path.without_route().to_string(), "<simon@example.com>");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    route: Vec<DomainName>,
+    mailbox: EmailAddress,
+}
+
+///
+/// The reverse-path of the SMTP `MAIL` command; the envelope sender, or the null path of a bounce.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// assert_eq!(ReversePath::from_str("<>"), Ok(ReversePath::Null));
+///
+/// let path = ReversePath::from_str("<simon@example.com>").unwrap();
+/// assert_eq!(path.mailbox().map(EmailAddress::domain), Some("example.com"));
+/// assert_eq!(path.to_string(), "<simon@example.com>");
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ReversePath {
+    /// The null reverse-path, `<>`.
+    Null,
+    /// The path to a mailbox.
+    Mailbox(EmailAddress),
+}
+
+///
+/// The forward-path of the SMTP `RCPT` command; a recipient, or the postmaster of the server
+/// itself.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let options = Options::from(Profile::Rfc5321);
+/// assert_eq!(ForwardPath::parse_with("<postmaster>", &options), Ok(ForwardPath::Postmaster));
+///
+/// let path = ForwardPath::parse_with("<Postmaster@example.com>", &options).unwrap();
+/// assert!(path.is_postmaster());
+/// assert_eq!(path.mailbox().map(EmailAddress::domain), Some("example.com"));
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ForwardPath {
+    /// The bare `<Postmaster>` path, without a domain, for the postmaster of the server.
+    Postmaster,
+    /// The path to a mailbox.
+    Mailbox(EmailAddress),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for Path {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", LT)?;
+        for (i, domain) in self.route.iter().enumerate() {
+            let separator = if i == 0 { "" } else { ROUTE_SEPARATOR };
+            write!(f, "{}{}{}", separator, AT, domain)?;
+        }
+        if !self.route.is_empty() {
+            write!(f, "{}", ROUTE_END)?;
+        }
+        write!(f, "{}{}", self.mailbox, GT)
+    }
+}
+
+impl FromStr for Path {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl From<EmailAddress> for Path {
+    fn from(mailbox: EmailAddress) -> Self {
+        Self {
+            route: Vec::new(),
+            mailbox,
+        }
+    }
+}
+
+impl From<Path> for EmailAddress {
+    fn from(path: Path) -> Self {
+        path.mailbox
+    }
+}
+
+impl Path {
+    ///
+    /// Parse a path, which must be enclosed in angle brackets, parsing the domains of its route
+    /// and its mailbox using the rules selected by `options`. The positions in any error are
+    /// those of the characters of `path`.
+    ///
+    pub fn parse_with(path: &str, options: &Options) -> Result<Self, Error> {
+        let inner = path
+            .strip_prefix(LT)
+            .and_then(|inner| inner.strip_suffix(GT))
+            .ok_or(Error::MissingAngleBrackets)?;
+        let start = LT.len_utf8();
+
+        let mut route = Vec::new();
+        let mut mailbox = (inner, start);
+        if inner.starts_with(AT) {
+            if let Some(end) = inner.find(ROUTE_END) {
+                let mut offset = start;
+                for at_domain in inner[..end].split(ROUTE_SEPARATOR) {
+                    let domain = at_domain.strip_prefix(AT).ok_or_else(|| {
+                        match at_domain.chars().next() {
+                            Some(c) => Error::DomainInvalidCharacter(c, offset),
+                            None => Error::DomainEmpty,
+                        }
+                    })?;
+                    let domain = DomainName::parse_with(domain, options)
+                        .map_err(|e| e.offset(offset + AT.len_utf8()))?;
+                    route.push(domain);
+                    offset += at_domain.len() + ROUTE_SEPARATOR.len();
+                }
+                mailbox = (&inner[end + 1..], start + end + 1);
+            }
+        }
+
+        let (mailbox, start) = mailbox;
+        if mailbox.starts_with(LT) {
+            return Err(Error::LocalPartInvalidCharacter(LT, start));
+        }
+        let options = options.for_unbracketed().into_owned().without_obsolete();
+        let mailbox = EmailAddress::parse_with(mailbox, &options).map_err(|e| e.offset(start))?;
+        Ok(Self { route, mailbox })
+    }
+
+    ///
+    /// Returns the domains of the source route, in the order they are to be relayed through;
+    /// empty if there is no route.
+    ///
+    pub fn route(&self) -> &[DomainName] {
+        &self.route
+    }
+
+    /// Returns the mailbox the path ends in.
+    pub fn mailbox(&self) -> &EmailAddress {
+        &self.mailbox
+    }
+
+    /// Returns this path without its source route, as RFC 5321 recommends.
+    pub fn without_route(mut self) -> Self {
+        self.route.clear();
+        self
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ReversePath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReversePath::Null => f.write_str(NULL_PATH),
+            ReversePath::Mailbox(email) => write!(f, "{}{}{}", LT, email, GT),
+        }
+    }
+}
+
+impl FromStr for ReversePath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl From<EmailAddress> for ReversePath {
+    fn from(email: EmailAddress) -> Self {
+        ReversePath::Mailbox(email)
+    }
+}
+
+impl From<Path> for ReversePath {
+    fn from(path: Path) -> Self {
+        ReversePath::Mailbox(path.into())
+    }
+}
+
+impl From<Option<EmailAddress>> for ReversePath {
+    fn from(email: Option<EmailAddress>) -> Self {
+        email.map_or(ReversePath::Null, ReversePath::Mailbox)
+    }
+}
+
+impl From<ReversePath> for Option<EmailAddress> {
+    fn from(path: ReversePath) -> Self {
+        match path {
+            ReversePath::Null => None,
+            ReversePath::Mailbox(email) => Some(email),
+        }
+    }
+}
+
+impl ReversePath {
+    ///
+    /// Parse a reverse-path, which must be enclosed in angle brackets, parsing it as a `Path`
+    /// unless it is the null reverse-path; any source route is discarded. The positions in any
+    /// error are those of the characters of `path`.
+    ///
+    pub fn parse_with(path: &str, options: &Options) -> Result<Self, Error> {
+        if path == NULL_PATH {
+            return Ok(ReversePath::Null);
+        }
+        Path::parse_with(path, options).map(ReversePath::from)
+    }
+
+    /// Returns `true` if this is the null reverse-path.
+    pub fn is_null(&self) -> bool {
+        matches!(self, ReversePath::Null)
+    }
+
+    /// Returns the mailbox of this path, or `None` if it is the null reverse-path.
+    pub fn mailbox(&self) -> Option<&EmailAddress> {
+        match self {
+            ReversePath::Null => None,
+            ReversePath::Mailbox(email) => Some(email),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for ForwardPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ForwardPath::Postmaster => write!(f, "{}{}{}", LT, POSTMASTER, GT),
+            ForwardPath::Mailbox(email) => write!(f, "{}{}{}", LT, email, GT),
+        }
+    }
+}
+
+impl FromStr for ForwardPath {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, &Options::default())
+    }
+}
+
+impl From<EmailAddress> for ForwardPath {
+    fn from(email: EmailAddress) -> Self {
+        ForwardPath::Mailbox(email)
+    }
+}
+
+impl From<Path> for ForwardPath {
+    fn from(path: Path) -> Self {
+        ForwardPath::Mailbox(path.into())
+    }
+}
+
+impl ForwardPath {
+    ///
+    /// Parse a forward-path, which must be enclosed in angle brackets, parsing it as a `Path`
+    /// unless it is the bare `<Postmaster>`, in any case, which is accepted whatever `options`
+    /// are used; any source route is discarded. The positions in any error are those of the
+    /// characters of `path`.
+    ///
+    pub fn parse_with(path: &str, options: &Options) -> Result<Self, Error> {
+        let bare = path
+            .strip_prefix(LT)
+            .and_then(|inner| inner.strip_suffix(GT));
+        if matches!(bare, Some(bare) if bare.eq_ignore_ascii_case(POSTMASTER)) {
+            return Ok(ForwardPath::Postmaster);
+        }
+        Path::parse_with(path, options).map(ForwardPath::from)
+    }
+
+    ///
+    /// Returns `true` if this is the bare `<Postmaster>` path, or the path to a mailbox whose
+    /// local part is `Postmaster`.
+    ///
+    pub fn is_postmaster(&self) -> bool {
+        match self {
+            ForwardPath::Postmaster => true,
+            ForwardPath::Mailbox(email) => email.is_postmaster(),
+        }
+    }
+
+    /// Returns the mailbox of this path, or `None` if it is the bare `<Postmaster>` path.
+    pub fn mailbox(&self) -> Option<&EmailAddress> {
+        match self {
+            ForwardPath::Postmaster => None,
+            ForwardPath::Mailbox(email) => Some(email),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const NULL_PATH: &str = "<>";
+
+const ROUTE_SEPARATOR: &str = ",";
+
+const ROUTE_END: char = ':';
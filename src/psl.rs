@@ -0,0 +1,102 @@
+/*!
+Lookups in the [Public Suffix List](https://publicsuffix.org/), which is embedded in the crate
+from `data/public_suffix_list.dat`.
+
+Both ICANN and private suffixes are used, and a domain with an unlisted top-level domain has that
+top-level domain as its public suffix, as required by the list's own algorithm. Domains are
+matched without regard to ASCII case, and the returned slices are of the domain given.
+
+The registrable domains returned by the `publicsuffix` crate, and by the `addr` crate with the
+`addr` feature, can be converted to a `DomainName`.
+*/
+
+use crate::{DomainName, Error};
+use publicsuffix::{List, Psl};
+use std::convert::TryFrom;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn public_suffix(domain: &str) -> Option<&str> {
+    let lowercase = domain.to_ascii_lowercase();
+    let suffix = list().suffix(lowercase.as_bytes())?;
+    Some(&domain[domain.len() - suffix.as_bytes().len()..])
+}
+
+pub(crate) fn registrable_domain(domain: &str) -> Option<&str> {
+    let lowercase = domain.to_ascii_lowercase();
+    let registrable = list().domain(lowercase.as_bytes())?;
+    Some(&domain[domain.len() - registrable.as_bytes().len()..])
+}
+
+pub(crate) fn list() -> &'static List {
+    static LIST: OnceLock<List> = OnceLock::new();
+    LIST.get_or_init(|| PUBLIC_SUFFIX_LIST.parse().unwrap())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl TryFrom<publicsuffix::Domain<'_>> for DomainName {
+    type Error = Error;
+
+    fn try_from(domain: publicsuffix::Domain<'_>) -> Result<Self, Self::Error> {
+        match std::str::from_utf8(domain.as_bytes()) {
+            Ok(domain) => DomainName::from_str(domain),
+            Err(e) => Err(Error::InvalidUtf8(e.valid_up_to())),
+        }
+    }
+}
+
+#[cfg(feature = "addr")]
+impl TryFrom<addr::domain::Name<'_>> for DomainName {
+    type Error = Error;
+
+    fn try_from(name: addr::domain::Name<'_>) -> Result<Self, Self::Error> {
+        DomainName::from_str(name.as_str())
+    }
+}
+
+#[cfg(feature = "addr")]
+impl<'a> TryFrom<&'a crate::EmailAddress> for addr::email::Address<'a> {
+    type Error = addr::error::Error<'a>;
+
+    ///
+    /// Parse `email` with the embedded Public Suffix List; the `addr` crate accepts neither
+    /// quoted local parts nor domain literals.
+    ///
+    fn try_from(email: &'a crate::EmailAddress) -> Result<Self, Self::Error> {
+        use addr::parser::EmailAddress;
+        list().parse_email_address(email.as_str())
+    }
+}
+
+#[cfg(feature = "addr")]
+impl<'a> TryFrom<&'a crate::EmailAddress> for addr::domain::Name<'a> {
+    type Error = addr::error::Error<'a>;
+
+    ///
+    /// Parse the domain of `email` with the embedded Public Suffix List.
+    ///
+    fn try_from(email: &'a crate::EmailAddress) -> Result<Self, Self::Error> {
+        use addr::parser::DomainName;
+        list().parse_domain_name(email.domain())
+    }
+}
+
+#[cfg(feature = "addr")]
+impl TryFrom<addr::email::Address<'_>> for crate::EmailAddress {
+    type Error = Error;
+
+    fn try_from(address: addr::email::Address<'_>) -> Result<Self, Self::Error> {
+        crate::EmailAddress::from_str(address.as_str())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const PUBLIC_SUFFIX_LIST: &str = include_str!("data/public_suffix_list.dat");
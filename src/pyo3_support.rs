@@ -0,0 +1,182 @@
+/*!
+Python bindings, so that Python code validates addresses with exactly the rules of this crate;
+requires the `pyo3` feature, and is built into an extension module named `email_address`, for
+example by `maturin build --features pyo3`.
+
+```python
+import email_address
+
+email = email_address.EmailAddress("Simon@Example.COM")
+assert email.local_part == "Simon"
+assert str(email.normalize()) == "simon@example.com"
+
+assert email_address.is_valid("simon@example.com")
+assert email_address.normalize("\"Simon\"@Example.COM", False) == "Simon@example.com"
+assert email_address.error_code("simon@") == 8
+
+try:
+    email_address.EmailAddress("simon@example..com")
+except email_address.EmailAddressError as e:
+    message, code, position = e.args
+```
+
+The exception `EmailAddressError` is a subclass of `ValueError`, with the message of the `Error`,
+its numeric code as returned by `Error::code`, and the offset of the problem, counted in
+characters as Python indexes a `str` rather than in bytes as `ErrorCode::position`, or `None`, as
+arguments.
+*/
+
+use crate::{EmailAddress, Error, ErrorCode, LocalPartCase};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// The Python class `EmailAddress`, a valid address; the constructor raises
+/// `EmailAddressError` if its argument is not.
+///
+#[pyclass(
+    name = "EmailAddress",
+    module = "email_address",
+    frozen,
+    eq,
+    hash,
+    str,
+    from_py_object
+)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PyEmailAddress(EmailAddress);
+
+create_exception!(
+    email_address,
+    EmailAddressError,
+    PyValueError,
+    "Raised when a string is not a valid address."
+);
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Initialize the Python module `email_address`.
+///
+#[pymodule]
+pub fn email_address(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyEmailAddress>()?;
+    module.add(
+        "EmailAddressError",
+        module.py().get_type::<EmailAddressError>(),
+    )?;
+    module.add_function(wrap_pyfunction!(is_valid, module)?)?;
+    module.add_function(wrap_pyfunction!(normalize, module)?)?;
+    module.add_function(wrap_pyfunction!(error_code, module)?)?;
+    Ok(())
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for PyEmailAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl From<EmailAddress> for PyEmailAddress {
+    fn from(email: EmailAddress) -> Self {
+        Self(email)
+    }
+}
+
+impl From<PyEmailAddress> for EmailAddress {
+    fn from(email: PyEmailAddress) -> Self {
+        email.0
+    }
+}
+
+#[pymethods]
+impl PyEmailAddress {
+    #[new]
+    fn new(address: &str) -> PyResult<Self> {
+        EmailAddress::from_str(address)
+            .map(Self)
+            .map_err(|error| address_error(address, error))
+    }
+
+    #[getter]
+    fn local_part(&self) -> &str {
+        self.0.local_part()
+    }
+
+    #[getter]
+    fn domain(&self) -> &str {
+        self.0.domain()
+    }
+
+    #[pyo3(signature = (lowercase_local_part = true))]
+    fn normalize(&self, lowercase_local_part: bool) -> Self {
+        Self(self.0.normalized(local_part_case(lowercase_local_part)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EmailAddress({:?})", self.0.as_str())
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+#[pyfunction]
+fn is_valid(address: &str) -> bool {
+    EmailAddress::is_valid(address)
+}
+
+#[pyfunction]
+#[pyo3(signature = (address, lowercase_local_part = true))]
+fn normalize(address: &str, lowercase_local_part: bool) -> PyResult<String> {
+    EmailAddress::from_str(address)
+        .map(|email| {
+            email
+                .normalized(local_part_case(lowercase_local_part))
+                .to_string()
+        })
+        .map_err(|error| address_error(address, error))
+}
+
+#[pyfunction]
+fn error_code(address: &str) -> Option<u16> {
+    EmailAddress::from_str(address)
+        .err()
+        .map(|error| error.code())
+}
+
+///
+/// The exception for `error`, found when parsing `address`, with the byte offset of the error
+/// converted to a character offset.
+///
+fn address_error(address: &str, error: Error) -> PyErr {
+    let ErrorCode { code, position, .. } = error.error_code();
+    let position = if position == ErrorCode::NO_POSITION {
+        None
+    } else {
+        Some(address.get(..position).unwrap_or(address).chars().count())
+    };
+    EmailAddressError::new_err((error.to_string(), code, position))
+}
+
+fn local_part_case(lowercase: bool) -> LocalPartCase {
+    if lowercase {
+        LocalPartCase::Lowercase
+    } else {
+        LocalPartCase::Preserve
+    }
+}
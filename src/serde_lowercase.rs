@@ -0,0 +1,47 @@
+/*!
+Serialization of an `EmailAddress` in its lowercase form, as given by
+`EmailAddress::to_lowercase`, for use with the `serde` `with` attribute, so that addresses
+written by different services compare, hash, and join consistently.
+
+```rust
+use email_address::EmailAddress;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+#[derive(Serialize, Deserialize)]
+struct Contact {
+    #[serde(with = "email_address::serde_lowercase")]
+    email: EmailAddress,
+}
+
+let contact = Contact {
+    email: EmailAddress::from_str("Simon@Example.COM").unwrap(),
+};
+let json = serde_json::to_string(&contact).unwrap();
+assert_eq!(json, r#"{"email":{"local":"simon","domain":"example.com"}}"#);
+```
+
+The serialized form is otherwise that of `EmailAddress`; a deserialized address is also
+lowercased, so that values written before the attribute was added are read consistently.
+*/
+
+use crate::EmailAddress;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Serialize `email` in its lowercase form.
+///
+pub fn serialize<S: Serializer>(email: &EmailAddress, serializer: S) -> Result<S::Ok, S::Error> {
+    email.to_lowercase().serialize(serializer)
+}
+
+///
+/// Deserialize an address, returning it in its lowercase form.
+///
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<EmailAddress, D::Error> {
+    EmailAddress::deserialize(deserializer).map(|email| email.to_lowercase())
+}
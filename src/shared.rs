@@ -0,0 +1,98 @@
+/*!
+An address that is cheap to clone, for programs that pass the same addresses between many
+threads or tasks; the address is stored once, and each clone only increments a reference count.
+*/
+
+use crate::{EmailAddress, Error};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+use std::sync::Arc;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// An email address backed by an `Arc<str>`, so that cloning it never allocates.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let email = SharedEmailAddress::from_str("simon@example.com").unwrap();
+/// let clone = email.clone();
+///
+/// assert_eq!(clone.domain(), "example.com");
+/// assert!(std::ptr::eq(email.as_str(), clone.as_str()));
+/// ```
+///
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SharedEmailAddress {
+    address: Arc<str>,
+    at: usize,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Display for SharedEmailAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.address)
+    }
+}
+
+impl Debug for SharedEmailAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedEmailAddress")
+            .field("local", &self.local_part())
+            .field("domain", &self.domain())
+            .finish()
+    }
+}
+
+impl FromStr for SharedEmailAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EmailAddress::from_str(s).map(Self::from)
+    }
+}
+
+impl AsRef<str> for SharedEmailAddress {
+    fn as_ref(&self) -> &str {
+        &self.address
+    }
+}
+
+impl From<EmailAddress> for SharedEmailAddress {
+    fn from(email: EmailAddress) -> Self {
+        Self {
+            at: email.local_part().len(),
+            address: Arc::from(email.as_str()),
+        }
+    }
+}
+
+impl From<&SharedEmailAddress> for EmailAddress {
+    fn from(email: &SharedEmailAddress) -> Self {
+        EmailAddress::from_parts(email.local_part(), email.domain())
+    }
+}
+
+impl SharedEmailAddress {
+    /// Returns the email address as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the local part of the address.
+    pub fn local_part(&self) -> &str {
+        &self.address[..self.at]
+    }
+
+    /// Returns the domain of the address.
+    pub fn domain(&self) -> &str {
+        &self.address[self.at + 1..]
+    }
+}
@@ -0,0 +1,426 @@
+/*!
+Checks that a mailbox exists by asking the mail server of its domain, an SMTP "callout";
+requires the `smtp` feature, and a Tokio runtime.
+
+The callout connects to the most preferred mail exchange of the domain, and starts a mail
+transaction with `EHLO`, `MAIL FROM`, and `RCPT TO`, but quits before sending `DATA`, so no
+mail is delivered. The reply to `RCPT TO` says whether the server will accept mail for the
+mailbox; many servers accept mail for every mailbox, and others refuse callouts altogether, so a
+result of `Accepted` does not prove that the mailbox exists.
+
+If either address requires the `SMTPUTF8` extension, RFC 6531, the transaction is only started
+with a server that advertises it in its reply to `EHLO`; otherwise the result is `Unknown`, as a
+server without the extension would refuse the address whether or not the mailbox exists.
+*/
+
+use crate::dns::{self, DnsCheck};
+use crate::{DomainPart, EmailAddress, SMTP_UTF8_PARAMETER};
+use hickory_resolver::TokioAsyncResolver;
+use std::fmt::{Display, Formatter};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A configured SMTP callout, used to ask the mail server of an address whether it will accept
+/// mail for it.
+///
+/// ```rust,no_run
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// # async fn check() {
+/// let callout = Callout::new("verifier.example.net");
+/// let email = EmailAddress::from_str("simon@example.com").unwrap();
+/// if callout.verify(&email).await.is_rejected() {
+///     println!("no such mailbox: {}", email);
+/// }
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Callout {
+    helo: String,
+    mail_from: Option<EmailAddress>,
+    port: u16,
+    timeout: Duration,
+}
+
+///
+/// A reply from an SMTP server, with the text of each line of a multi-line reply joined by
+/// newlines.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Reply {
+    code: u16,
+    text: String,
+}
+
+///
+/// The result of an SMTP callout.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CalloutResult {
+    /// The server accepted the recipient.
+    Accepted,
+    /// The server permanently rejected the recipient, or the domain has a null MX record.
+    Rejected(Reply),
+    ///
+    /// The server temporarily refused the recipient in a way that suggests greylisting; the same
+    /// callout made again after some minutes is likely to get a definite answer. Every 450 or
+    /// 451 reply is taken as greylisting, as those are the codes greylisting servers use, whether
+    /// or not its text says so, as is any other 4xx reply whose text mentions greylisting.
+    ///
+    Greylisted(Reply),
+    /// The callout could not be completed, or the server's answer was not definite.
+    Unknown(String),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Callout {
+    ///
+    /// A callout that introduces itself with `helo`, which should be the fully qualified domain
+    /// name of the host making the callout, and uses the null reverse-path, `MAIL FROM:<>`, as
+    /// a bounce would.
+    ///
+    pub fn new(helo: &str) -> Self {
+        Self {
+            helo: helo.to_string(),
+            mail_from: None,
+            port: SMTP_PORT,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    ///
+    /// Use `mail_from` as the reverse-path, rather than the null reverse-path; some servers
+    /// reject callouts that do not name a sender.
+    ///
+    pub fn with_mail_from(mut self, mail_from: EmailAddress) -> Self {
+        self.mail_from = Some(mail_from);
+        self
+    }
+
+    ///
+    /// Connect to mail servers on `port`, rather than on port 25.
+    ///
+    pub fn with_port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    ///
+    /// Abandon the callout, with a result of `Unknown`, if it has not finished within `timeout`;
+    /// the default is 30 seconds.
+    ///
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    ///
+    /// Ask the mail server of the domain of `email` whether it will accept mail for it, finding
+    /// the server with the resolver shared with `EmailAddress::check_dns`.
+    ///
+    pub async fn verify(&self, email: &EmailAddress) -> CalloutResult {
+        match dns::shared_resolver() {
+            Ok(resolver) => self.verify_with(email, resolver).await,
+            Err(e) => CalloutResult::Unknown(e.to_string()),
+        }
+    }
+
+    ///
+    /// As `verify`, but finding the mail server with `resolver`.
+    ///
+    pub async fn verify_with(
+        &self,
+        email: &EmailAddress,
+        resolver: &TokioAsyncResolver,
+    ) -> CalloutResult {
+        let addresses = std::iter::once(email).chain(&self.mail_from);
+        if let Some(address) = addresses.into_iter().find(|email| !email.is_smtp_mailbox()) {
+            return CalloutResult::Unknown(format!(
+                "the address {:?} is not an SMTP mailbox",
+                address.as_str()
+            ));
+        }
+        let hosts = match dns::check(email, resolver).await {
+            Ok(DnsCheck::Mx(hosts)) => hosts.into_iter().map(Host::Name).collect(),
+            Ok(DnsCheck::ImplicitMx) => vec![Host::Name(email.domain().to_string())],
+            Ok(DnsCheck::AddressLiteral) => match email.domain_part() {
+                DomainPart::Ipv4(address) => vec![Host::Address(address.into())],
+                DomainPart::Ipv6(address) => vec![Host::Address(address.into())],
+                _ => {
+                    return CalloutResult::Unknown(
+                        "the domain literal is not an IP address".to_string(),
+                    )
+                }
+            },
+            Ok(DnsCheck::NullMx) => return CalloutResult::Rejected(Reply::null_mx()),
+            Ok(_) => {
+                return CalloutResult::Unknown("the domain has no mail server".to_string());
+            }
+            Err(e) => return CalloutResult::Unknown(e.to_string()),
+        };
+        match tokio::time::timeout(self.timeout, self.call(&hosts, email)).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(e)) => CalloutResult::Unknown(e.to_string()),
+            Err(_) => CalloutResult::Unknown("the callout timed out".to_string()),
+        }
+    }
+
+    async fn call(&self, hosts: &[Host], email: &EmailAddress) -> std::io::Result<CalloutResult> {
+        let mut stream = None;
+        let mut error = None;
+        for host in hosts {
+            let connected = match host {
+                Host::Name(name) => TcpStream::connect((name.as_str(), self.port)).await,
+                Host::Address(address) => {
+                    TcpStream::connect(SocketAddr::new(*address, self.port)).await
+                }
+            };
+            match connected {
+                Ok(connected) => {
+                    stream = Some(connected);
+                    break;
+                }
+                Err(e) => error = Some(e),
+            }
+        }
+        let mut stream = match (stream, error) {
+            (Some(stream), _) => BufReader::new(stream),
+            (None, Some(e)) => return Err(e),
+            (None, None) => return Ok(CalloutResult::Unknown("no mail server".to_string())),
+        };
+
+        let reply = read_reply(&mut stream).await?;
+        if reply.code != 220 {
+            return Ok(CalloutResult::Unknown(format!(
+                "greeting refused: {}",
+                reply
+            )));
+        }
+        let mut reply = command(&mut stream, &format!("EHLO {}", self.helo)).await?;
+        let mut extended = true;
+        if reply.is_permanent_failure() {
+            reply = command(&mut stream, &format!("HELO {}", self.helo)).await?;
+            extended = false;
+        }
+        if !reply.is_success() {
+            return Ok(CalloutResult::Unknown(format!("HELO refused: {}", reply)));
+        }
+        let smtputf8 = std::iter::once(email)
+            .chain(&self.mail_from)
+            .any(EmailAddress::requires_smtputf8);
+        if smtputf8 && !(extended && reply.has_extension(SMTP_UTF8_PARAMETER)) {
+            let _ = command(&mut stream, "QUIT").await;
+            return Ok(CalloutResult::Unknown(format!(
+                "the server does not support {}",
+                SMTP_UTF8_PARAMETER
+            )));
+        }
+        let mail_from = self
+            .mail_from
+            .as_ref()
+            .map(EmailAddress::as_str)
+            .unwrap_or_default();
+        let mut mail = format!("MAIL FROM:<{}>", mail_from);
+        if smtputf8 {
+            mail.push(' ');
+            mail.push_str(SMTP_UTF8_PARAMETER);
+        }
+        let reply = command(&mut stream, &mail).await?;
+        if !reply.is_success() {
+            return Ok(CalloutResult::Unknown(format!(
+                "MAIL FROM refused: {}",
+                reply
+            )));
+        }
+        let reply = command(&mut stream, &format!("RCPT TO:<{}>", email.as_str())).await?;
+        // The answer is known; a failure to quit cleanly does not change it.
+        let _ = command(&mut stream, "QUIT").await;
+        Ok(CalloutResult::from(reply))
+    }
+}
+
+impl CalloutResult {
+    /// Returns `true` if the server accepted the recipient.
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, CalloutResult::Accepted)
+    }
+
+    /// Returns `true` if the server permanently rejected the recipient.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self, CalloutResult::Rejected(_))
+    }
+}
+
+impl From<Reply> for CalloutResult {
+    fn from(reply: Reply) -> Self {
+        if reply.is_success() {
+            CalloutResult::Accepted
+        } else if reply.is_permanent_failure() {
+            CalloutResult::Rejected(reply)
+        } else if reply.is_greylisting() {
+            CalloutResult::Greylisted(reply)
+        } else {
+            CalloutResult::Unknown(reply.to_string())
+        }
+    }
+}
+
+impl Display for Reply {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.code, self.text.replace('\n', " "))
+    }
+}
+
+impl Reply {
+    /// Returns the three-digit reply code.
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Returns the text of the reply, without the reply code.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn null_mx() -> Self {
+        // The reply RFC 7505, §4.1, asks senders to report for a domain with a null MX.
+        Self {
+            code: 556,
+            text: "5.1.10 Recipient address has null MX".to_string(),
+        }
+    }
+
+    fn is_success(&self) -> bool {
+        (200..300).contains(&self.code)
+    }
+
+    fn is_permanent_failure(&self) -> bool {
+        (500..600).contains(&self.code)
+    }
+
+    fn has_extension(&self, keyword: &str) -> bool {
+        // The first line of a reply to EHLO greets the client, and each later line starts with
+        // the keyword of an extension the server supports, RFC 5321, §4.1.1.1.
+        self.text.lines().skip(1).any(|line| {
+            matches!(line.split(' ').next(), Some(word) if word.eq_ignore_ascii_case(keyword))
+        })
+    }
+
+    fn is_greylisting(&self) -> bool {
+        let text = self.text.to_lowercase();
+        matches!(self.code, 450 | 451)
+            || (400..500).contains(&self.code)
+                && (text.contains("greylist") || text.contains("graylist"))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Types
+// ------------------------------------------------------------------------------------------------
+
+#[derive(Debug)]
+enum Host {
+    Name(String),
+    Address(IpAddr),
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+async fn command(stream: &mut BufReader<TcpStream>, command: &str) -> std::io::Result<Reply> {
+    let stream_mut = stream.get_mut();
+    stream_mut.write_all(command.as_bytes()).await?;
+    stream_mut.write_all(b"\r\n").await?;
+    read_reply(stream).await
+}
+
+async fn read_reply(stream: &mut BufReader<TcpStream>) -> std::io::Result<Reply> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    loop {
+        line.clear();
+        let read = (&mut *stream)
+            .take(MAX_REPLY_LINE_LENGTH as u64)
+            .read_line(&mut line)
+            .await?;
+        if read == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        if read == MAX_REPLY_LINE_LENGTH && !line.ends_with('\n') {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!(
+                    "a reply line is longer than {} octets",
+                    MAX_REPLY_LINE_LENGTH
+                ),
+            ));
+        }
+        match parse_reply_line(line.trim_end_matches(['\r', '\n'])) {
+            Some((code, last, text)) => {
+                if lines.len() == MAX_REPLY_LINES {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("a reply is longer than {} lines", MAX_REPLY_LINES),
+                    ));
+                }
+                lines.push(text.to_string());
+                if last {
+                    return Ok(Reply {
+                        code,
+                        text: lines.join("\n"),
+                    });
+                }
+            }
+            None => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("not an SMTP reply: {:?}", line),
+                ))
+            }
+        }
+    }
+}
+
+///
+/// Split a line of a reply, RFC 5321, §4.2, into its code, whether it is the last line of the
+/// reply, and its text.
+///
+pub(crate) fn parse_reply_line(line: &str) -> Option<(u16, bool, &str)> {
+    let code = line.get(..3)?;
+    if !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let code = code.parse().ok()?;
+    match line.as_bytes().get(3) {
+        None => Some((code, true, "")),
+        Some(b' ') => Some((code, true, &line[4..])),
+        Some(b'-') => Some((code, false, &line[4..])),
+        Some(_) => None,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const SMTP_PORT: u16 = 25;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+// The longest reply line, including the code and CRLF, RFC 5321, §4.5.3.1.5.
+const MAX_REPLY_LINE_LENGTH: usize = 512;
+
+// Enough for the reply to EHLO of a server with many extensions.
+const MAX_REPLY_LINES: usize = 32;
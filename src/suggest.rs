@@ -0,0 +1,289 @@
+/*!
+Suggested corrections for mistyped domains, found by comparing the domain of an address with a
+dictionary of known domains; by default the domains of the major mail providers.
+
+Domains are compared by edit distance, counting the insertion, deletion, substitution, or
+transposition of a single character as one edit. Unless a maximum distance is set, a domain is
+only corrected to one that is close relative to its length, so that short domains such as `me.com`
+are not suggested for every short domain.
+*/
+
+use crate::{EmailAddress, DOT};
+use std::borrow::Cow;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Suggests corrections for mistyped domains from a dictionary of known domains.
+///
+/// ```rust
+/// use email_address::*;
+/// use std::str::FromStr;
+///
+/// let suggester = Suggester::default()
+///     .without_provider_domains()
+///     .with_domains(vec!["example.com", "example.org"])
+///     .with_max_distance(1);
+///
+/// let email = EmailAddress::from_str("simon@exmaple.com").unwrap();
+/// assert_eq!(suggester.suggest(&email), Some("example.com"));
+///
+/// let email = EmailAddress::from_str("simon@gmial.com").unwrap();
+/// assert_eq!(suggester.suggest(&email), None);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Suggester {
+    domains: Vec<Cow<'static, str>>,
+    max_distance: Option<usize>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+pub(crate) fn suggest_domain(domain: &str) -> Option<&'static str> {
+    closest(domain, PROVIDER_DOMAINS.iter().copied(), None)
+}
+
+///
+/// If the top-level domain of `domain` is not a delegated top-level domain, but is one edit from
+/// a common one, or is a country-code second-level domain missing its dot, return the domain
+/// with the corrected top-level domain.
+///
+pub(crate) fn suggest_tld(domain: &str) -> Option<String> {
+    let (rest, tld) = domain.rsplit_once(DOT)?;
+    let tld = tld.to_lowercase();
+    if !tld.is_ascii() || tld.starts_with("xn--") || is_tld(&tld) {
+        return None;
+    }
+    let corrected = missing_dot(&tld)
+        .or_else(|| {
+            COMMON_TLDS
+                .iter()
+                .find(|candidate| edit_distance(&tld, candidate) == 1)
+                .map(|candidate| candidate.to_string())
+        })
+        .or_else(|| {
+            let mut candidates = tlds().filter(|candidate| edit_distance(&tld, candidate) == 1);
+            match (candidates.next(), candidates.next()) {
+                (Some(candidate), None) => Some(candidate.to_string()),
+                _ => None,
+            }
+        })?;
+    Some(format!("{}{}{}", rest, DOT, corrected))
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for Suggester {
+    fn default() -> Self {
+        Self {
+            domains: PROVIDER_DOMAINS
+                .iter()
+                .copied()
+                .map(Cow::Borrowed)
+                .collect(),
+            max_distance: None,
+        }
+    }
+}
+
+impl Suggester {
+    ///
+    /// Remove the built-in mail provider domains from the dictionary.
+    ///
+    pub fn without_provider_domains(mut self) -> Self {
+        self.domains
+            .retain(|domain| matches!(domain, Cow::Owned(_)));
+        self
+    }
+
+    ///
+    /// Add `domain` to the dictionary.
+    ///
+    pub fn with_domain<S: Into<String>>(mut self, domain: S) -> Self {
+        self.domains.push(Cow::Owned(domain.into().to_lowercase()));
+        self
+    }
+
+    ///
+    /// Add each of `domains` to the dictionary. Domains added earlier are preferred when two
+    /// domains are equally close to a mistyped domain.
+    ///
+    pub fn with_domains<I, S>(self, domains: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        domains
+            .into_iter()
+            .fold(self, |suggester, domain| suggester.with_domain(domain))
+    }
+
+    ///
+    /// Suggest any domain within `max_distance` edits, rather than only those close relative to
+    /// their length.
+    ///
+    pub fn with_max_distance(mut self, max_distance: usize) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    ///
+    /// If the domain of `email` looks like a mistyped domain from the dictionary, return the
+    /// domain from the dictionary.
+    ///
+    pub fn suggest(&self, email: &EmailAddress) -> Option<&str> {
+        self.suggest_domain(email.domain())
+    }
+
+    ///
+    /// If `domain` looks like a mistyped domain from the dictionary, return the domain from the
+    /// dictionary.
+    ///
+    pub fn suggest_domain(&self, domain: &str) -> Option<&str> {
+        closest(
+            domain,
+            self.domains.iter().map(|domain| domain.as_ref()),
+            self.max_distance,
+        )
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn tlds() -> impl Iterator<Item = &'static str> {
+    TLDS.lines().filter(|line| !line.starts_with('#'))
+}
+
+fn is_tld(tld: &str) -> bool {
+    tlds().any(|candidate| candidate == tld)
+}
+
+///
+/// A country-code top-level domain run together with a common second-level domain, such as
+/// `couk` for `co.uk`.
+///
+fn missing_dot(tld: &str) -> Option<String> {
+    SECOND_LEVEL_DOMAINS.iter().find_map(|second_level| {
+        let country = tld.strip_prefix(second_level)?;
+        if country.len() == 2 && is_tld(country) {
+            Some(format!("{}{}{}", second_level, DOT, country))
+        } else {
+            None
+        }
+    })
+}
+
+fn closest<'a, I>(domain: &str, candidates: I, max_distance: Option<usize>) -> Option<&'a str>
+where
+    I: Iterator<Item = &'a str> + Clone,
+{
+    let domain = domain.to_lowercase();
+    if candidates.clone().any(|candidate| candidate == domain) {
+        return None;
+    }
+    candidates
+        .map(|candidate| (candidate, edit_distance(&domain, candidate)))
+        .filter(|(candidate, distance)| match max_distance {
+            Some(max_distance) => *distance <= max_distance,
+            None => *distance <= MAX_DISTANCE && *distance * 7 <= candidate.len(),
+        })
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+///
+/// The optimal string alignment distance between `a` and `b`; the Levenshtein distance, also
+/// counting the transposition of two adjacent characters as a single edit.
+///
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let width = b.len() + 1;
+    let mut distances = vec![0; (a.len() + 1) * width];
+    for i in 0..=a.len() {
+        distances[i * width] = i;
+    }
+    for (j, distance) in distances.iter_mut().enumerate().take(width) {
+        *distance = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (distances[(i - 1) * width + j] + 1)
+                .min(distances[i * width + j - 1] + 1)
+                .min(distances[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[(i - 2) * width + j - 2] + 1);
+            }
+            distances[i * width + j] = distance;
+        }
+    }
+    distances[a.len() * width + b.len()]
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const MAX_DISTANCE: usize = 2;
+
+const TLDS: &str = include_str!("data/tlds.txt");
+
+///
+/// The most widely used top-level domains, checked in order before the complete list so that,
+/// for example, `.cmo` is corrected to `.com` and not `.cm`.
+///
+const COMMON_TLDS: &[&str] = &[
+    "com", "net", "org", "edu", "gov", "io", "co", "uk", "de", "fr", "ca", "au", "us", "info",
+    "biz", "me", "nl", "it", "es", "ru", "jp", "br", "in",
+];
+
+///
+/// Second-level domains that are commonly registered under country-code top-level domains,
+/// longest first so that `comau` is read as `com.au` and not `co.mau`.
+///
+const SECOND_LEVEL_DOMAINS: &[&str] = &["com", "org", "net", "gov", "edu", "co", "ac", "or", "ne"];
+
+///
+/// Major mail providers, most widely used first so that the first of two equally close
+/// candidates is the more likely.
+///
+const PROVIDER_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "yahoo.com",
+    "hotmail.com",
+    "outlook.com",
+    "icloud.com",
+    "aol.com",
+    "live.com",
+    "msn.com",
+    "googlemail.com",
+    "hotmail.co.uk",
+    "yahoo.co.uk",
+    "ymail.com",
+    "me.com",
+    "mac.com",
+    "protonmail.com",
+    "proton.me",
+    "gmx.com",
+    "gmx.de",
+    "gmx.net",
+    "web.de",
+    "mail.com",
+    "yandex.ru",
+    "mail.ru",
+    "qq.com",
+    "163.com",
+    "zoho.com",
+    "fastmail.com",
+    "comcast.net",
+    "verizon.net",
+    "att.net",
+];
@@ -0,0 +1,83 @@
+/*!
+Integration with the derive macro of the `validator` crate, replacing its own check of email
+addresses, which is based on the HTML form rules, with this crate's; requires the `validator`
+feature.
+
+Fields that are already an `EmailAddress` need no check, but can be given the `email`
+validation, which they always pass, and fields that are strings can be checked with the custom
+function `validate_email_address`.
+
+```rust
+use email_address::EmailAddress;
+use validator::Validate;
+
+#[derive(Validate)]
+struct SignUp {
+    #[validate(custom(function = "email_address::validate_email_address"))]
+    email: String,
+    #[validate(email)]
+    backup: Option<EmailAddress>,
+}
+
+let sign_up = SignUp {
+    email: "\"simon johnston\"@example.com".to_string(),
+    backup: None,
+};
+assert!(sign_up.validate().is_ok());
+
+let sign_up = SignUp {
+    email: "simon@example..com".to_string(),
+    backup: None,
+};
+let errors = sign_up.validate().unwrap_err();
+assert_eq!(errors.field_errors()["email"][0].code, "email");
+```
+*/
+
+use crate::EmailAddress;
+use std::borrow::Cow;
+use std::str::FromStr;
+use validator::{ValidateEmail, ValidationError};
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Check that `value` is a valid address, for use as a `validator` custom function; the error
+/// has the code `email`, as the built-in check does, the message of this crate's `Error`, and
+/// the parameter `error`, the numeric code of that error as returned by `Error::code`.
+///
+pub fn validate_email_address<S: AsRef<str> + ?Sized>(value: &S) -> Result<(), ValidationError> {
+    EmailAddress::from_str(value.as_ref())
+        .map(|_| ())
+        .map_err(|error| {
+            let mut validation =
+                ValidationError::new(VALIDATION_CODE).with_message(Cow::Owned(error.to_string()));
+            validation.add_param(Cow::Borrowed(ERROR_PARAMETER), &error.code());
+            validation.add_param(Cow::Borrowed(VALUE_PARAMETER), &value.as_ref());
+            validation
+        })
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl ValidateEmail for EmailAddress {
+    fn validate_email(&self) -> bool {
+        true
+    }
+
+    fn as_email_string(&self) -> Option<Cow<'_, str>> {
+        Some(Cow::Borrowed(self.as_str()))
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const VALIDATION_CODE: &str = "email";
+
+const ERROR_PARAMETER: &str = "error";
+
+const VALUE_PARAMETER: &str = "value";
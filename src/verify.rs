@@ -0,0 +1,664 @@
+/*!
+A pipeline of checks, from syntax to the mail server of the domain, configured once for a
+deployment and run on each address to give a `VerificationReport`.
+
+Each check is run in turn, and timed; a check that fails permanently ends the pipeline, as the
+checks after it would have nothing useful to add. The checks that need the network are only
+run when enabled, and only with the `dns` and `smtp` features.
+
+As well as the outcome of each check, the report scores the address from 0 to 100, deducting
+a fixed penalty for each `Reason` found, so that an application can accept addresses above a
+threshold of its choosing without interpreting each check.
+*/
+
+use crate::{DomainList, EmailAddress, Options, DOT, LBRACKET};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "dns")]
+use crate::DnsCheck;
+#[cfg(feature = "normalize")]
+use crate::LocalPartCase;
+#[cfg(feature = "smtp")]
+use crate::{Callout, CalloutResult};
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A configured pipeline of checks to verify addresses with.
+///
+/// ```rust
+/// use email_address::*;
+///
+/// let disposable = DomainList::default().with_domain("mailinator.com");
+/// let verifier = Verifier::new()
+///     .with_disposable_domains(disposable, Policy::Reject)
+///     .with_role_accounts(Policy::Warn);
+///
+/// let report = block_on(verifier.verify("info@example.com"));
+/// assert_eq!(report.verdict(), Verdict::Risky);
+///
+/// let report = block_on(verifier.verify("simon@mailinator.com"));
+/// assert_eq!(report.verdict(), Verdict::Invalid);
+/// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+/// #     let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+/// #     runtime.block_on(future)
+/// # }
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Verifier {
+    options: Options,
+    #[cfg(feature = "normalize")]
+    normalize: Option<LocalPartCase>,
+    disposable: Option<(DomainList, Policy)>,
+    roles: Option<(HashSet<String>, Policy)>,
+    #[cfg(feature = "dns")]
+    dns: bool,
+    #[cfg(feature = "smtp")]
+    callout: Option<Callout>,
+}
+
+///
+/// How an address that a check matches, such as one at a disposable domain, is treated.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Policy {
+    /// The check reports a warning, and the address is `Verdict::Risky`.
+    Warn,
+    /// The check fails, and the address is `Verdict::Invalid`.
+    Reject,
+}
+
+///
+/// A check that a `Verifier` can run.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Check {
+    /// The address is parsed with the verifier's `Options`.
+    Syntax,
+    /// The address is normalized, as by `EmailAddress::normalized`.
+    Normalization,
+    /// The domain is compared with a list of disposable email domains.
+    Disposable,
+    /// The local part is compared with a list of role accounts.
+    Role,
+    /// The domain is looked up in DNS, as by `EmailAddress::check_dns`.
+    Dns,
+    /// The mail server of the domain is asked whether it will accept the address.
+    Smtp,
+}
+
+///
+/// The outcome of one check.
+///
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CheckStatus {
+    /// The check passed.
+    Passed,
+    /// The check passed, but found something that makes the address risky to send to.
+    Warning(String),
+    /// The check failed.
+    Failed(String),
+    /// The check could not decide, such as when a DNS lookup fails, or a server greylists.
+    Unknown(String),
+}
+
+///
+/// The overall verdict on an address, from the outcomes of every check run.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Verdict {
+    /// Every check passed.
+    Valid,
+    /// Every check passed, but at least one with a warning.
+    Risky,
+    /// No check failed, but at least one could not decide.
+    Unknown,
+    /// At least one check failed.
+    Invalid,
+}
+
+///
+/// A finding that lowers the score of an address; `code` returns a stable identifier for each,
+/// for logging and analytics.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Reason {
+    /// The address is not valid; the score is 0.
+    InvalidSyntax,
+    /// The local part is a quoted string, which many systems mishandle.
+    QuotedLocalPart,
+    /// The domain is a single label, such as `localhost`, with no dot.
+    DotlessDomain,
+    /// The domain is an address literal, rather than a domain name.
+    AddressLiteral,
+    /// The domain is that of a disposable email service.
+    Disposable,
+    /// The local part is that of a role account, rather than a person.
+    RoleAccount,
+    /// The domain has no MX record, only an address record.
+    NoMxRecord,
+    /// The domain has a null MX record, and accepts no mail; the score is 0.
+    NullMx,
+    /// The domain has no MX or address records, or does not exist; the score is 0.
+    NoMailServer,
+    /// The DNS lookup of the domain failed.
+    DnsUnknown,
+    /// The mail server rejected the mailbox; the score is 0.
+    MailboxRejected,
+    /// The mail server greylisted the callout.
+    Greylisted,
+    /// The SMTP callout did not get a definite answer.
+    SmtpUnknown,
+}
+
+///
+/// The outcome, and the time taken, of one check run by a `Verifier`.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckReport {
+    check: Check,
+    status: CheckStatus,
+    elapsed: Duration,
+}
+
+///
+/// The result of verifying an address; the outcome of each check run, in order, and the overall
+/// verdict.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    address: String,
+    email: Option<EmailAddress>,
+    checks: Vec<CheckReport>,
+    reasons: Vec<Reason>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl Default for Verifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Verifier {
+    ///
+    /// A verifier that only checks syntax, with the default `Options`; enable other checks with
+    /// the `with_*` methods.
+    ///
+    pub fn new() -> Self {
+        Self {
+            options: Options::default(),
+            #[cfg(feature = "normalize")]
+            normalize: None,
+            disposable: None,
+            roles: None,
+            #[cfg(feature = "dns")]
+            dns: false,
+            #[cfg(feature = "smtp")]
+            callout: None,
+        }
+    }
+
+    ///
+    /// Parse addresses using the rules selected by `options`, rather than the defaults.
+    ///
+    pub fn with_options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    ///
+    /// Normalize each valid address, lowercasing the local part according to `case`; the
+    /// normalized address is used by the checks that follow, and by `VerificationReport::email`.
+    ///
+    #[cfg(feature = "normalize")]
+    pub fn with_normalization(mut self, case: LocalPartCase) -> Self {
+        self.normalize = Some(case);
+        self
+    }
+
+    ///
+    /// Check the domain of each address against `domains`, such as `DomainList::disposable`,
+    /// treating an address that matches according to `policy`.
+    ///
+    pub fn with_disposable_domains(mut self, domains: DomainList, policy: Policy) -> Self {
+        self.disposable = Some((domains, policy));
+        self
+    }
+
+    ///
+    /// Check the local part of each address, ignoring case and any tag, against a built-in list
+    /// of role accounts such as `info` and `postmaster`, treating an address that matches
+    /// according to `policy`.
+    ///
+    pub fn with_role_accounts(self, policy: Policy) -> Self {
+        self.with_role_local_parts(
+            ROLE_ACCOUNTS
+                .lines()
+                .filter(|line| !line.is_empty() && !line.starts_with('#')),
+            policy,
+        )
+    }
+
+    ///
+    /// As `with_role_accounts`, but with the role accounts `local_parts`.
+    ///
+    pub fn with_role_local_parts<I, S>(mut self, local_parts: I, policy: Policy) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let local_parts = local_parts
+            .into_iter()
+            .map(|local_part| local_part.as_ref().to_lowercase())
+            .collect();
+        self.roles = Some((local_parts, policy));
+        self
+    }
+
+    ///
+    /// Look up the domain of each address in DNS, failing an address whose domain does not
+    /// accept mail.
+    ///
+    #[cfg(feature = "dns")]
+    pub fn with_dns(mut self) -> Self {
+        self.dns = true;
+        self
+    }
+
+    ///
+    /// Ask the mail server of each address, with `callout`, whether it will accept mail for the
+    /// address; this also enables the DNS check, as the server must first be found.
+    ///
+    #[cfg(feature = "smtp")]
+    pub fn with_callout(mut self, callout: Callout) -> Self {
+        self.dns = true;
+        self.callout = Some(callout);
+        self
+    }
+
+    ///
+    /// Run each enabled check on `address`, in the order of the variants of `Check`, stopping at
+    /// the first that fails.
+    ///
+    pub async fn verify(&self, address: &str) -> VerificationReport {
+        let mut report = VerificationReport {
+            address: address.to_string(),
+            email: None,
+            checks: Vec::new(),
+            reasons: Vec::new(),
+        };
+
+        let start = Instant::now();
+        let email = match EmailAddress::parse_with(address, &self.options) {
+            Ok(email) => email,
+            Err(e) => {
+                report.push(Check::Syntax, CheckStatus::Failed(e.to_string()), start);
+                report.reasons.push(Reason::InvalidSyntax);
+                return report;
+            }
+        };
+        report.push(Check::Syntax, CheckStatus::Passed, start);
+        if email.is_quoted() {
+            report.reasons.push(Reason::QuotedLocalPart);
+        }
+        if email.domain().starts_with(LBRACKET) {
+            report.reasons.push(Reason::AddressLiteral);
+        } else if !email.domain().contains(DOT) {
+            report.reasons.push(Reason::DotlessDomain);
+        }
+
+        #[cfg(feature = "normalize")]
+        let email = match self.normalize {
+            Some(case) => {
+                let start = Instant::now();
+                let normalized = email.normalized(case);
+                report.push(Check::Normalization, CheckStatus::Passed, start);
+                normalized
+            }
+            None => email,
+        };
+        report.email = Some(email.clone());
+
+        if let Some((domains, policy)) = &self.disposable {
+            let start = Instant::now();
+            let status = if domains.matches(&email) {
+                report.reasons.push(Reason::Disposable);
+                policy.status(format!("{} is a disposable email domain", email.domain()))
+            } else {
+                CheckStatus::Passed
+            };
+            if !report.push(Check::Disposable, status, start) {
+                return report;
+            }
+        }
+
+        if let Some((local_parts, policy)) = &self.roles {
+            let start = Instant::now();
+            let local_part = email.without_tag().local_part_unescaped().to_lowercase();
+            let status = if local_parts.contains(&local_part) {
+                report.reasons.push(Reason::RoleAccount);
+                policy.status(format!("{} is a role account", local_part))
+            } else {
+                CheckStatus::Passed
+            };
+            if !report.push(Check::Role, status, start) {
+                return report;
+            }
+        }
+
+        #[cfg(feature = "dns")]
+        if self.dns {
+            let start = Instant::now();
+            let (status, reason) = match email.check_dns().await {
+                Ok(DnsCheck::ImplicitMx) => (
+                    CheckStatus::Warning(format!(
+                        "{} has no MX record, only an address record",
+                        email.domain()
+                    )),
+                    Some(Reason::NoMxRecord),
+                ),
+                Ok(check) if check.accepts_mail() => (CheckStatus::Passed, None),
+                Ok(DnsCheck::NullMx) => (
+                    CheckStatus::Failed(format!("{} accepts no mail", email.domain())),
+                    Some(Reason::NullMx),
+                ),
+                Ok(_) => (
+                    CheckStatus::Failed(format!("{} has no mail server", email.domain())),
+                    Some(Reason::NoMailServer),
+                ),
+                Err(e) => (
+                    CheckStatus::Unknown(e.to_string()),
+                    Some(Reason::DnsUnknown),
+                ),
+            };
+            report.reasons.extend(reason);
+            let passed = matches!(status, CheckStatus::Passed | CheckStatus::Warning(_));
+            report.push(Check::Dns, status, start);
+            if !passed {
+                return report;
+            }
+        }
+
+        #[cfg(feature = "smtp")]
+        if let Some(callout) = &self.callout {
+            let start = Instant::now();
+            let (status, reason) = match callout.verify(&email).await {
+                CalloutResult::Accepted => (CheckStatus::Passed, None),
+                CalloutResult::Rejected(reply) => (
+                    CheckStatus::Failed(reply.to_string()),
+                    Some(Reason::MailboxRejected),
+                ),
+                CalloutResult::Greylisted(reply) => (
+                    CheckStatus::Unknown(format!("greylisted: {}", reply)),
+                    Some(Reason::Greylisted),
+                ),
+                CalloutResult::Unknown(reason) => {
+                    (CheckStatus::Unknown(reason), Some(Reason::SmtpUnknown))
+                }
+            };
+            report.reasons.extend(reason);
+            report.push(Check::Smtp, status, start);
+        }
+
+        report
+    }
+}
+
+impl Policy {
+    fn status(&self, message: String) -> CheckStatus {
+        match self {
+            Policy::Warn => CheckStatus::Warning(message),
+            Policy::Reject => CheckStatus::Failed(message),
+        }
+    }
+}
+
+impl Display for Check {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Check::Syntax => "syntax",
+                Check::Normalization => "normalization",
+                Check::Disposable => "disposable",
+                Check::Role => "role",
+                Check::Dns => "dns",
+                Check::Smtp => "smtp",
+            }
+        )
+    }
+}
+
+impl Display for CheckStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CheckStatus::Passed => write!(f, "passed"),
+            CheckStatus::Warning(message) => write!(f, "warning: {}", message),
+            CheckStatus::Failed(message) => write!(f, "failed: {}", message),
+            CheckStatus::Unknown(message) => write!(f, "unknown: {}", message),
+        }
+    }
+}
+
+impl CheckStatus {
+    fn verdict(&self) -> Verdict {
+        match self {
+            CheckStatus::Passed => Verdict::Valid,
+            CheckStatus::Warning(_) => Verdict::Risky,
+            CheckStatus::Failed(_) => Verdict::Invalid,
+            CheckStatus::Unknown(_) => Verdict::Unknown,
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Verdict::Valid => "valid",
+                Verdict::Risky => "risky",
+                Verdict::Unknown => "unknown",
+                Verdict::Invalid => "invalid",
+            }
+        )
+    }
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Reason::InvalidSyntax => "the address is not valid",
+                Reason::QuotedLocalPart => "the local part is quoted",
+                Reason::DotlessDomain => "the domain has no dot",
+                Reason::AddressLiteral => "the domain is an address literal",
+                Reason::Disposable => "the domain is a disposable email service",
+                Reason::RoleAccount => "the local part is a role account",
+                Reason::NoMxRecord => "the domain has no MX record",
+                Reason::NullMx => "the domain accepts no mail",
+                Reason::NoMailServer => "the domain has no mail server",
+                Reason::DnsUnknown => "the DNS lookup failed",
+                Reason::MailboxRejected => "the mail server rejected the mailbox",
+                Reason::Greylisted => "the mail server greylisted the callout",
+                Reason::SmtpUnknown => "the SMTP callout was not answered",
+            }
+        )
+    }
+}
+
+impl Reason {
+    /// Returns a stable, `snake_case`, identifier for this reason.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Reason::InvalidSyntax => "invalid_syntax",
+            Reason::QuotedLocalPart => "quoted_local_part",
+            Reason::DotlessDomain => "dotless_domain",
+            Reason::AddressLiteral => "address_literal",
+            Reason::Disposable => "disposable",
+            Reason::RoleAccount => "role_account",
+            Reason::NoMxRecord => "no_mx_record",
+            Reason::NullMx => "null_mx",
+            Reason::NoMailServer => "no_mail_server",
+            Reason::DnsUnknown => "dns_unknown",
+            Reason::MailboxRejected => "mailbox_rejected",
+            Reason::Greylisted => "greylisted",
+            Reason::SmtpUnknown => "smtp_unknown",
+        }
+    }
+
+    /// Returns the number of points this reason deducts from a score of 100.
+    pub fn penalty(&self) -> u8 {
+        match self {
+            Reason::InvalidSyntax
+            | Reason::NullMx
+            | Reason::NoMailServer
+            | Reason::MailboxRejected => 100,
+            Reason::Disposable => 60,
+            Reason::DotlessDomain => 40,
+            Reason::AddressLiteral => 30,
+            Reason::RoleAccount | Reason::DnsUnknown | Reason::SmtpUnknown => 20,
+            Reason::QuotedLocalPart | Reason::NoMxRecord | Reason::Greylisted => 10,
+        }
+    }
+}
+
+impl CheckReport {
+    /// Returns the check that was run.
+    pub fn check(&self) -> Check {
+        self.check
+    }
+
+    /// Returns the outcome of the check.
+    pub fn status(&self) -> &CheckStatus {
+        &self.status
+    }
+
+    /// Returns the time the check took.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+impl Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.address, self.verdict())?;
+        for check in &self.checks {
+            write!(f, "\n  {}: {}", check.check, check.status)?;
+        }
+        Ok(())
+    }
+}
+
+impl VerificationReport {
+    /// Returns the address as it was given to `Verifier::verify`.
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    ///
+    /// Returns the parsed, and if enabled normalized, address; `None` if the syntax check
+    /// failed.
+    ///
+    pub fn email(&self) -> Option<&EmailAddress> {
+        self.email.as_ref()
+    }
+
+    /// Returns the report of each check run, in the order they were run.
+    pub fn checks(&self) -> &[CheckReport] {
+        &self.checks
+    }
+
+    /// Returns the status of `check`, or `None` if it was not run.
+    pub fn status(&self, check: Check) -> Option<&CheckStatus> {
+        self.checks
+            .iter()
+            .find(|report| report.check == check)
+            .map(|report| &report.status)
+    }
+
+    /// Returns the overall verdict; the worst outcome of any check.
+    pub fn verdict(&self) -> Verdict {
+        self.checks
+            .iter()
+            .map(|report| report.status.verdict())
+            .max()
+            .unwrap_or(Verdict::Unknown)
+    }
+
+    /// Returns the reasons found that lower the score of the address, in the order found.
+    pub fn reasons(&self) -> &[Reason] {
+        &self.reasons
+    }
+
+    ///
+    /// Returns the score of the address, from 0 to 100; 100 less the penalty of each reason,
+    /// and never less than 0. The score is independent of the `Policy` of each check, so an
+    /// address may be scored well above 0 and still be `Verdict::Invalid`, if a check was
+    /// configured to reject it.
+    ///
+    /// ```rust
+    /// use email_address::*;
+    ///
+    /// let verifier = Verifier::new().with_role_accounts(Policy::Warn);
+    /// let report = block_on(verifier.verify("\"info\"@localhost"));
+    /// assert_eq!(
+    ///     report.reasons(),
+    ///     &[Reason::QuotedLocalPart, Reason::DotlessDomain, Reason::RoleAccount]
+    /// );
+    /// assert_eq!(report.score(), 30);
+    /// # fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    /// #     let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    /// #     runtime.block_on(future)
+    /// # }
+    /// ```
+    ///
+    pub fn score(&self) -> u8 {
+        let penalty: u32 = self
+            .reasons
+            .iter()
+            .map(|reason| u32::from(reason.penalty()))
+            .sum();
+        100u32.saturating_sub(penalty) as u8
+    }
+
+    /// Returns `true` if the verdict is `Verdict::Valid`.
+    pub fn is_valid(&self) -> bool {
+        self.verdict() == Verdict::Valid
+    }
+
+    /// Returns the total time taken by the checks.
+    pub fn elapsed(&self) -> Duration {
+        self.checks.iter().map(|report| report.elapsed).sum()
+    }
+
+    // Record the outcome of `check`, returning `false` if it failed.
+    fn push(&mut self, check: Check, status: CheckStatus, start: Instant) -> bool {
+        let failed = matches!(status, CheckStatus::Failed(_));
+        self.checks.push(CheckReport {
+            check,
+            status,
+            elapsed: start.elapsed(),
+        });
+        !failed
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const ROLE_ACCOUNTS: &str = include_str!("data/role_accounts.txt");
@@ -0,0 +1,235 @@
+/*!
+Helpers for web handlers that take an address from a form or JSON body; requires the `web`
+feature. An `EmailField` deserializes from any string, so that a malformed address is not a
+deserialization failure of the whole body, and is then checked into an `EmailAddress` or a
+`FieldErrors`, which `axum` turns into a `422 Unprocessable Entity` response with the details
+of each field.
+
+```rust
+use axum::Json;
+use email_address::{EmailField, FieldErrors};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct SignUp {
+    email: EmailField,
+    backup: Option<EmailField>,
+}
+
+async fn sign_up(Json(form): Json<SignUp>) -> Result<String, FieldErrors> {
+    let mut errors = FieldErrors::new();
+    let email = errors.check("email", form.email);
+    let backup = form.backup.and_then(|backup| errors.check("backup", backup));
+    errors.into_result()?;
+    Ok(format!("{} {:?}", email.unwrap(), backup))
+}
+
+let form: SignUp = serde_json::from_str(r#"{"email": "simon@example..com"}"#).unwrap();
+let errors = form.email.into_result("email").unwrap_err();
+assert_eq!(
+    serde_json::to_value(&errors).unwrap()["errors"][0]["field"],
+    "email"
+);
+```
+*/
+
+use crate::{EmailAddress, Error, Options, ParseError};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+// ------------------------------------------------------------------------------------------------
+// Public Types
+// ------------------------------------------------------------------------------------------------
+
+///
+/// A field of a form or JSON body holding an address, which deserializes from any string and
+/// keeps the result of parsing it using the default `Options`.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailField {
+    input: String,
+    result: Result<EmailAddress, ParseError>,
+}
+
+///
+/// The problem with a single field, as reported in the body of a `422` response.
+///
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldError {
+    field: String,
+    code: u16,
+    message: String,
+    error: Error,
+    span: Range<usize>,
+}
+
+///
+/// The problems with the fields of a request, serialized as `{"errors": [...]}` and returned as
+/// a `422 Unprocessable Entity` response; an empty set is not an error.
+///
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FieldErrors {
+    errors: Vec<FieldError>,
+}
+
+// ------------------------------------------------------------------------------------------------
+// Implementations
+// ------------------------------------------------------------------------------------------------
+
+impl<'de> Deserialize<'de> for EmailField {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|input| Self::parse_with(input, &Options::default()))
+    }
+}
+
+impl EmailField {
+    ///
+    /// Create a field from `input`, parsed using `options` rather than the defaults.
+    ///
+    pub fn parse_with<S: Into<String>>(input: S, options: &Options) -> Self {
+        let input = input.into();
+        let result = EmailAddress::parse_spanned(&input, options);
+        Self { input, result }
+    }
+
+    /// Returns the string as it was given.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// Returns `true` if the string is a valid address.
+    pub fn is_valid(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// Returns the parsed address, or the error found in the string.
+    pub fn result(&self) -> Result<&EmailAddress, &ParseError> {
+        self.result.as_ref()
+    }
+
+    ///
+    /// Returns the parsed address, or the error found in the string reported against the
+    /// field named `field`.
+    ///
+    pub fn into_result(self, field: &str) -> Result<EmailAddress, FieldErrors> {
+        let mut errors = FieldErrors::new();
+        match errors.check(field, self) {
+            Some(email) => Ok(email),
+            None => Err(errors),
+        }
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for FieldError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl FieldError {
+    ///
+    /// Create the error for the field named `field` from the error found when parsing it.
+    ///
+    pub fn new<S: Into<String>>(field: S, error: &ParseError) -> Self {
+        Self {
+            field: field.into(),
+            code: error.error().code(),
+            message: error.error().to_string(),
+            error: error.error().clone(),
+            span: error.span(),
+        }
+    }
+
+    /// Returns the name of the field.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the error found in the field.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    /// Returns the byte offsets, within the field's value, of the part that caused the error.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+impl Display for FieldErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, error) in self.errors.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            Display::fmt(error, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FieldErrors {}
+
+impl IntoResponse for FieldErrors {
+    fn into_response(self) -> Response {
+        (FieldErrors::STATUS, Json(self)).into_response()
+    }
+}
+
+impl FieldErrors {
+    /// The status of the response for a request with invalid fields.
+    pub const STATUS: StatusCode = StatusCode::UNPROCESSABLE_ENTITY;
+
+    /// Create an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Returns the address in `email`, or records its error against the field named `field`
+    /// and returns `None`.
+    ///
+    pub fn check(&mut self, field: &str, email: EmailField) -> Option<EmailAddress> {
+        match email.result {
+            Ok(email) => Some(email),
+            Err(error) => {
+                self.push(FieldError::new(field, &error));
+                None
+            }
+        }
+    }
+
+    /// Add the error for a field.
+    pub fn push(&mut self, error: FieldError) {
+        self.errors.push(error);
+    }
+
+    /// Returns `true` if no field has an error.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Returns the error of each field, in the order they were found.
+    pub fn errors(&self) -> &[FieldError] {
+        &self.errors
+    }
+
+    /// Returns `Ok` if no field has an error, or else these errors.
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
@@ -0,0 +1,88 @@
+/*!
+The `xtext` encoding of RFC 3461, §4, used for the parameters of Delivery Status Notification
+requests, such as the original recipient in `ORCPT=rfc822;simon+2Bnews@example.com`.
+
+```ebnf
+xtext          = *( xchar / hexchar )
+
+xchar          = any ASCII CHAR between "!" (33) and "~" (126) inclusive,
+                 except for "+" and "=".
+
+hexchar        = ASCII "+" immediately followed by two upper case hexadecimal digits
+```
+
+Characters outside ASCII are encoded as the `hexchar`s of their UTF-8 bytes.
+*/
+
+use crate::Error;
+
+// ------------------------------------------------------------------------------------------------
+// Public Functions
+// ------------------------------------------------------------------------------------------------
+
+///
+/// Returns `s` encoded as `xtext`.
+///
+pub(crate) fn encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        if is_xchar(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push(HEXCHAR);
+            encoded.push(HEX_DIGITS[usize::from(b >> 4)] as char);
+            encoded.push(HEX_DIGITS[usize::from(b & 0x0F)] as char);
+        }
+    }
+    encoded
+}
+
+///
+/// Returns the bytes encoded as the `xtext` in `s`, or `Error::InvalidXtext` with the offset of
+/// the first character that is neither an `xchar` nor the start of a `hexchar`.
+///
+pub(crate) fn decode(s: &str) -> Result<Vec<u8>, Error> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let b = bytes[index];
+        if is_xchar(b) {
+            decoded.push(b);
+            index += 1;
+        } else if b == HEXCHAR as u8 {
+            let high = bytes.get(index + 1).and_then(|b| hex_value(*b));
+            let low = bytes.get(index + 2).and_then(|b| hex_value(*b));
+            match (high, low) {
+                (Some(high), Some(low)) => decoded.push(high << 4 | low),
+                _ => return Err(Error::InvalidXtext(index)),
+            }
+            index += 3;
+        } else {
+            return Err(Error::InvalidXtext(index));
+        }
+    }
+    Ok(decoded)
+}
+
+// ------------------------------------------------------------------------------------------------
+// Private Functions
+// ------------------------------------------------------------------------------------------------
+
+fn is_xchar(b: u8) -> bool {
+    (b'!'..=b'~').contains(&b) && b != HEXCHAR as u8 && b != b'='
+}
+
+fn hex_value(b: u8) -> Option<u8> {
+    match b {
+        b'0'..=b'9' => Some(b - b'0'),
+        b'A'..=b'F' => Some(b - b'A' + 10),
+        _ => None,
+    }
+}
+
+// ------------------------------------------------------------------------------------------------
+
+const HEXCHAR: char = '+';
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";